@@ -0,0 +1,63 @@
+//! Compares `BitSet` against `HashSet<usize>` for the insert / lookup / count
+//! pattern used when tracking seen states. Run with `cargo bench --bench bitset`.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc25::bitset::BitSet;
+use aoc25::fmt::Elapsed;
+
+const VALUES: usize = 1_000_000;
+const ITERATIONS: u32 = 20;
+
+fn values() -> Vec<usize> {
+    // Deterministic scatter over a range four times the value count.
+    (0..VALUES)
+        .map(|i| (i * 2_654_435_761) % (VALUES * 4))
+        .collect()
+}
+
+fn time<F: FnMut() -> usize>(name: &str, mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let duration = start.elapsed();
+    println!(
+        "{:<24} total {}, average {}",
+        name,
+        Elapsed(duration),
+        Elapsed(duration / ITERATIONS)
+    );
+    duration
+}
+
+fn main() {
+    let values = values();
+
+    time("bitset insert", || {
+        let mut set = BitSet::with_capacity(VALUES * 4);
+        values.iter().filter(|&&v| set.insert(v)).count()
+    });
+    time("hashset insert", || {
+        let mut set = HashSet::with_capacity(VALUES);
+        values.iter().filter(|&&v| set.insert(v)).count()
+    });
+
+    let bitset: BitSet = values.iter().copied().collect();
+    let hashset: HashSet<usize> = values.iter().copied().collect();
+    time("bitset contains", || {
+        (0..VALUES * 4).filter(|v| bitset.contains(*v)).count()
+    });
+    time("hashset contains", || {
+        (0..VALUES * 4).filter(|v| hashset.contains(v)).count()
+    });
+
+    time("bitset union + count", || {
+        let mut union = bitset.clone();
+        union.union_with(&bitset);
+        union.len()
+    });
+    time("hashset union + count", || hashset.union(&hashset).count());
+}
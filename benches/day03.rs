@@ -0,0 +1,70 @@
+//! Compares rescanning `BatteryLine::largest_number` against building a
+//! `BatteryLineIndex` once and querying it for several digit counts. Run
+//! with `cargo bench --bench day03`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc25::days::day03::{BatteryLine, BatteryLineIndex, parse_battery_line};
+use aoc25::fmt::Elapsed;
+
+const LINES: usize = 200;
+const LINE_LENGTH: usize = 100;
+const ITERATIONS: u32 = 50;
+const QUERIES: [u32; 4] = [2, 4, 8, 12];
+
+fn lines() -> Vec<BatteryLine> {
+    // Deterministic digits 1-9, like the puzzle input.
+    (0..LINES)
+        .map(|l| {
+            let line: String = (0..LINE_LENGTH)
+                .map(|i| char::from(b'1' + ((l * 7_919 + i * 104_729) % 9) as u8))
+                .collect();
+            parse_battery_line(&line).expect("battery line")
+        })
+        .collect()
+}
+
+fn time<F: FnMut() -> u64>(name: &str, mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let duration = start.elapsed();
+    println!(
+        "{:<24} total {}, average {}",
+        name,
+        Elapsed(duration),
+        Elapsed(duration / ITERATIONS)
+    );
+    duration
+}
+
+fn main() {
+    let lines = lines();
+
+    time("rescan", || {
+        lines
+            .iter()
+            .flat_map(|line| QUERIES.map(|d| line.largest_number(d).expect("largest")))
+            .sum()
+    });
+    time("index build + query", || {
+        lines
+            .iter()
+            .map(|line| BatteryLineIndex::new(line).expect("index"))
+            .flat_map(|index| QUERIES.map(|d| index.largest_number(d as usize).expect("largest")))
+            .sum()
+    });
+
+    let indexes: Vec<BatteryLineIndex> = lines
+        .iter()
+        .map(|line| BatteryLineIndex::new(line).expect("index"))
+        .collect();
+    time("index query only", || {
+        indexes
+            .iter()
+            .flat_map(|index| QUERIES.map(|d| index.largest_number(d as usize).expect("largest")))
+            .sum()
+    });
+}
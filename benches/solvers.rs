@@ -0,0 +1,62 @@
+//! Criterion benchmarks of the solver hot paths on the test inputs under
+//! `data/`: `id_is_valid` over every ID in the day 2 ranges, the day 2
+//! range scan sequentially and in parallel, `largest_number` on each day 3
+//! line and `State::apply_multiple` over the day 1 instructions. Run with
+//! `cargo bench --bench solvers`.
+
+use std::hint::black_box;
+
+use aoc25::days::day01::{State, ZeroCounting, read_instructions_file};
+use aoc25::days::day02::{
+    self, calc_count_sum, calc_count_sum_parallel, id_is_valid, parse_input_file,
+};
+use aoc25::days::day03::read_input_file;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn day02(c: &mut Criterion) {
+    let ranges = parse_input_file("data/day02/test_input.txt").expect("day 2 test input");
+    for mode in [day02::Mode::Two, day02::Mode::Multiple] {
+        c.bench_function(&format!("id_is_valid ({})", mode.name()), |b| {
+            b.iter(|| {
+                ranges
+                    .iter()
+                    .flat_map(|range| range.ids())
+                    .filter(|&id| !id_is_valid(black_box(id), mode))
+                    .count()
+            })
+        });
+        c.bench_function(&format!("calc_count_sum ({})", mode.name()), |b| {
+            b.iter(|| calc_count_sum(black_box(&ranges), mode).map(|scan| scan.totals()))
+        });
+        c.bench_function(&format!("calc_count_sum_parallel ({})", mode.name()), |b| {
+            b.iter(|| calc_count_sum_parallel(black_box(&ranges), mode).map(|scan| scan.totals()))
+        });
+    }
+}
+
+fn day03(c: &mut Criterion) {
+    let lines = read_input_file("data/day03/test_input.txt").expect("day 3 test input");
+    for digits in [2, 12] {
+        c.bench_function(&format!("largest_number ({} digits)", digits), |b| {
+            b.iter(|| {
+                black_box(&lines)
+                    .iter()
+                    .map(|line| line.largest_number(digits).expect("largest"))
+                    .sum::<u64>()
+            })
+        });
+    }
+}
+
+fn day01(c: &mut Criterion) {
+    let instructions =
+        read_instructions_file("data/day01/test_input.txt").expect("day 1 test input");
+    for counting in [ZeroCounting::After, ZeroCounting::Both] {
+        c.bench_function(&format!("apply_multiple ({})", counting), |b| {
+            b.iter(|| State::new().apply_multiple(black_box(&instructions).clone(), counting))
+        });
+    }
+}
+
+criterion_group!(benches, day02, day03, day01);
+criterion_main!(benches);
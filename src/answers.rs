@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::result::AocResult;
+use crate::run::DayAnswers;
+
+/// Where verified answers are kept unless told otherwise.
+pub const ANSWERS_PATH: &str = "data/answers.toml";
+
+/// Deletes the verified answers at `ANSWERS_PATH`, or only lists them if
+/// `dry_run`, returning the path if there were any.
+pub fn clean(dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    let path = PathBuf::from(ANSWERS_PATH);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    if !dry_run {
+        std::fs::remove_file(&path)?;
+    }
+    Ok(vec![path])
+}
+
+/// Verified answers by day and part, kept as TOML:
+///
+/// ```toml
+/// [day01]
+/// part1 = "3"
+/// part2 = "6"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    known: BTreeMap<(u32, u32), String>,
+}
+
+/// How a fresh answer compares with the stored one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// No answer is stored for the part.
+    Unverified,
+    Matches,
+    Regression {
+        expected: String,
+    },
+}
+
+impl Answers {
+    /// The answers in the file at `path`, or none if it doesn't exist.
+    pub fn load(path: &Path) -> AocResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|e| AocError::ParseError(format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(AocError::io(path, e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        std::fs::write(path, self.to_string()).map_err(|e| AocError::io(path, e))
+    }
+
+    /// Reads the TOML written by `Display`: `[dayNN]` tables of
+    /// `partN = "answer"` keys, with blank lines and `#` comments allowed.
+    pub fn parse(text: &str) -> AocResult<Self> {
+        let mut answers = Self::default();
+        let mut day = None;
+        for (number, line) in text.lines().enumerate() {
+            let invalid = |what: &str| {
+                AocError::ParseError(format!("line {}: {} in '{}'", number + 1, what, line))
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let parsed = table.strip_prefix("day").and_then(|d| d.parse().ok());
+                day = Some(parsed.ok_or_else(|| invalid("expected [dayNN]"))?);
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected partN = \"answer\""))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(|| invalid("expected part1 or part2"))?;
+            let value = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .filter(|v| !v.contains(['"', '\\']))
+                .ok_or_else(|| invalid("expected a quoted answer"))?;
+            let day = day.ok_or_else(|| invalid("answer before any [dayNN]"))?;
+            answers.record(day, part, value);
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.known.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Stores `answer` as verified, replacing any answer stored before.
+    pub fn record(&mut self, day: u32, part: u32, answer: &str) {
+        self.known.insert((day, part), answer.to_string());
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Check {
+        match self.get(day, part) {
+            None => Check::Unverified,
+            Some(expected) if expected == answer => Check::Matches,
+            Some(expected) => Check::Regression {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    /// Every answer solved in `results` checked against the stored ones,
+    /// as `(day, part, answer, check)`.
+    pub fn check_all<'a>(&self, results: &'a [DayAnswers]) -> Vec<(u32, u32, &'a str, Check)> {
+        let mut checks = Vec::new();
+        for result in results {
+            for &part in result.part.numbers() {
+                if let Some(answer) = result.answer(part) {
+                    let check = self.check(result.day, part, &answer.0);
+                    checks.push((result.day, part, answer.0.as_str(), check));
+                }
+            }
+        }
+        checks
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut last_day = None;
+        for (&(day, part), answer) in &self.known {
+            if last_day != Some(day) {
+                if last_day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{:02}]", day)?;
+                last_day = Some(day);
+            }
+            writeln!(f, "part{} = \"{}\"", part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::{Part, solve_all};
+
+    #[test]
+    fn test_round_trip() {
+        let text = "# verified\n[day01]\npart1 = \"3\"\npart2 = \"6\"\n\n[day03]\npart2 = \"3121910778619\"\n";
+        let answers = Answers::parse(text).expect("answers");
+        assert_eq!(answers.get(1, 2), Some("6"));
+        assert_eq!(answers.get(3, 1), None);
+        assert_eq!(
+            answers.to_string(),
+            "[day01]\npart1 = \"3\"\npart2 = \"6\"\n\n[day03]\npart2 = \"3121910778619\"\n"
+        );
+        assert_eq!(
+            Answers::parse(&answers.to_string()).expect("answers"),
+            answers
+        );
+
+        assert!(Answers::parse("part1 = \"3\"\n").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 3\n").is_err());
+        assert!(Answers::parse("[week1]\n").is_err());
+        assert!(Answers::parse("[day01]\nanswer = \"3\"\n").is_err());
+    }
+
+    #[test]
+    fn test_check_all() {
+        let mut answers = Answers::default();
+        answers.record(1, 1, "3");
+        answers.record(1, 2, "7");
+        let results = solve_all(
+            &[(
+                1,
+                "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n".to_string(),
+            )],
+            Part::Both,
+            false,
+        );
+        assert_eq!(
+            answers.check_all(&results),
+            vec![
+                (1, 1, "3", Check::Matches),
+                (
+                    1,
+                    2,
+                    "6",
+                    Check::Regression {
+                        expected: "7".to_string()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(answers.check(2, 1, "1"), Check::Unverified);
+    }
+}
@@ -0,0 +1,358 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::AocError;
+use crate::json::{self, ToJson};
+use crate::result::AocResult;
+use crate::run::RunReport;
+
+/// Where `--out-dir` puts run directories when given no path.
+pub const RUNS_DIR: &str = "runs";
+
+/// Name of the manifest written into every run directory.
+pub const MANIFEST: &str = "manifest.json";
+
+/// Kind of the `report.json` every run directory ends with.
+pub const REPORT: &str = "report";
+
+/// A directory holding everything one run wrote, such as traces, dumps
+/// and plots, next to a `manifest.json` describing the run. The directory
+/// is only created once something is written to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunDir {
+    path: PathBuf,
+    day: u32,
+    input: String,
+    args: Vec<String>,
+    started: String,
+    artifacts: Vec<(&'static str, String)>,
+}
+
+impl RunDir {
+    /// A run directory for `day` under `base`, named for the current UTC
+    /// time, e.g. `runs/20251201T050000Z`.
+    pub fn new(base: &Path, day: u32, input: &str) -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let started = timestamp(seconds);
+        let mut path = base.join(&started);
+        let mut suffix = 1;
+        while path.exists() {
+            suffix += 1;
+            path = base.join(format!("{}-{}", started, suffix));
+        }
+        RunDir {
+            path,
+            day,
+            input: input.to_string(),
+            args: std::env::args().skip(1).collect(),
+            started,
+            artifacts: vec![],
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Where to write the `kind` of artifact asked for as `name`: its file
+    /// name inside the run directory. Records it in the manifest.
+    pub fn place(&mut self, kind: &'static str, name: &str) -> AocResult<String> {
+        let file = Path::new(name)
+            .file_name()
+            .map_or_else(|| kind.to_string(), |f| f.to_string_lossy().into_owned());
+        self.create()?;
+        self.artifacts.push((kind, file.clone()));
+        Ok(self.path.join(file).display().to_string())
+    }
+
+    /// The manifest for a run that ended with `report`.
+    pub fn manifest(&self, report: &RunReport) -> String {
+        let artifacts: Vec<String> = self
+            .artifacts
+            .iter()
+            .map(|(kind, file)| json::object(&[("kind", kind.to_json()), ("path", file.to_json())]))
+            .collect();
+        json::object(&[
+            ("day", self.day.to_json()),
+            ("started", self.started.to_json()),
+            ("version", env!("CARGO_PKG_VERSION").to_json()),
+            ("input", self.input.to_json()),
+            ("args", self.args.to_json()),
+            ("mode", report.mode.to_json()),
+            ("answer", report.answer.to_json()),
+            ("artifacts", format!("[{}]", artifacts.join(","))),
+        ])
+    }
+
+    /// Writes `report` as `report.json` and the manifest, returning the
+    /// directory.
+    pub fn finish(mut self, report: &RunReport) -> AocResult<PathBuf> {
+        let path = self.place(REPORT, "report.json")?;
+        write(Path::new(&path), report.to_json() + "\n")?;
+        write(&self.path.join(MANIFEST), self.manifest(report) + "\n")?;
+        Ok(self.path)
+    }
+
+    fn create(&self) -> AocResult<()> {
+        std::fs::create_dir_all(&self.path).map_err(|e| AocError::io(&self.path, e))
+    }
+}
+
+fn write(path: &Path, contents: String) -> AocResult<()> {
+    std::fs::write(path, contents).map_err(|e| AocError::io(path, e))
+}
+
+/// `path` as given, or moved into `run` when there is a run directory.
+pub fn place(run: &mut Option<RunDir>, kind: &'static str, path: &str) -> AocResult<String> {
+    match run {
+        Some(run) => run.place(kind, path),
+        None => Ok(path.to_string()),
+    }
+}
+
+/// Writes `report` and the manifest if there is a run directory, telling
+/// the user where it went.
+pub fn finish(run: Option<RunDir>, report: &RunReport) -> AocResult<()> {
+    if let Some(run) = run {
+        let path = run.finish(report)?;
+        eprintln!("Run artifacts written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Run directories earlier `--out-dir` runs left under `base`. A missing
+/// `base` has none.
+pub fn runs(base: &Path) -> io::Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(base) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut found = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            found.push(path);
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Deletes every run directory under `base`, or only lists them if
+/// `dry_run`, returning their paths.
+pub fn clean(base: &Path, dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    let found = runs(base)?;
+    if !dry_run {
+        for path in &found {
+            std::fs::remove_dir_all(path)?;
+        }
+    }
+    Ok(found)
+}
+
+/// The files of `kind` listed in the manifests of the run directories
+/// under `base`, leaving out any already removed.
+pub fn artifacts(base: &Path, kind: &str) -> io::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    for run in runs(base)? {
+        let manifest = match std::fs::read_to_string(run.join(MANIFEST)) {
+            Ok(manifest) => manifest,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        found.extend(
+            listed(&manifest)
+                .into_iter()
+                .filter(|(listed_kind, _)| listed_kind == kind)
+                .map(|(_, file)| run.join(file))
+                .filter(|path| path.is_file()),
+        );
+    }
+    Ok(found)
+}
+
+/// Deletes the files of `kind` from the run directories under `base`, or
+/// only lists them if `dry_run`, returning their paths. The rest of each
+/// run directory is kept.
+pub fn clean_artifacts(base: &Path, kind: &str, dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    let found = artifacts(base, kind)?;
+    if !dry_run {
+        for path in &found {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(found)
+}
+
+/// Deletes the `report.json` of every run directory under `base`, or only
+/// lists them if `dry_run`.
+pub fn clean_reports(base: &Path, dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    clean_artifacts(base, REPORT, dry_run)
+}
+
+/// The `(kind, file)` pairs a manifest's `artifacts` list holds. Files
+/// are only ever names inside the run directory, so anything else is
+/// skipped.
+fn listed(manifest: &str) -> Vec<(String, String)> {
+    let mut listed = Vec::new();
+    let Some((_, mut rest)) = manifest.split_once("\"artifacts\":[") else {
+        return listed;
+    };
+    while let Some(entry) = rest.strip_prefix("{\"kind\":") {
+        let Some((kind, after)) = json::read_string(entry) else {
+            break;
+        };
+        let Some((file, after)) = after.strip_prefix(",\"path\":").and_then(json::read_string)
+        else {
+            break;
+        };
+        if Path::new(&file)
+            .file_name()
+            .is_some_and(|name| name == file.as_str())
+        {
+            listed.push((kind, file));
+        }
+        rest = after.strip_prefix('}').unwrap_or(after);
+        rest = rest.strip_prefix(',').unwrap_or(rest);
+    }
+    listed
+}
+
+/// `seconds` since the Unix epoch as a compact UTC timestamp,
+/// `YYYYMMDDTHHMMSSZ`.
+pub fn timestamp(seconds: u64) -> String {
+    let (days, rest) = (seconds / 86400, seconds % 86400);
+    // Howard Hinnant's civil-from-days, shifted so years start in March.
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest / 60 % 60,
+        rest % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0), "19700101T000000Z");
+        assert_eq!(timestamp(951_782_400), "20000229T000000Z");
+        assert_eq!(timestamp(1_764_565_200), "20251201T050000Z");
+    }
+
+    #[test]
+    fn test_run_dir() {
+        let base = std::env::temp_dir().join(format!("aoc25-artifacts-{}", std::process::id()));
+        let mut run = RunDir::new(&base, 1, "data/day01/input.txt");
+        assert!(!run.path().exists());
+        let trace = run.place("trace", "out/trace.jsonl").expect("trace path");
+        assert_eq!(Path::new(&trace), run.path().join("trace.jsonl"));
+        assert!(run.path().is_dir());
+
+        let report = RunReport {
+            day: 1,
+            part: 1,
+            mode: "after".to_string(),
+            settings: vec![],
+            metrics: vec![],
+            answer: "3".to_string(),
+            phases: vec![],
+        };
+        let manifest = run.manifest(&report);
+        assert!(manifest.contains("\"input\":\"data/day01/input.txt\""));
+        assert!(manifest.contains("\"answer\":\"3\""));
+        assert!(manifest.contains("{\"kind\":\"trace\",\"path\":\"trace.jsonl\"}"));
+
+        let dir = run.finish(&report).expect("finish");
+        assert!(dir.join(MANIFEST).is_file());
+        assert!(dir.join("report.json").is_file());
+        assert_ne!(RunDir::new(&base, 1, "-").path(), dir);
+        std::fs::remove_dir_all(&base).expect("clean up");
+
+        assert!(runs(&base).expect("no runs").is_empty());
+        let mut none = None;
+        assert_eq!(
+            place(&mut none, "trace", "t.jsonl").expect("path"),
+            "t.jsonl"
+        );
+    }
+
+    #[test]
+    fn test_clean() {
+        let base = std::env::temp_dir().join(format!("aoc25-clean-runs-{}", std::process::id()));
+        let first = base.join("20251201T050000Z");
+        std::fs::create_dir_all(&first).expect("run directory");
+        std::fs::write(first.join(MANIFEST), "{}").expect("manifest");
+        std::fs::write(base.join("notes.txt"), "kept").expect("notes");
+
+        assert_eq!(clean(&base, true).expect("dry run"), vec![first.clone()]);
+        assert!(first.is_dir());
+        assert_eq!(clean(&base, false).expect("clean"), vec![first.clone()]);
+        assert!(!first.exists());
+        assert!(base.join("notes.txt").is_file());
+        std::fs::remove_dir_all(&base).expect("clean up");
+    }
+
+    #[test]
+    fn test_clean_artifacts() {
+        let base =
+            std::env::temp_dir().join(format!("aoc25-clean-artifacts-{}", std::process::id()));
+        let mut run = RunDir::new(&base, 1, "-");
+        let trace = PathBuf::from(run.place("trace", "out/trace.jsonl").expect("trace"));
+        std::fs::write(&trace, "{}\n").expect("trace");
+        let report = RunReport {
+            day: 1,
+            part: 1,
+            mode: "after".to_string(),
+            settings: vec![],
+            metrics: vec![],
+            answer: "3".to_string(),
+            phases: vec![],
+        };
+        let dir = run.finish(&report).expect("finish");
+
+        assert_eq!(
+            listed(&std::fs::read_to_string(dir.join(MANIFEST)).expect("manifest")),
+            [
+                ("trace".to_string(), "trace.jsonl".to_string()),
+                ("report".to_string(), "report.json".to_string())
+            ]
+        );
+        assert!(listed("{\"artifacts\":[{\"kind\":\"trace\",\"path\":\"../x\"}]}").is_empty());
+        assert_eq!(
+            clean_artifacts(&base, "trace", true).expect("dry run"),
+            vec![trace.clone()]
+        );
+        assert!(trace.is_file());
+        assert_eq!(
+            clean_artifacts(&base, "trace", false).expect("clean"),
+            vec![trace.clone()]
+        );
+        assert!(!trace.exists());
+        assert!(artifacts(&base, "trace").expect("none left").is_empty());
+        assert_eq!(
+            clean_reports(&base, false).expect("reports"),
+            [dir.join("report.json")]
+        );
+        assert!(dir.join(MANIFEST).is_file());
+        std::fs::remove_dir_all(&base).expect("clean up");
+    }
+}
@@ -0,0 +1,517 @@
+use std::fmt;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::fmt::Elapsed;
+use crate::json::{self, ToJson};
+use crate::result::AocResult;
+use crate::run::Part;
+use crate::solver::Solver;
+
+/// How long a benchmark runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Untimed runs first, to warm caches and let the CPU clock up.
+    pub warmup: u32,
+    pub iterations: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 10,
+            iterations: 1000,
+        }
+    }
+}
+
+/// Summary of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Statistics of `samples`, or `None` if there are none.
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        // Nearest rank: the smallest sample at least 95% of them reach.
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Stats {
+            min,
+            median,
+            p95,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Per-iteration timings of one benchmarked function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkResult {
+    warmup: u32,
+    samples: Vec<Duration>,
+}
+
+impl BenchmarkResult {
+    /// Runs `f` `options.warmup` times untimed, then times each of
+    /// `options.iterations` runs.
+    pub fn run<T, F>(options: &BenchOptions, mut f: F) -> Self
+    where
+        F: FnMut() -> T,
+    {
+        for _ in 0..options.warmup {
+            black_box(f());
+        }
+        let samples = (0..options.iterations)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(f());
+                start.elapsed()
+            })
+            .collect();
+        BenchmarkResult {
+            warmup: options.warmup,
+            samples,
+        }
+    }
+
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    /// Time spent in the timed iterations.
+    pub fn duration(&self) -> Duration {
+        self.samples.iter().sum()
+    }
+
+    pub fn stats(&self) -> Option<Stats> {
+        Stats::of(&self.samples)
+    }
+}
+
+impl fmt::Display for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Iterations: {} (after {} warm-up)",
+            self.samples.len(),
+            self.warmup
+        )?;
+        writeln!(f, "Duration: {}", Elapsed(self.duration()))?;
+        if let Some(stats) = self.stats() {
+            writeln!(f, "Min:      {}", Elapsed(stats.min))?;
+            writeln!(f, "Median:   {}", Elapsed(stats.median))?;
+            writeln!(f, "Mean:     {}", Elapsed(stats.mean))?;
+            writeln!(f, "p95:      {}", Elapsed(stats.p95))?;
+            write!(f, "Stddev:   {}", Elapsed(stats.stddev))?;
+        }
+        Ok(())
+    }
+}
+
+impl ToJson for BenchmarkResult {
+    fn to_json(&self) -> String {
+        let micros = |d: Duration| d.as_micros().to_string();
+        let mut fields = vec![
+            ("iterations", self.samples.len().to_string()),
+            ("warmup", self.warmup.to_json()),
+            ("duration_us", micros(self.duration())),
+        ];
+        if let Some(stats) = self.stats() {
+            fields.extend([
+                ("min_us", micros(stats.min)),
+                ("median_us", micros(stats.median)),
+                ("mean_us", micros(stats.mean)),
+                ("p95_us", micros(stats.p95)),
+                ("stddev_us", micros(stats.stddev)),
+            ]);
+        }
+        json::object(&fields)
+    }
+}
+
+/// Timings of a day's stages, each benchmarked on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchReport {
+    pub day: u32,
+    pub stages: Vec<(&'static str, BenchmarkResult)>,
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} benchmark", self.day)?;
+        for (name, result) in &self.stages {
+            write!(f, "\n\n{}:\n{}", name, result)?;
+        }
+        Ok(())
+    }
+}
+
+/// `{"day":2,"stages":{"scan":{"iterations":1000,...},...}}`
+impl ToJson for BenchReport {
+    fn to_json(&self) -> String {
+        let stages: Vec<(&str, String)> = self
+            .stages
+            .iter()
+            .map(|(name, result)| (*name, result.to_json()))
+            .collect();
+        json::object(&[
+            ("day", self.day.to_json()),
+            ("stages", json::object(&stages)),
+        ])
+    }
+}
+
+/// Where `--save-baseline` keeps results, one file per day and name.
+pub const BASELINE_DIR: &str = "target/baselines";
+
+/// The file baseline `name` of `day` is kept in.
+pub fn baseline_path(day: u32, name: &str) -> PathBuf {
+    Path::new(BASELINE_DIR)
+        .join(format!("day{:02}", day))
+        .join(format!("{}.tsv", name))
+}
+
+/// Baselines saved by earlier `--save-baseline` runs.
+pub fn baselines() -> io::Result<Vec<PathBuf>> {
+    let days = match std::fs::read_dir(BASELINE_DIR) {
+        Ok(days) => days,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut found = Vec::new();
+    for day in days {
+        let day = day?.path();
+        if !day.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(day)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "tsv") {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Deletes every saved baseline, or only lists them if `dry_run`,
+/// returning their paths.
+pub fn clean_baselines(dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    let found = baselines()?;
+    if !dry_run {
+        for path in &found {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(found)
+}
+
+/// The median time of each stage of a benchmark, kept to compare later
+/// runs against. Stored as `stage<TAB>nanoseconds` lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    pub stages: Vec<(String, Duration)>,
+}
+
+impl Baseline {
+    pub fn of(report: &BenchReport) -> Self {
+        let stages = report
+            .stages
+            .iter()
+            .filter_map(|(name, result)| Some((name.to_string(), result.stats()?.median)))
+            .collect();
+        Baseline { stages }
+    }
+
+    pub fn load(path: &Path) -> AocResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| AocError::io(path, e))?;
+        Self::parse(&text).map_err(|e| AocError::ParseError(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        let io_error = |e: std::io::Error| AocError::io(path, e);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        std::fs::write(path, self.to_string()).map_err(io_error)
+    }
+
+    pub fn parse(text: &str) -> AocResult<Self> {
+        let stages = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (name, nanos) = line
+                    .split_once('\t')
+                    .and_then(|(name, nanos)| Some((name, nanos.trim().parse().ok()?)))
+                    .ok_or_else(|| {
+                        AocError::ParseError(format!(
+                            "expected stage and nanoseconds in '{}'",
+                            line
+                        ))
+                    })?;
+                Ok((name.to_string(), Duration::from_nanos(nanos)))
+            })
+            .collect::<AocResult<_>>()?;
+        Ok(Baseline { stages })
+    }
+
+    pub fn get(&self, stage: &str) -> Option<Duration> {
+        self.stages
+            .iter()
+            .find(|(name, _)| name == stage)
+            .map(|&(_, median)| median)
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, median) in &self.stages {
+            writeln!(f, "{}\t{}", name, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// How one stage's median moved against the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageChange {
+    pub stage: String,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl StageChange {
+    /// The change in percent, negative when faster.
+    pub fn percent(&self) -> f64 {
+        (self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+/// A benchmark compared with a baseline, stage by stage. Stages missing
+/// from either side are left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub changes: Vec<StageChange>,
+    /// Slowdown in percent past which a stage counts as regressed.
+    pub threshold: f64,
+}
+
+impl Comparison {
+    pub fn new(baseline: &Baseline, current: &Baseline, threshold: f64) -> Self {
+        let changes = current
+            .stages
+            .iter()
+            .filter_map(|(stage, after)| {
+                Some(StageChange {
+                    stage: stage.clone(),
+                    before: baseline.get(stage)?,
+                    after: *after,
+                })
+            })
+            .collect();
+        Comparison { changes, threshold }
+    }
+
+    pub fn regressed(&self, change: &StageChange) -> bool {
+        change.percent() > self.threshold
+    }
+
+    /// Whether no stage regressed.
+    pub fn passed(&self) -> bool {
+        !self.changes.iter().any(|change| self.regressed(change))
+    }
+}
+
+impl ToJson for Comparison {
+    fn to_json(&self) -> String {
+        let changes: Vec<String> = self
+            .changes
+            .iter()
+            .map(|change| {
+                json::object(&[
+                    ("stage", change.stage.to_json()),
+                    ("before_ns", change.before.as_nanos().to_string()),
+                    ("after_ns", change.after.as_nanos().to_string()),
+                    ("regressed", self.regressed(change).to_string()),
+                ])
+            })
+            .collect();
+        json::object(&[
+            ("passed", self.passed().to_string()),
+            ("changes", format!("[{}]", changes.join(","))),
+        ])
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{:<10} {:>12} -> {:>12} {:>+8.1}% {}",
+                change.stage,
+                Elapsed(change.before),
+                Elapsed(change.after),
+                change.percent(),
+                if self.regressed(change) { "FAIL" } else { "ok" }
+            )?;
+        }
+        write!(
+            f,
+            "{} (threshold {:+.1}%)",
+            if self.passed() { "PASS" } else { "FAIL" },
+            self.threshold
+        )
+    }
+}
+
+/// Benchmarks a day: parsing the input, then each part on the parsed
+/// input, so slow parsing can't hide in the part timings. Implemented for
+/// every `Solver`.
+pub trait Bench {
+    fn bench(&self, input: &str, part: Part, options: &BenchOptions) -> AocResult<BenchReport>;
+}
+
+impl<S: Solver + ?Sized> Bench for S {
+    fn bench(&self, input: &str, part: Part, options: &BenchOptions) -> AocResult<BenchReport> {
+        let parsed = self.parse(input)?;
+        let mut stages = vec![(
+            "parse",
+            BenchmarkResult::run(options, || self.parse(input).is_ok()),
+        )];
+        for &number in part.numbers() {
+            let solve = |parsed| match number {
+                1 => self.part1(parsed),
+                _ => self.part2(parsed),
+            };
+            solve(&parsed)?;
+            let name = if number == 1 { "part 1" } else { "part 2" };
+            stages.push((
+                name,
+                BenchmarkResult::run(options, || solve(&parsed).is_ok()),
+            ));
+        }
+        Ok(BenchReport {
+            day: self.day(),
+            stages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::of(&millis(&[5, 1, 3, 2, 4])).expect("stats");
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::of(&millis(&samples)).expect("stats");
+        assert_eq!(stats.median, Duration::from_micros(50_500));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+        assert!(Stats::of(&[]).is_none());
+    }
+
+    #[test]
+    fn test_baseline_comparison() {
+        let baseline = Baseline::parse("parse\t1000\npart 1\t2000\n").expect("baseline");
+        assert_eq!(baseline.get("part 1"), Some(Duration::from_micros(2)));
+        assert_eq!(
+            Baseline::parse(&baseline.to_string()).expect("round trip"),
+            baseline
+        );
+        assert!(Baseline::parse("parse 1000\n").is_err());
+
+        let current = Baseline::parse("parse\t900\npart 1\t2200\npart 2\t50\n").expect("current");
+        let comparison = Comparison::new(&baseline, &current, 5.0);
+        assert_eq!(comparison.changes.len(), 2);
+        assert!((comparison.changes[0].percent() + 10.0).abs() < 1e-9);
+        assert!(!comparison.passed());
+        assert!(comparison.to_string().ends_with("FAIL (threshold +5.0%)"));
+        assert!(Comparison::new(&baseline, &current, 15.0).passed());
+        assert_eq!(
+            comparison.to_json(),
+            "{\"passed\":false,\"changes\":[\
+             {\"stage\":\"parse\",\"before_ns\":1000,\"after_ns\":900,\"regressed\":false},\
+             {\"stage\":\"part 1\",\"before_ns\":2000,\"after_ns\":2200,\"regressed\":true}]}"
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!("aoc25-baseline-{}", std::process::id()))
+            .join("before.tsv");
+        baseline.save(&path).expect("save");
+        assert_eq!(Baseline::load(&path).expect("load"), baseline);
+        std::fs::remove_dir_all(path.parent().expect("dir")).expect("clean up");
+        assert_eq!(
+            baseline_path(2, "before"),
+            Path::new("target/baselines/day02/before.tsv")
+        );
+    }
+
+    #[test]
+    fn test_bench_solver() {
+        let mut calls = 0;
+        let options = BenchOptions {
+            warmup: 2,
+            iterations: 3,
+        };
+        let result = BenchmarkResult::run(&options, || calls += 1);
+        assert_eq!(calls, 5);
+        assert_eq!(result.samples().len(), 3);
+
+        let day01 = solver::find(1).expect("day 1");
+        let report = day01
+            .bench("L68\nL30\nR48\n", Part::Both, &options)
+            .expect("report");
+        let names: Vec<&str> = report.stages.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["parse", "part 1", "part 2"]);
+        assert!(
+            report
+                .to_string()
+                .starts_with("Day 1 benchmark\n\nparse:\n")
+        );
+        let json = report.to_json();
+        assert!(
+            json.starts_with("{\"day\":1,\"stages\":{\"parse\":{\"iterations\":3,\"warmup\":2,")
+        );
+        assert!(json.contains("\"part 2\":{"));
+        assert!(day01.bench("L68\nX\n", Part::One, &options).is_err());
+    }
+}
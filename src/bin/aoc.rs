@@ -0,0 +1,201 @@
+//! One entry point for every day: `aoc day01 --mode during`,
+//! `aoc day02 --bench`, or `aoc all` to solve them all. `aoc new-day 07`
+//! scaffolds the next day, `aoc fetch --day 7` downloads its input and
+//! `aoc submit --day 7 --part 1` posts an answer. `aoc tui` opens a
+//! dashboard for running days and spotting regressions.
+
+use std::path::Path;
+
+use aoc25::answers::ANSWERS_PATH;
+use aoc25::run::{Part, RunAllConfig, run_all};
+use aoc25::solver::COMMANDS;
+use aoc25::{dashboard, input, scaffold, submit};
+use clap::{Args, FromArgMatches};
+
+fn cli() -> clap::Command {
+    let mut cli = clap::Command::new("aoc")
+        .about("Advent of Code 2025 solutions")
+        .subcommand_required(true)
+        .arg_required_else_help(true);
+    for day in COMMANDS {
+        cli = cli.subcommand((day.command)().name(day.name).about(day.about));
+    }
+    cli.subcommand(
+        RunAllConfig::augment_args(clap::Command::new("all"))
+            .about("Solve every day from its input file"),
+    )
+    .subcommand(
+        clap::Command::new("new-day")
+            .about("Create a solver skeleton, data files and registry entry for a day")
+            .arg(
+                clap::Arg::new("day")
+                    .required(true)
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Day number, e.g. 07"),
+            )
+            .arg(
+                clap::Arg::new("title")
+                    .long("title")
+                    .default_value("Untitled")
+                    .help("The puzzle's title on the site"),
+            )
+            .arg(
+                clap::Arg::new("root")
+                    .long("root")
+                    .default_value(".")
+                    .help("Crate directory to generate into"),
+            ),
+    )
+    .subcommand(
+        clap::Command::new("fetch")
+            .about("Download a day's input using the session cookie in AOC_SESSION")
+            .arg(
+                clap::Arg::new("day")
+                    .long("day")
+                    .short('d')
+                    .required(true)
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Day number, e.g. 5"),
+            ),
+    )
+    .subcommand(
+        clap::Command::new("submit")
+            .about("Solve a part from the day's input and submit the answer")
+            .arg(
+                clap::Arg::new("day")
+                    .long("day")
+                    .short('d')
+                    .required(true)
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Day number, e.g. 5"),
+            )
+            .arg(
+                clap::Arg::new("part")
+                    .long("part")
+                    .short('p')
+                    .required(true)
+                    .value_parser(["1", "2"])
+                    .help("Part to submit"),
+            ),
+    )
+    .subcommand(
+        clap::Command::new("tui")
+            .about("Run days interactively and watch their answers and logs")
+            .arg(
+                clap::Arg::new("dir")
+                    .long("dir")
+                    .default_value("data")
+                    .help("Directory holding the dayNN inputs"),
+            )
+            .arg(
+                clap::Arg::new("answers")
+                    .long("answers")
+                    .default_value(ANSWERS_PATH)
+                    .help("Verified answers to check against"),
+            ),
+    )
+}
+
+fn new_day(matches: &clap::ArgMatches) {
+    let day = *matches.get_one::<u32>("day").expect("day is required");
+    let root = matches
+        .get_one::<String>("root")
+        .expect("root has a default");
+    let title = matches
+        .get_one::<String>("title")
+        .expect("title has a default");
+    match scaffold::new_day(Path::new(root), day, title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to create day {}: {}", day, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn fetch(matches: &clap::ArgMatches) {
+    let day = *matches.get_one::<u32>("day").expect("day is required");
+    match input::download(day) {
+        Ok((path, true)) => println!("Wrote {}", path.display()),
+        Ok((path, false)) => println!("{} already exists", path.display()),
+        Err(e) => {
+            eprintln!("Failed to download day {}: {}", day, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn submit(matches: &clap::ArgMatches) {
+    let day = *matches.get_one::<u32>("day").expect("day is required");
+    let part = matches.get_one::<String>("part").expect("part is required");
+    match submit::submit(day, Part::from(part.as_str())) {
+        Ok((submission, posted)) => {
+            let when = if posted { "" } else { " (recorded earlier)" };
+            println!(
+                "Day {} part {}: {} is {}{}",
+                day, submission.part, submission.answer, submission.verdict, when
+            );
+            if submission.verdict != submit::Verdict::Correct {
+                std::process::exit(2);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to submit day {} part {}: {}", day, part, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn tui(matches: &clap::ArgMatches) {
+    let dir = matches.get_one::<String>("dir").expect("dir has a default");
+    let answers = matches
+        .get_one::<String>("answers")
+        .expect("answers has a default");
+    if let Err(e) = dashboard::run(
+        dir.into(),
+        answers.into(),
+        tracing::level_filters::LevelFilter::INFO,
+    ) {
+        eprintln!("Dashboard failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    let matches = cli().get_matches();
+    let result = match matches.subcommand() {
+        Some(("all", matches)) => RunAllConfig::from_arg_matches(matches).map(|config| {
+            if !run_all(&config) {
+                std::process::exit(1);
+            }
+        }),
+        Some(("new-day", matches)) => {
+            new_day(matches);
+            Ok(())
+        }
+        Some(("fetch", matches)) => {
+            fetch(matches);
+            Ok(())
+        }
+        Some(("submit", matches)) => {
+            submit(matches);
+            Ok(())
+        }
+        Some(("tui", matches)) => {
+            tui(matches);
+            Ok(())
+        }
+        Some((name, matches)) => match COMMANDS.iter().find(|day| day.name == name) {
+            Some(day) => aoc25::cli::exit((day.run)(matches)),
+            None => unreachable!("clap only accepts registered subcommands"),
+        },
+        None => unreachable!("a subcommand is required"),
+    };
+    if let Err(e) = result {
+        e.exit();
+    }
+}
@@ -0,0 +1,316 @@
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Indices of the set bits in `mask`, lowest first.
+pub fn bits(mut mask: u64) -> impl Iterator<Item = u32> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+        let bit = mask.trailing_zeros();
+        mask &= mask - 1;
+        Some(bit)
+    })
+}
+
+/// Every submask of `mask`, including `mask` itself and `0`, in decreasing
+/// numeric order.
+pub fn submasks(mask: u64) -> impl Iterator<Item = u64> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = if current == 0 {
+            None
+        } else {
+            Some((current - 1) & mask)
+        };
+        Some(current)
+    })
+}
+
+fn word_iter(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &w)| bits(w).map(move |b| i * WORD_BITS + b as usize))
+}
+
+/// Bitset holding values `0..N * 64` inline, for small state spaces that
+/// need to be `Copy` and hashable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBitSet<const N: usize> {
+    words: [u64; N],
+}
+
+impl<const N: usize> Default for FixedBitSet<N> {
+    fn default() -> Self {
+        FixedBitSet { words: [0; N] }
+    }
+}
+
+impl<const N: usize> FixedBitSet<N> {
+    pub const CAPACITY: usize = N * WORD_BITS;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Panics if `value` is not below `CAPACITY`.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / WORD_BITS, value % WORD_BITS);
+        let was_set = self.words[word] & (1 << bit) != 0;
+        self.words[word] |= 1 << bit;
+        !was_set
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, bit) = (value / WORD_BITS, value % WORD_BITS);
+        let was_set = self.words[word] & (1 << bit) != 0;
+        self.words[word] &= !(1 << bit);
+        was_set
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.words[value / WORD_BITS] & (1 << (value % WORD_BITS)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; N];
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= !b;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        word_iter(&self.words)
+    }
+}
+
+/// Heap-allocated bitset that grows to fit the largest value inserted.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bits: usize) -> Self {
+        BitSet {
+            words: vec![0; bits.div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / WORD_BITS, value % WORD_BITS);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let was_set = self.words[word] & (1 << bit) != 0;
+        self.words[word] |= 1 << bit;
+        !was_set
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / WORD_BITS, value % WORD_BITS);
+        match self.words.get_mut(word) {
+            Some(w) => {
+                let was_set = *w & (1 << bit) != 0;
+                *w &= !(1 << bit);
+                was_set
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / WORD_BITS)
+            .is_some_and(|w| w & (1 << (value % WORD_BITS)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.words.truncate(other.words.len());
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
+    }
+
+    pub fn difference_with(&mut self, other: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= !b;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        word_iter(&self.words)
+    }
+}
+
+/// Equality ignores trailing zero words, so sets that grew differently but
+/// hold the same values compare equal.
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let (short, long) = if self.words.len() <= other.words.len() {
+            (&self.words, &other.words)
+        } else {
+            (&other.words, &self.words)
+        };
+        short == &long[..short.len()] && long[short.len()..].iter().all(|&w| w == 0)
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits() {
+        let collected: Vec<u32> = bits(0b1010_0101).collect();
+        assert_eq!(collected, vec![0, 2, 5, 7]);
+        assert_eq!(bits(0).count(), 0);
+        assert_eq!(bits(u64::MAX).count(), 64);
+    }
+
+    #[test]
+    fn test_submasks() {
+        let collected: Vec<u64> = submasks(0b101).collect();
+        assert_eq!(collected, vec![0b101, 0b100, 0b001, 0]);
+        assert_eq!(submasks(0).collect::<Vec<u64>>(), vec![0]);
+    }
+
+    #[test]
+    fn test_fixed_bitset() {
+        let mut set = FixedBitSet::<2>::new();
+        assert_eq!(FixedBitSet::<2>::CAPACITY, 128);
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(100));
+        assert!(set.contains(100));
+        assert!(!set.contains(500));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![3, 100]);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_fixed_set_operations() {
+        let mut a = FixedBitSet::<1>::new();
+        let mut b = FixedBitSet::<1>::new();
+        for v in [1, 2, 3] {
+            a.insert(v);
+        }
+        for v in [2, 3, 4] {
+            b.insert(v);
+        }
+        let mut union = a;
+        union.union_with(&b);
+        assert_eq!(union.len(), 4);
+        let mut both = a;
+        both.intersect_with(&b);
+        assert_eq!(both.iter().collect::<Vec<usize>>(), vec![2, 3]);
+        a.difference_with(&b);
+        assert_eq!(a.iter().collect::<Vec<usize>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_growable_bitset() {
+        let mut set = BitSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(1000));
+        assert!(set.contains(1000));
+        assert!(!set.contains(999));
+        assert!(!set.contains(100_000));
+        assert!(!set.remove(100_000));
+        assert_eq!(set.len(), 1);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_growable_set_operations() {
+        let a: BitSet = [1, 70, 200].into_iter().collect();
+        let b: BitSet = [70, 300].into_iter().collect();
+        let mut union = a.clone();
+        union.union_with(&b);
+        assert_eq!(union.iter().collect::<Vec<usize>>(), vec![1, 70, 200, 300]);
+        let mut both = a.clone();
+        both.intersect_with(&b);
+        assert_eq!(both.iter().collect::<Vec<usize>>(), vec![70]);
+        let mut only_a = a.clone();
+        only_a.difference_with(&b);
+        assert_eq!(only_a.len(), 2);
+    }
+
+    #[test]
+    fn test_equality_ignores_capacity() {
+        let mut a = BitSet::with_capacity(1024);
+        a.insert(5);
+        let b: BitSet = [5].into_iter().collect();
+        assert_eq!(a, b);
+        a.insert(900);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,270 @@
+use std::path::Path;
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+
+use crate::artifacts::{RUNS_DIR, RunDir};
+use crate::bench::{Baseline, BenchOptions, BenchReport, Comparison, baseline_path};
+use crate::input;
+use crate::json::{self, ToJson};
+use crate::logging::LogFormat;
+use crate::progress::{self, Progress};
+use crate::result::AocResult;
+use crate::run::{DigitGrouping, OutputFormat, Part};
+use crate::timing::Timings;
+
+/// Flags every day's command line shares. Each day flattens this into its
+/// own `Config` next to the flags only it has.
+#[derive(clap::Args, Debug, Clone)]
+pub struct CommonConfig {
+    #[clap(
+        short,
+        long,
+        help = "Path to input file, or '-' for stdin [default: data/dayNN/input.txt]"
+    )]
+    pub input: Option<String>,
+
+    #[clap(
+        long,
+        value_parser = PossibleValuesParser::new(["1", "2", "both"]).map(|s| Part::from(s.as_str())),
+        help = "Solve part '1', '2' or 'both' through the shared runner instead of by mode"
+    )]
+    pub part: Option<Part>,
+
+    #[command(flatten)]
+    pub verbosity: clap_verbosity_flag::Verbosity,
+
+    #[clap(
+        long,
+        default_value = "text",
+        value_parser = PossibleValuesParser::new(["text", "json"]).map(|s| OutputFormat::from(s.as_str())),
+        help = "Output: 'text' or 'json'"
+    )]
+    pub output: OutputFormat,
+
+    #[clap(
+        long,
+        default_value = "text",
+        value_parser = PossibleValuesParser::new(["text", "json"]).map(|s| LogFormat::from(s.as_str())),
+        help = "Log lines on stderr as 'text' or 'json', one object per line tagged with the day and phase"
+    )]
+    pub log_format: LogFormat,
+
+    #[clap(
+        long,
+        default_value = "none",
+        value_parser = PossibleValuesParser::new(["none", "underscore", "comma"])
+            .map(|s| DigitGrouping::from(s.as_str())),
+        help = "Group answer digits in text output: 'none', 'underscore' or 'comma'"
+    )]
+    pub group_digits: DigitGrouping,
+
+    #[clap(
+        long,
+        help = "Print how long parsing and solving took after the answer (JSON output always has it)"
+    )]
+    pub timings: bool,
+
+    #[clap(
+        long,
+        help = "Write the parsed input as JSON to this path (needs the 'serde' feature)"
+    )]
+    pub dump_parsed: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = RUNS_DIR,
+        help = "Collect the files this run writes, with a manifest, in a new timestamped directory under DIR [default: runs]"
+    )]
+    pub out_dir: Option<String>,
+
+    #[clap(short, long, help = "Run benchmark")]
+    pub bench: bool,
+
+    #[clap(long, help = "Benchmark iterations", default_value = "1000")]
+    pub iterations: u32,
+
+    #[clap(
+        long,
+        help = "Untimed benchmark runs before the timed ones",
+        default_value = "10"
+    )]
+    pub warmup: u32,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        requires = "bench",
+        help = "Save the benchmark medians as baseline NAME"
+    )]
+    pub save_baseline: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        requires = "bench",
+        help = "Compare the benchmark with baseline NAME, failing on slowdowns past --threshold"
+    )]
+    pub compare_baseline: Option<String>,
+
+    #[clap(
+        long,
+        default_value = "5",
+        help = "Slowdown in percent that --compare-baseline fails on"
+    )]
+    pub threshold: f64,
+}
+
+impl CommonConfig {
+    /// `--input`, or the day's puzzle input under `data/`.
+    pub fn input_path(&self, day: u32) -> String {
+        match &self.input {
+            Some(path) => path.clone(),
+            None => input::input_path(day).display().to_string(),
+        }
+    }
+
+    /// `--iterations` and `--warmup` for `--bench`.
+    pub fn bench_options(&self) -> BenchOptions {
+        BenchOptions {
+            warmup: self.warmup,
+            iterations: self.iterations,
+        }
+    }
+
+    /// Prints `report`, then compares and saves it as `--compare-baseline`
+    /// and `--save-baseline` ask. Returns false if a stage regressed, or an
+    /// error if a baseline can't be read or written.
+    pub fn finish_bench(&self, report: &BenchReport) -> AocResult<bool> {
+        if self.output == OutputFormat::Text {
+            println!("{}", report);
+        }
+        let current = Baseline::of(report);
+        let comparison = match &self.compare_baseline {
+            Some(name) => {
+                let baseline = Baseline::load(&baseline_path(report.day, name))?;
+                Some((name, Comparison::new(&baseline, &current, self.threshold)))
+            }
+            None => None,
+        };
+        let saved = match &self.save_baseline {
+            Some(name) => {
+                let path = baseline_path(report.day, name);
+                current.save(&path)?;
+                Some((name, path))
+            }
+            None => None,
+        };
+        match self.output {
+            OutputFormat::Text => {
+                if let Some((name, comparison)) = &comparison {
+                    println!("\nCompared with baseline '{}':\n{}", name, comparison);
+                }
+                if let Some((name, path)) = &saved {
+                    println!("Saved baseline '{}' to {}", name, path.display());
+                }
+            }
+            OutputFormat::Json => {
+                let mut fields = vec![("bench", report.to_json())];
+                if let Some((_, comparison)) = &comparison {
+                    fields.push(("comparison", comparison.to_json()));
+                }
+                if let Some((_, path)) = &saved {
+                    fields.push(("saved_baseline", path.display().to_string().to_json()));
+                }
+                println!("{}", json::object(&fields));
+            }
+        }
+        Ok(comparison.is_none_or(|(_, comparison)| comparison.passed()))
+    }
+
+    /// Prints `timings` below a text answer if `--timings` was given.
+    pub fn print_timings(&self, timings: &Timings) {
+        if self.timings {
+            println!("Timings: {}", timings);
+        }
+    }
+
+    /// Progress for a long solver loop counting `label`. JSON output gets
+    /// log lines instead of a bar, so scripts reading it see no redraws.
+    pub fn progress(&self, label: &str) -> Box<dyn Progress> {
+        progress::for_terminal(label, self.output == OutputFormat::Text)
+    }
+
+    /// The directory for this run's artifacts, if `--out-dir` was given.
+    pub fn run_dir(&self, day: u32) -> Option<RunDir> {
+        let base = self.out_dir.as_ref()?;
+        Some(RunDir::new(Path::new(base), day, &self.input_path(day)))
+    }
+}
+
+/// Ends a day's command line with the outcome of its `run`: status 0 if it
+/// passed, 1 if it failed a check, or the error's `exit_code` after
+/// printing its `diagnostic`.
+pub fn exit(result: AocResult<bool>) -> ! {
+    match result {
+        Ok(true) => std::process::exit(0),
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("error: {}", e.diagnostic());
+            std::process::exit(e.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(clap::Parser, Debug)]
+    struct DayConfig {
+        #[command(flatten)]
+        common: CommonConfig,
+
+        #[clap(long)]
+        extra: bool,
+    }
+
+    #[test]
+    fn test_flattened_flags() {
+        let config = DayConfig::parse_from(["day07", "--extra", "-b", "--iterations", "5", "-vv"]);
+        assert!(config.extra);
+        assert!(config.common.bench);
+        assert_eq!(config.common.iterations, 5);
+        assert_eq!(
+            config.common.bench_options(),
+            BenchOptions {
+                warmup: 10,
+                iterations: 5
+            }
+        );
+        assert_eq!(config.common.input_path(7), "data/day07/input.txt");
+        assert_eq!(config.common.output, OutputFormat::Text);
+
+        let config = DayConfig::parse_from(["day07", "-i", "example.txt", "--part", "2"]);
+        assert_eq!(config.common.input_path(7), "example.txt");
+        assert_eq!(config.common.part, Some(Part::Two));
+        assert!(config.common.run_dir(7).is_none());
+        assert!(DayConfig::try_parse_from(["day07", "--part", "3"]).is_err());
+        assert!(DayConfig::try_parse_from(["day07", "--part", "banana"]).is_err());
+        assert!(DayConfig::try_parse_from(["day07", "--output", "xml"]).is_err());
+        assert!(DayConfig::try_parse_from(["day07", "--group-digits", "zzz"]).is_err());
+        assert!(DayConfig::try_parse_from(["day07", "--iterations", "5000000000"]).is_err());
+        let config = DayConfig::parse_from(["day07", "--group-digits", "comma"]);
+        assert_eq!(config.common.group_digits, DigitGrouping::Comma);
+
+        assert!(DayConfig::try_parse_from(["day07", "--save-baseline", "before"]).is_err());
+        let config = DayConfig::parse_from(["day07", "-b", "--compare-baseline", "before"]);
+        assert_eq!(config.common.compare_baseline.as_deref(), Some("before"));
+        assert_eq!(config.common.threshold, 5.0);
+
+        let config = DayConfig::parse_from(["day07", "--out-dir"]);
+        assert_eq!(config.common.out_dir.as_deref(), Some(RUNS_DIR));
+        let run = config.common.run_dir(7).expect("run directory");
+        assert!(run.path().starts_with(RUNS_DIR));
+        let config = DayConfig::parse_from(["day07", "--out-dir", "experiments", "--extra"]);
+        assert_eq!(config.common.out_dir.as_deref(), Some("experiments"));
+    }
+}
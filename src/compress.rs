@@ -0,0 +1,130 @@
+/// Maps a sparse set of `i64` coordinates onto dense indices `0..len` in
+/// sorted order, so huge coordinate ranges can back a small `Grid`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CoordinateCompressor {
+    values: Vec<i64>,
+}
+
+impl CoordinateCompressor {
+    /// Accepts coordinates in any order, with duplicates.
+    pub fn new(coords: impl IntoIterator<Item = i64>) -> Self {
+        let mut values: Vec<i64> = coords.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        CoordinateCompressor { values }
+    }
+
+    /// Like `new`, but also keeps `c - 1` and `c + 1` for every coordinate so
+    /// that empty space between two coordinates survives compression as its
+    /// own index. Useful before flood filling a compressed grid.
+    pub fn with_neighbours(coords: impl IntoIterator<Item = i64>) -> Self {
+        Self::new(coords.into_iter().flat_map(|c| {
+            [c.checked_sub(1), Some(c), c.checked_add(1)]
+                .into_iter()
+                .flatten()
+        }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Dense index of a coordinate that was compressed.
+    pub fn index(&self, coord: i64) -> Option<usize> {
+        self.values.binary_search(&coord).ok()
+    }
+
+    /// Original coordinate for a dense index.
+    pub fn value(&self, index: usize) -> Option<i64> {
+        self.values.get(index).copied()
+    }
+
+    /// Index of the first compressed coordinate that is `>= coord`, which
+    /// is `len()` when every coordinate is smaller.
+    pub fn lower_bound(&self, coord: i64) -> usize {
+        self.values.partition_point(|&v| v < coord)
+    }
+
+    /// Distance from the coordinate at `index` to the next one, i.e. how
+    /// many original units a compressed cell stands for.
+    pub fn span(&self, index: usize) -> Option<u64> {
+        let here = self.value(index)?;
+        let next = self.value(index + 1)?;
+        Some(here.abs_diff(next))
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+
+    #[test]
+    fn test_unsorted_with_duplicates() {
+        let c = CoordinateCompressor::new([50, -7, 1_000_000_000, 50, 3, -7]);
+        assert_eq!(c.len(), 4);
+        assert_eq!(c.values(), &[-7, 3, 50, 1_000_000_000]);
+        assert_eq!(c.index(50), Some(2));
+        assert_eq!(c.index(4), None);
+        assert_eq!(c.value(3), Some(1_000_000_000));
+        assert_eq!(c.value(4), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let coords = [9, 2, 2, 40, -5, 9];
+        let c = CoordinateCompressor::new(coords);
+        for coord in coords {
+            let index = c.index(coord).expect("index");
+            assert_eq!(c.value(index), Some(coord));
+        }
+    }
+
+    #[test]
+    fn test_lower_bound_and_span() {
+        let c = CoordinateCompressor::new([10, 20, 40]);
+        assert_eq!(c.lower_bound(5), 0);
+        assert_eq!(c.lower_bound(20), 1);
+        assert_eq!(c.lower_bound(21), 2);
+        assert_eq!(c.lower_bound(41), 3);
+        assert_eq!(c.span(1), Some(20));
+        assert_eq!(c.span(2), None);
+    }
+
+    #[test]
+    fn test_with_neighbours() {
+        let c = CoordinateCompressor::with_neighbours([0, 10, i64::MAX]);
+        assert_eq!(c.values(), &[-1, 0, 1, 9, 10, 11, i64::MAX - 1, i64::MAX]);
+        assert!(CoordinateCompressor::new([]).is_empty());
+    }
+
+    #[test]
+    fn test_compressed_grid_flood_fill() {
+        // A wall running down x = 1_000_000 splits a huge strip in two.
+        let xs = CoordinateCompressor::with_neighbours([0, 1_000_000, 2_000_000]);
+        let ys = CoordinateCompressor::with_neighbours([0, 5_000_000]);
+        let mut grid = Grid::new(xs.len(), ys.len(), false);
+        let wall_x = xs.index(1_000_000).expect("x") as i64;
+        for y in 0..ys.len() as i64 {
+            grid.set(Point::new(wall_x, y), true);
+        }
+        let start = Point::new(xs.index(0).expect("x") as i64, 0);
+        let region = grid.flood_fill(start, |wall| !wall);
+        assert!(
+            region
+                .iter()
+                .all(|p| xs.value(p.x as usize).expect("x") < 1_000_000)
+        );
+        // Columns for -1, 0, 1 and 999_999 lie left of the wall.
+        assert_eq!(region.len(), 4 * ys.len());
+    }
+}
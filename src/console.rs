@@ -0,0 +1,12 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// Prints each event on its own line to stdout, the way the CLI shows
+/// narrative that solvers return rather than print.
+pub fn render<T: Display>(events: impl IntoIterator<Item = T>) {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for event in events {
+        let _ = writeln!(out, "{}", event);
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+use std::io;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How much of the input the fingerprint quotes.
+const PREVIEW_CHARS: usize = 80;
+
+/// What a run was doing, kept up to date so a panic can report it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CrashContext {
+    pub day: u32,
+    pub input_path: String,
+    pub phase: Option<&'static str>,
+    /// Whether `phase` has returned, so the run is between phases or
+    /// printing its results.
+    pub phase_ended: bool,
+    pub fingerprint: Option<Fingerprint>,
+}
+
+/// Enough of the input to recognise it again without copying all of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub bytes: usize,
+    pub lines: usize,
+    /// FNV-1a hash of the whole input.
+    pub hash: u64,
+    pub preview: String,
+}
+
+impl Fingerprint {
+    pub fn of(input: &str) -> Self {
+        let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        Fingerprint {
+            bytes: input.len(),
+            lines: input.lines().count(),
+            hash,
+            preview: input.chars().take(PREVIEW_CHARS).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "input: {} bytes, {} lines, fnv1a {:016x}",
+            self.bytes, self.lines, self.hash
+        )?;
+        write!(f, "input starts: {:?}", self.preview)
+    }
+}
+
+impl CrashContext {
+    /// Where the run was: `startup`, a phase such as `solve`, or
+    /// `after solve` once it has returned.
+    pub fn stage(&self) -> String {
+        match self.phase {
+            None => "startup".to_string(),
+            Some(phase) if self.phase_ended => format!("after {}", phase),
+            Some(phase) => phase.to_string(),
+        }
+    }
+}
+
+static CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+fn update(f: impl FnOnce(&mut CrashContext)) {
+    if let Ok(mut context) = CONTEXT.lock()
+        && let Some(context) = context.as_mut()
+    {
+        f(context);
+    }
+}
+
+/// Replaces the default panic message with a short note pointing at a
+/// crash report, written to the temp directory, that records `day`,
+/// `input_path` and whatever `set_phase` and `set_input` added since.
+pub fn install(day: u32, input_path: &str) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        *context = Some(CrashContext {
+            day,
+            input_path: input_path.to_string(),
+            ..Default::default()
+        });
+    }
+    panic::set_hook(Box::new(|info| {
+        let message = message(info);
+        if is_broken_pipe(&message) {
+            return;
+        }
+        let context = CONTEXT
+            .lock()
+            .ok()
+            .and_then(|context| context.clone())
+            .unwrap_or_default();
+        let report = report(&context, &message, info);
+        let path = report_path(context.day);
+        match std::fs::write(&path, &report) {
+            Ok(()) => eprintln!(
+                "day {} crashed during {}; details in {}",
+                context.day,
+                context.stage(),
+                path.display()
+            ),
+            Err(_) => eprint!("{}", report),
+        }
+    }));
+}
+
+/// Records the phase the run is in. `Phases::time` calls this.
+pub fn set_phase(phase: &'static str) {
+    update(|context| {
+        context.phase = Some(phase);
+        context.phase_ended = false;
+    });
+}
+
+/// Records that the current phase has returned. `Phases::time` calls
+/// this.
+pub fn end_phase() {
+    update(|context| context.phase_ended = true);
+}
+
+/// Records a fingerprint of the input once it has been read.
+pub fn set_input(input: &str) {
+    update(|context| context.fingerprint = Some(Fingerprint::of(input)));
+}
+
+fn report_path(day: u32) -> PathBuf {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    std::env::temp_dir().join(format!("aoc25-day{:02}-crash-{}.txt", day, seconds))
+}
+
+fn is_report_name(name: &str) -> bool {
+    name.strip_prefix("aoc25-day")
+        .and_then(|rest| rest.split_once("-crash-"))
+        .is_some_and(|(_, rest)| rest.ends_with(".txt"))
+}
+
+/// Crash reports left in the temp directory by earlier runs.
+pub fn reports() -> io::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir(std::env::temp_dir())? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(is_report_name)
+        {
+            found.push(path);
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Deletes every crash report, or only lists them if `dry_run`, returning
+/// their paths.
+pub fn clean(dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    let found = reports()?;
+    if !dry_run {
+        for path in &found {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(found)
+}
+
+fn message(info: &PanicHookInfo) -> String {
+    info.payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Whether the panic is `println!` finding stdout closed, as when the
+/// output is piped into `head`. That is the reader going away rather than
+/// a crash, so it gets no report.
+fn is_broken_pipe(message: &str) -> bool {
+    message.starts_with("failed printing to std") && message.contains("Broken pipe")
+}
+
+fn report(context: &CrashContext, message: &str, info: &PanicHookInfo) -> String {
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "unknown location".to_string());
+    format_report(context, message, &location)
+}
+
+fn format_report(context: &CrashContext, message: &str, location: &str) -> String {
+    let mut out = format!(
+        "day: {}\nphase: {}\ninput path: {}\npanic: {}\nat: {}\n",
+        context.day,
+        context.stage(),
+        context.input_path,
+        message,
+        location
+    );
+    if let Some(fingerprint) = &context.fingerprint {
+        out.push_str(&format!("{}\n", fingerprint));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        let fingerprint = Fingerprint::of("L68\nL30\n");
+        assert_eq!(fingerprint.bytes, 8);
+        assert_eq!(fingerprint.lines, 2);
+        assert_eq!(Fingerprint::of("").hash, 0xcbf29ce484222325);
+        assert_ne!(fingerprint.hash, Fingerprint::of("L68\nL31\n").hash);
+        assert_eq!(
+            Fingerprint::of(&"9".repeat(200)).preview.len(),
+            PREVIEW_CHARS
+        );
+    }
+
+    #[test]
+    fn test_report_names() {
+        assert!(is_report_name("aoc25-day03-crash-1700000000.txt"));
+        assert!(!is_report_name("aoc25-day03-trace.jsonl"));
+        assert!(!is_report_name("other-day03-crash-1.txt"));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let context = CrashContext {
+            day: 3,
+            input_path: "data/day03/input.txt".to_string(),
+            phase: Some("solve"),
+            phase_ended: false,
+            fingerprint: Some(Fingerprint::of("12\n")),
+        };
+        let report = format_report(&context, "index out of bounds", "src/days/day03.rs:1:1");
+        assert_eq!(
+            report,
+            format!(
+                "day: 3\nphase: solve\ninput path: data/day03/input.txt\n\
+                 panic: index out of bounds\nat: src/days/day03.rs:1:1\n\
+                 input: 3 bytes, 1 lines, fnv1a {:016x}\ninput starts: \"12\\n\"\n",
+                Fingerprint::of("12\n").hash
+            )
+        );
+    }
+
+    #[test]
+    fn test_stage() {
+        let mut context = CrashContext::default();
+        assert_eq!(context.stage(), "startup");
+        context.phase = Some("parse");
+        assert_eq!(context.stage(), "parse");
+        context.phase_ended = true;
+        assert_eq!(context.stage(), "after parse");
+    }
+
+    #[test]
+    fn test_broken_pipe() {
+        assert!(is_broken_pipe(
+            "failed printing to stdout: Broken pipe (os error 32)"
+        ));
+        assert!(!is_broken_pipe("index out of bounds"));
+    }
+}
@@ -0,0 +1,491 @@
+//! `aoc tui`: a ratatui dashboard listing every day with its stars and
+//! latest answers, checked against the stored answers, above a pane of the
+//! log lines solvers wrote. Keys pick a day and run it, or run them all;
+//! days are solved on a worker thread so the log pane keeps scrolling.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::answers::{Answers, Check};
+use crate::fmt::Elapsed;
+use crate::run::{DayAnswers, Part, solve_all};
+use crate::solver;
+use crate::submit::Stars;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// How many log lines are kept.
+const LOG_KEPT: usize = 200;
+
+/// How long to wait for a key before redrawing with whatever the solvers
+/// logged or answered meanwhile.
+const TICK: Duration = Duration::from_millis(100);
+
+const HELP: &str = "[↑/↓] select  [enter] run day  [a] run all  [s] record answers  [q] quit";
+
+/// What a key pressed in the dashboard asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Move the selection up or down a day: `↑`/`k` and `↓`/`j`.
+    Up,
+    Down,
+    /// Solve both parts of the selected day: `enter` or `r`.
+    RunSelected,
+    /// Solve every day with an input file: `a`.
+    RunAll,
+    /// Store the latest answers as verified: `s`.
+    Record,
+    /// `q` or `esc`.
+    Quit,
+}
+
+impl Action {
+    pub fn from_key(code: KeyCode) -> Option<Self> {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::Up),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::Down),
+            KeyCode::Enter | KeyCode::Char('r') => Some(Action::RunSelected),
+            KeyCode::Char('a') => Some(Action::RunAll),
+            KeyCode::Char('s') => Some(Action::Record),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// The log lines shown in the log pane, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct LogPane(Arc<Mutex<VecDeque<String>>>);
+
+impl LogPane {
+    pub fn push(&self, line: impl Into<String>) {
+        let mut lines = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() == LOG_KEPT {
+            lines.pop_front();
+        }
+        lines.push_back(line.into());
+    }
+
+    /// The latest `count` lines, oldest first.
+    pub fn latest(&self, count: usize) -> Vec<String> {
+        let lines = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Sends `tracing` events to the pane instead of stderr, which would
+/// tear the screen.
+impl<'a> MakeWriter<'a> for LogPane {
+    type Writer = PaneWriter;
+
+    fn make_writer(&'a self) -> PaneWriter {
+        PaneWriter {
+            pane: self.clone(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// One event's output, pushed to the pane line by line when dropped.
+pub struct PaneWriter {
+    pane: LogPane,
+    buf: Vec<u8>,
+}
+
+impl Write for PaneWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for PaneWriter {
+    fn drop(&mut self) {
+        for line in String::from_utf8_lossy(&self.buf).lines() {
+            self.pane.push(line);
+        }
+    }
+}
+
+/// Every registered day, its latest run and how it compares with the
+/// stored answers.
+pub struct Dashboard {
+    /// Where the `dayNN` inputs and submissions are.
+    pub dir: PathBuf,
+    pub answers_path: PathBuf,
+    answers: Answers,
+    results: Vec<DayAnswers>,
+    /// The row picked in the days table.
+    selected: TableState,
+    /// Days handed to the worker whose answers haven't come back yet.
+    running: Vec<u32>,
+    solved: Option<Receiver<DayAnswers>>,
+    pub log: LogPane,
+}
+
+impl Dashboard {
+    /// A dashboard over the inputs in `dir`, checking against the answers
+    /// in `answers_path`. A file that can't be read is noted in the log.
+    pub fn new(dir: PathBuf, answers_path: PathBuf, log: LogPane) -> Self {
+        let answers = Answers::load(&answers_path).unwrap_or_else(|e| {
+            log.push(format!("Not checking answers: {}", e));
+            Answers::default()
+        });
+        Dashboard {
+            dir,
+            answers_path,
+            answers,
+            results: vec![],
+            selected: TableState::default().with_selected(Some(0)),
+            running: vec![],
+            solved: None,
+            log,
+        }
+    }
+
+    /// The day of the selected row.
+    pub fn selected_day(&self) -> Option<u32> {
+        let row = self.selected.selected()?;
+        solver::COMMANDS.get(row).map(|command| command.day)
+    }
+
+    /// Carries out `action`, returning false once it is time to quit.
+    pub fn apply(&mut self, action: Action) -> bool {
+        match action {
+            Action::Up => self.selected.select_previous(),
+            Action::Down => {
+                let last = solver::COMMANDS.len().saturating_sub(1);
+                let row = self
+                    .selected
+                    .selected()
+                    .map_or(0, |row| (row + 1).min(last));
+                self.selected.select(Some(row));
+            }
+            Action::RunSelected => {
+                if let Some(day) = self.selected_day() {
+                    self.run(&[day]);
+                }
+            }
+            Action::RunAll => self.run(&solver::days().collect::<Vec<_>>()),
+            Action::Record => self.record(),
+            Action::Quit => return false,
+        }
+        true
+    }
+
+    /// Starts solving `days` on a worker thread, unless a run is still
+    /// going.
+    fn run(&mut self, days: &[u32]) {
+        if !self.running.is_empty() {
+            self.log.push("Still running, try again when it finishes");
+            return;
+        }
+        let mut inputs = Vec::new();
+        for &day in days {
+            let path = self.dir.join(format!("day{:02}/input.txt", day));
+            match std::fs::read_to_string(&path) {
+                Ok(input) => inputs.push((day, input)),
+                Err(e) => self
+                    .log
+                    .push(format!("Skipping day {}: {}: {}", day, path.display(), e)),
+            }
+        }
+        self.running = inputs.iter().map(|&(day, _)| day).collect();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for input in inputs {
+                for result in solve_all(std::slice::from_ref(&input), Part::Both, false) {
+                    if tx.send(result).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        self.solved = Some(rx);
+    }
+
+    /// Shows the answers the worker has sent since the last call.
+    pub fn poll(&mut self) {
+        let solved: Vec<DayAnswers> = match &self.solved {
+            Some(rx) => rx.try_iter().collect(),
+            None => return,
+        };
+        for result in solved {
+            self.show(result);
+        }
+    }
+
+    /// Puts `result` on the board in place of the day's previous run.
+    pub fn show(&mut self, result: DayAnswers) {
+        match &result.answers {
+            Ok(_) => self.log.push(format!(
+                "Day {} solved in {}",
+                result.day,
+                Elapsed(result.duration)
+            )),
+            Err(e) => self.log.push(format!("Day {} failed: {}", result.day, e)),
+        }
+        for (day, part, answer, check) in self.answers.check_all(std::slice::from_ref(&result)) {
+            if let Check::Regression { expected } = check {
+                self.log.push(format!(
+                    "Day {} part {} regressed: expected {}, got {}",
+                    day, part, expected, answer
+                ));
+            }
+        }
+        self.running.retain(|&day| day != result.day);
+        self.results.retain(|r| r.day != result.day);
+        self.results.push(result);
+    }
+
+    fn record(&mut self) {
+        let fresh: Vec<(u32, u32, String)> = self
+            .answers
+            .check_all(&self.results)
+            .into_iter()
+            .map(|(day, part, answer, _)| (day, part, answer.to_string()))
+            .collect();
+        for (day, part, answer) in &fresh {
+            self.answers.record(*day, *part, answer);
+        }
+        match self.answers.save(&self.answers_path) {
+            Ok(()) => self.log.push(format!(
+                "Recorded {} answers in {}",
+                fresh.len(),
+                self.answers_path.display()
+            )),
+            Err(e) => self.log.push(format!("Failed to record answers: {}", e)),
+        }
+    }
+
+    /// The days table, the log pane and the keys, filling `frame`.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [days, log, help] = Layout::vertical([
+            Constraint::Length(solver::COMMANDS.len() as u16 + 3),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let header = ["Day", "Stars", "Title", "Part 1", "Part 2", "Time", "Check"];
+        let mut cells = vec![];
+        let mut checks = vec![];
+        for command in solver::COMMANDS {
+            let stars = Stars::load(&self.dir, command.day).unwrap_or_default();
+            let result = self.results.iter().find(|r| r.day == command.day);
+            let cell = |part| match result.map(|r| (r.answer(part), &r.answers)) {
+                Some((Some(answer), _)) => answer.to_string(),
+                Some((None, Err(_))) => "failed".to_string(),
+                _ => "-".to_string(),
+            };
+            let time = if self.running.contains(&command.day) {
+                "running".to_string()
+            } else {
+                result.map_or("-".to_string(), |r| Elapsed(r.duration).to_string())
+            };
+            let check = result.map_or("-", |r| self.check(r));
+            checks.push(check);
+            cells.push([
+                command.day.to_string(),
+                stars.to_string(),
+                command.title.to_string(),
+                cell(1),
+                cell(2),
+                time,
+                check.to_string(),
+            ]);
+        }
+        let widths: Vec<Constraint> = (0..header.len())
+            .map(|column| {
+                let width = cells.iter().map(|row| row[column].chars().count()).max();
+                Constraint::Length(width.unwrap_or(0).max(header[column].len()) as u16)
+            })
+            .collect();
+        let header = Row::new(header).style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = cells.into_iter().zip(checks).map(|(row, check)| {
+            let colour = match check {
+                "REGRESSED" | "failed" => Color::Red,
+                "ok" => Color::Green,
+                _ => Color::Reset,
+            };
+            Row::new(row).style(Style::default().fg(colour))
+        });
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .block(Block::bordered().title(" Days "))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, days, &mut self.selected);
+
+        let shown = log.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = self.log.latest(shown).into_iter().map(Line::from).collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Log ")),
+            log,
+        );
+        frame.render_widget(Paragraph::new(HELP), help);
+    }
+
+    /// `REGRESSED` if any part differs from its stored answer, `ok` if
+    /// they all match and `new` if some have nothing stored.
+    fn check(&self, result: &DayAnswers) -> &'static str {
+        let checks = self.answers.check_all(std::slice::from_ref(result));
+        if result.answers.is_err() {
+            "failed"
+        } else if checks
+            .iter()
+            .any(|(.., check)| matches!(check, Check::Regression { .. }))
+        {
+            "REGRESSED"
+        } else if checks.iter().all(|(.., check)| *check == Check::Matches) {
+            "ok"
+        } else {
+            "new"
+        }
+    }
+}
+
+/// Runs the dashboard on the terminal until `q`. Solver logs go to the log
+/// pane at `level` and above.
+pub fn run(dir: PathBuf, answers_path: PathBuf, level: LevelFilter) -> io::Result<()> {
+    let log = LogPane::default();
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(log.clone())
+        .with_ansi(false)
+        .without_time()
+        .try_init();
+    let mut dashboard = Dashboard::new(dir, answers_path, log);
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut dashboard);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, dashboard: &mut Dashboard) -> io::Result<()> {
+    loop {
+        dashboard.poll();
+        terminal.draw(|frame| dashboard.draw(frame))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && let Some(action) = Action::from_key(key.code)
+            && !dashboard.apply(action)
+        {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AocError;
+    use crate::solution::Answer;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn test_action_from_key() {
+        assert_eq!(Action::from_key(KeyCode::Down), Some(Action::Down));
+        assert_eq!(Action::from_key(KeyCode::Char('k')), Some(Action::Up));
+        assert_eq!(Action::from_key(KeyCode::Enter), Some(Action::RunSelected));
+        assert_eq!(Action::from_key(KeyCode::Char('a')), Some(Action::RunAll));
+        assert_eq!(Action::from_key(KeyCode::Char('s')), Some(Action::Record));
+        assert_eq!(Action::from_key(KeyCode::Esc), Some(Action::Quit));
+        assert_eq!(Action::from_key(KeyCode::Char('x')), None);
+    }
+
+    /// The text of each row of the screen, without trailing spaces.
+    fn screen(dashboard: &mut Dashboard) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(80, 16)).expect("test terminal");
+        terminal.draw(|frame| dashboard.draw(frame)).expect("draw");
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                let row: String = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dashboard() {
+        let dir = std::env::temp_dir().join(format!("aoc25-dashboard-{}", std::process::id()));
+        let answers = dir.join("answers.toml");
+        let mut dashboard = Dashboard::new(dir.clone(), answers, LogPane::default());
+        dashboard.answers.record(1, 1, "3");
+        dashboard.answers.record(1, 2, "7");
+        dashboard.show(DayAnswers {
+            day: 1,
+            part: Part::Both,
+            answers: Ok(vec![Answer::from(3u32), Answer::from(6u32)]),
+            duration: Duration::from_millis(2),
+            timings: None,
+        });
+        dashboard.show(DayAnswers {
+            day: 2,
+            part: Part::Both,
+            answers: Err(AocError::Unsupported("nope".to_string())),
+            duration: Duration::ZERO,
+            timings: None,
+        });
+        assert!(dashboard.apply(Action::Down));
+        assert_eq!(dashboard.selected_day(), Some(2));
+        for _ in solver::COMMANDS {
+            dashboard.apply(Action::Down);
+        }
+        assert_eq!(dashboard.selected_day(), solver::days().last());
+        assert!(dashboard.apply(Action::Up));
+        assert!(!dashboard.apply(Action::Quit));
+
+        let screen = screen(&mut dashboard);
+        assert_eq!(
+            screen[0],
+            "┌ Days ────────────────────────────────────────────────────────────────────────┐"
+        );
+        assert_eq!(
+            screen[1],
+            "│Day  Stars  Title            Part 1  Part 2  Time    Check                    │"
+        );
+        assert_eq!(
+            screen[2],
+            "│1    ..     Secret Entrance  3       6       2.0 ms  REGRESSED                │"
+        );
+        assert_eq!(
+            screen[3],
+            "│2    ..     Gift Shop        failed  failed  0 ns    failed                   │"
+        );
+        assert_eq!(
+            screen[4],
+            "│3    ..     Lobby            -       -       -       -                        │"
+        );
+        let log = screen.join("\n");
+        assert!(log.contains("│Day 1 part 2 regressed: expected 7, got 6"));
+        assert!(log.contains("│Day 2 failed: Unsupported: nope"));
+        assert!(screen[15].starts_with("[↑/↓] select"));
+    }
+}
@@ -0,0 +1,1088 @@
+use crate::cli::CommonConfig;
+use crate::describe::{Description, Example, Grammar, ModeDescription, Rules};
+use crate::error::AocError;
+use crate::input::{self, Input};
+use crate::json::{self, ToJson};
+use crate::math::{Matrix, ModRing};
+use crate::params::Params;
+use crate::parse::{nom_error, parse_complete, parse_lines, relaxed_number, unsigned};
+use crate::result::AocResult;
+use crate::sim::{Step, Stepper};
+use crate::solution::{Answer, Solution};
+use crate::turtle::Turtle;
+use std::fmt::{self};
+use std::str::FromStr;
+
+pub mod cli;
+pub mod counting;
+pub mod hooks;
+pub mod macros;
+pub mod stats;
+pub mod summary;
+pub mod trace;
+pub mod verify;
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+pub use counting::ZeroCounting;
+use hooks::{NoHooks, SimulationHooks};
+use macros::Dialect;
+use nom::{IResult, Parser, branch::alt, bytes::complete::tag, sequence::pair};
+use trace::TraceFormat;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mode {
+    #[cfg_attr(feature = "serde", serde(rename = "after"))]
+    CountZerosAfterRotation,
+    #[cfg_attr(feature = "serde", serde(rename = "during"))]
+    CountZerosDuringRotation,
+}
+
+impl Mode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::CountZerosAfterRotation => "after",
+            Mode::CountZerosDuringRotation => "during",
+        }
+    }
+
+    /// The puzzle part this mode answers.
+    pub fn part(self) -> u32 {
+        match self {
+            Mode::CountZerosAfterRotation => 1,
+            Mode::CountZerosDuringRotation => 2,
+        }
+    }
+}
+
+impl From<&str> for Mode {
+    fn from(s: &str) -> Self {
+        match s {
+            "after" => Mode::CountZerosAfterRotation,
+            "during" => Mode::CountZerosDuringRotation,
+            _ => Mode::CountZerosAfterRotation,
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = AocError;
+
+    /// Unlike `From<&str>`, rejects anything but `after` and `during`.
+    fn from_str(s: &str) -> AocResult<Self> {
+        match s {
+            "after" | "during" => Ok(Mode::from(s)),
+            _ => Err(AocError::InvalidArgument(format!(
+                "unknown mode '{}', expected 'after' or 'during'",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(clap::Parser, Debug, Clone)]
+pub struct Config {
+    #[command(flatten)]
+    pub common: CommonConfig,
+
+    #[clap(
+        short,
+        long,
+        default_value = "after",
+        value_parser = PossibleValuesParser::new(["after", "during"]).map(|s| Mode::from(s.as_str())),
+        help = "Mode: 'after' or 'during'"
+    )]
+    pub mode: Mode,
+
+    #[clap(
+        long,
+        help = "Treat blank-line-separated blocks as separate locks and combine their zero counts"
+    )]
+    pub locks: bool,
+
+    #[clap(long, help = "Report the first instruction that hits zero instead")]
+    pub first_zero: bool,
+
+    #[clap(long, help = "Report statistics about the instructions instead")]
+    pub stats: bool,
+
+    #[clap(
+        long,
+        help = "Count the times the dial points at this number instead of 0, passing or stopping"
+    )]
+    pub target: Option<u32>,
+
+    #[clap(
+        long,
+        default_value_t = DIAL_SIZE,
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with_all = ["part", "bench"],
+        help = "Notches on the dial, which starts halfway round"
+    )]
+    pub dial_size: u32,
+
+    #[clap(long, help = "Write a trace of every rotation to this path")]
+    pub trace: Option<String>,
+
+    #[clap(
+        long,
+        default_value = "jsonl",
+        requires = "trace",
+        value_parser = PossibleValuesParser::new(["jsonl", "csv"]).map(|s| TraceFormat::from(s.as_str())),
+        help = "Trace format: 'jsonl', which replay reads, or 'csv'"
+    )]
+    pub trace_format: TraceFormat,
+
+    #[clap(long, help = "Ring the terminal bell whenever the dial reaches zero")]
+    pub bell: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["locks", "first_zero", "stats", "trace", "bell", "dialect", "dump_parsed"],
+        help = "Read and solve the input a line at a time instead of loading it whole"
+    )]
+    pub stream: bool,
+
+    #[clap(
+        long,
+        default_value = "vanilla",
+        value_parser = PossibleValuesParser::new(["vanilla", "extended"]).map(|s| Dialect::from(s.as_str())),
+        help = "Input dialect: 'vanilla', or 'extended' to allow DEF macros and xN repeats"
+    )]
+    pub dialect: Dialect,
+}
+
+impl Config {
+    /// `--target` if given, otherwise what `--mode` counts.
+    pub fn counting(&self) -> ZeroCounting {
+        self.target
+            .map_or(ZeroCounting::from(self.mode), ZeroCounting::Target)
+    }
+
+    /// The dial before any rotation.
+    pub fn start(&self) -> State {
+        State::with_size(self.dial_size)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation {
+    Left,
+    Right,
+}
+
+/// Notches on the puzzle's dial.
+pub const DIAL_SIZE: u32 = 100;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct State {
+    pub num: u32,
+    /// Notches on the dial, numbered `0..size`.
+    pub size: u32,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::new()
+    }
+}
+
+impl State {
+    pub fn new() -> Self {
+        State::with_size(DIAL_SIZE)
+    }
+
+    /// A dial of `size` notches pointing halfway round, as the puzzle's
+    /// dial starts at 50.
+    pub fn with_size(size: u32) -> Self {
+        State {
+            num: size / 2,
+            size,
+        }
+    }
+
+    /// The puzzle's dial pointing at `num`.
+    pub fn starting_at(num: u32) -> Self {
+        State {
+            num,
+            size: DIAL_SIZE,
+        }
+    }
+
+    /// Turns the dial by `instruction`, reporting where it ended up.
+    pub fn rotate(&mut self, instruction: Instruction) -> RotationEvent {
+        let count = instruction.argument;
+        let dial = ModRing::new(self.num, self.size);
+        let (dial, wraps) = match instruction.operation {
+            Operation::Left => dial.wrapping_sub(count),
+            Operation::Right => dial.wrapping_add(count),
+        };
+        let num = dial.value();
+        // Wrapping is passing 0, except for the wrap that stops there, or
+        // that leaves from there when turning left.
+        let passes = match instruction.operation {
+            Operation::Left => wraps - u32::from(self.num == 0 && wraps > 0),
+            Operation::Right => wraps - u32::from(num == 0 && count > 0),
+        };
+        self.num = num;
+        RotationEvent {
+            instruction,
+            dial: self.num,
+            passes,
+        }
+    }
+
+    /// Turns the dial by `instruction`, returning how many times it passed
+    /// through 0 without stopping there.
+    pub fn apply(&mut self, instruction: Instruction) -> u32 {
+        self.rotate(instruction).passes
+    }
+
+    /// Dial position after running `instructions` back to back `repeats`
+    /// times, using matrix exponentiation rather than simulating each pass.
+    pub fn position_after_repeats(&self, instructions: &[Instruction], repeats: u64) -> u32 {
+        let size = self.size as u64;
+        let net = instructions
+            .iter()
+            .fold(0u64, |acc, instruction| match instruction.operation {
+                Operation::Right => (acc + instruction.argument as u64) % size,
+                Operation::Left => (acc + size - instruction.argument as u64 % size) % size,
+            });
+        let pass = Matrix::from_rows(vec![vec![1, net], vec![0, 1]]);
+        pass.pow_mod(repeats, size)
+            .apply_mod(&[self.num as u64, 1], size)[0] as u32
+    }
+
+    /// Runs every instruction, returning what `counting` counted.
+    pub fn apply_multiple(
+        &mut self,
+        instructions: Vec<Instruction>,
+        counting: ZeroCounting,
+    ) -> u32 {
+        let simulation = Simulation::new(self.clone(), &instructions, counting);
+        let mut stepper = Stepper::new(simulation);
+        stepper.run_to_end();
+        let simulation = stepper.into_state();
+        *self = simulation.state.clone();
+        simulation.zero_count()
+    }
+
+    /// `apply_multiple` over instructions produced one at a time, as by
+    /// `input::lines_iter`, so they never all have to be in memory. Returns
+    /// the zero count and how many instructions were applied, stopping at
+    /// the first error.
+    pub fn apply_stream(
+        &mut self,
+        instructions: impl IntoIterator<Item = AocResult<Instruction>>,
+        counting: ZeroCounting,
+    ) -> AocResult<(u32, usize)> {
+        let (mut zero_count, mut count) = (0, 0);
+        for instruction in instructions {
+            let start = self.clone();
+            let event = self.rotate(instruction?);
+            zero_count += counting.hits(&start, &event);
+            count += 1;
+        }
+        Ok((zero_count, count))
+    }
+
+    /// `apply_multiple`, telling `hooks` about each rotation as it happens.
+    pub fn apply_multiple_with_hooks(
+        &mut self,
+        instructions: &[Instruction],
+        counting: ZeroCounting,
+        hooks: &mut dyn SimulationHooks,
+    ) -> u32 {
+        let mut simulation = Simulation::new(self.clone(), instructions, counting);
+        simulation.run_with_hooks(hooks);
+        *self = simulation.state.clone();
+        simulation.zero_count()
+    }
+
+    /// `apply_multiple`, also returning what each rotation did so a front
+    /// end can narrate the run.
+    pub fn apply_multiple_with_events(
+        &mut self,
+        instructions: &[Instruction],
+        counting: ZeroCounting,
+    ) -> (u32, Vec<RotationEvent>) {
+        let simulation = Simulation::new(self.clone(), instructions, counting).recording_events();
+        let mut stepper = Stepper::new(simulation);
+        stepper.run_to_end();
+        let simulation = stepper.into_state();
+        *self = simulation.state.clone();
+        (
+            simulation.zero_count(),
+            simulation.events.unwrap_or_default(),
+        )
+    }
+}
+
+/// What one rotation did to the dial. The solver returns these rather than
+/// printing, leaving front ends to decide how to show them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RotationEvent {
+    pub instruction: Instruction,
+    /// Dial position after the rotation.
+    pub dial: u32,
+    /// Times the dial passed through 0 without stopping there.
+    pub passes: u32,
+}
+
+impl RotationEvent {
+    /// The event as a line of the puzzle's narrative, mentioning passes
+    /// through 0 only when `counting` counts them.
+    pub fn narrate(&self, counting: ZeroCounting) -> String {
+        let mut out = format!(
+            "- The dial is rotated {} to point at {}",
+            self.instruction, self.dial
+        );
+        if counting.counts_passes() && self.passes > 0 {
+            out.push_str(&format!(
+                "; during this rotation, it points at 0 {} times",
+                self.passes
+            ));
+        }
+        out.push('.');
+        out
+    }
+}
+
+/// Day01 as a `Step` simulation: each step applies the next instruction.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Simulation<'a> {
+    pub state: State,
+    instructions: &'a [Instruction],
+    position: usize,
+    counting: ZeroCounting,
+    events: Option<Vec<RotationEvent>>,
+    zeros: u32,
+    pub zeros_after: u32,
+    pub zeros_during: u32,
+}
+
+impl<'a> Simulation<'a> {
+    pub fn new(state: State, instructions: &'a [Instruction], counting: ZeroCounting) -> Self {
+        Simulation {
+            state,
+            instructions,
+            position: 0,
+            counting,
+            events: None,
+            zeros: 0,
+            zeros_after: 0,
+            zeros_during: 0,
+        }
+    }
+
+    /// Keeps a `RotationEvent` for every step taken.
+    pub fn recording_events(mut self) -> Self {
+        self.events = Some(Vec::new());
+        self
+    }
+
+    /// Events recorded so far, empty unless `recording_events` was used.
+    pub fn events(&self) -> &[RotationEvent] {
+        self.events.as_deref().unwrap_or_default()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// What the simulation's `ZeroCounting` has counted so far.
+    pub fn zero_count(&self) -> u32 {
+        self.zeros
+    }
+}
+
+impl Step for Simulation<'_> {
+    fn step(&mut self) -> bool {
+        self.step_with(&mut NoHooks)
+    }
+}
+
+impl Simulation<'_> {
+    /// `step`, telling `hooks` what the rotation did.
+    pub fn step_with(&mut self, hooks: &mut dyn SimulationHooks) -> bool {
+        let Some(&instruction) = self.instructions.get(self.position) else {
+            return false;
+        };
+        let start = self.state.clone();
+        let event = self.state.rotate(instruction);
+        self.zeros_during += event.passes;
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+        let stopped = u32::from(self.state.num == 0);
+        self.zeros_after += stopped;
+        self.position += 1;
+        hooks.on_instruction_applied(&event);
+        let times = self.counting.hits(&start, &event);
+        self.zeros += times;
+        if times > 0 {
+            hooks.on_zero_crossing(&event, times);
+        }
+        true
+    }
+
+    /// Runs to the end, telling `hooks` about every step.
+    pub fn run_with_hooks(&mut self, hooks: &mut dyn SimulationHooks) {
+        while self.step_with(hooks) {}
+    }
+}
+
+/// Where the dial first points at zero, as found by `first_zero`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FirstZero {
+    /// Zero-based position of the instruction in the input.
+    pub index: usize,
+    pub instruction: Instruction,
+}
+
+impl ToJson for FirstZero {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("index", (self.index as u64).to_json()),
+            ("instruction", self.instruction.to_json()),
+        ])
+    }
+}
+
+impl fmt::Display for FirstZero {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "instruction {} ({})", self.index + 1, self.instruction)
+    }
+}
+
+/// The first instruction that `counting` counts, e.g. one that leaves the
+/// dial at zero or, for `ZeroCounting::Both`, also one that moves the dial
+/// through zero. Stops simulating as soon as it is found.
+pub fn first_zero(
+    start: &State,
+    instructions: &[Instruction],
+    counting: ZeroCounting,
+) -> Option<FirstZero> {
+    let simulation = Simulation::new(start.clone(), instructions, counting);
+    let mut stepper = Stepper::new(simulation);
+    if !stepper.run_until(|s| s.zero_count() > 0) {
+        return None;
+    }
+    let index = stepper.state().position() - 1;
+    Some(FirstZero {
+        index,
+        instruction: instructions[index],
+    })
+}
+
+impl Operation {
+    pub fn from_letter(op: &str) -> Option<Self> {
+        match op {
+            "L" => Some(Operation::Left),
+            "R" => Some(Operation::Right),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instruction {
+    pub operation: Operation,
+    pub argument: u32,
+}
+
+impl ToJson for Instruction {
+    fn to_json(&self) -> String {
+        let operation = match self.operation {
+            Operation::Left => "L",
+            Operation::Right => "R",
+        };
+        json::object(&[
+            ("operation", operation.to_json()),
+            ("argument", self.argument.to_json()),
+        ])
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op_str = match self.operation {
+            Operation::Left => "L",
+            Operation::Right => "R",
+        };
+        write!(f, "{}{}", op_str, self.argument)
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    /// One whole instruction, as written in the input.
+    fn from_str(s: &str) -> AocResult<Self> {
+        let (op, count) = parse_complete(s, parse_instruction)?;
+        Ok(Instruction::new(op, count))
+    }
+}
+
+impl Instruction {
+    pub fn new(op: Operation, count: u32) -> Self {
+        Instruction {
+            operation: op,
+            argument: count,
+        }
+    }
+
+    /// Reads the instruction as "turn, then walk `argument` steps", for when
+    /// the rotations describe a path on a grid rather than a dial.
+    pub fn walk(&self, turtle: &mut Turtle) {
+        match self.operation {
+            Operation::Left => turtle.turn_left(),
+            Operation::Right => turtle.turn_right(),
+        }
+        turtle.forward(self.argument as u64);
+    }
+}
+
+pub fn read_instructions_file(path: &str) -> AocResult<Vec<Instruction>> {
+    let content = input::read_to_string(path)?;
+    parse_instructions(&content)
+}
+
+/// Parses one instruction per line, reporting every bad line at once.
+pub fn parse_instructions(content: &str) -> AocResult<Vec<Instruction>> {
+    parse_lines(content, parse)
+}
+
+pub fn grammar() -> Rules {
+    Rules(vec![
+        ("input", Grammar::lines(Grammar::rule("instruction"))),
+        (
+            "instruction",
+            Grammar::seq([Grammar::rule("op"), Grammar::rule("count")]),
+        ),
+        ("op", Grammar::alt([Grammar::lit("L"), Grammar::lit("R")])),
+        ("count", Grammar::Digits),
+    ])
+}
+
+pub fn parse_op(input: &str) -> IResult<&str, Operation> {
+    alt((
+        tag("L").map_opt(|_| Some(Operation::Left)),
+        tag("R").map_opt(|_| Some(Operation::Right)),
+    ))
+    .parse(input)
+}
+
+pub fn parse_count(input: &str) -> IResult<&str, u32> {
+    unsigned(input)
+}
+
+/// `parse_count`, also taking hex (`0x1F`) and underscore-grouped
+/// (`1_000`) counts, as the extended dialect does.
+pub fn parse_count_relaxed(input: &str) -> IResult<&str, u32> {
+    relaxed_number(input)
+}
+
+pub fn parse_instruction(input: &str) -> IResult<&str, (Operation, u32)> {
+    pair(parse_op, parse_count).parse(input)
+}
+
+pub fn parse(line: &str) -> std::result::Result<Instruction, AocError> {
+    let (_remainder, (op, count)) = parse_instruction(line).map_err(|e| nom_error(line, e))?;
+
+    Ok(Instruction::new(op, count))
+}
+
+/// `parse` with the count read by `parse_count_relaxed`. The whole line
+/// must be consumed.
+pub fn parse_relaxed(line: &str) -> AocResult<Instruction> {
+    let (op, count) = parse_complete(line, |s| pair(parse_op, parse_count_relaxed).parse(s))?;
+    Ok(Instruction::new(op, count))
+}
+
+/// Zero counts of several independent locks, each a block of instructions
+/// turning its own copy of the starting dial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockCombination {
+    pub zero_counts: Vec<u32>,
+}
+
+impl LockCombination {
+    pub fn sum(&self) -> u64 {
+        self.zero_counts.iter().map(|&c| c as u64).sum()
+    }
+
+    pub fn product(&self) -> u64 {
+        self.zero_counts.iter().map(|&c| c as u64).product()
+    }
+}
+
+impl ToJson for LockCombination {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("zero_counts", self.zero_counts.to_json()),
+            ("sum", self.sum().to_json()),
+            ("product", self.product().to_json()),
+        ])
+    }
+}
+
+impl fmt::Display for LockCombination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, count) in self.zero_counts.iter().enumerate() {
+            writeln!(f, "Lock {}: {}", i + 1, count)?;
+        }
+        writeln!(f, "Sum: {}", self.sum())?;
+        write!(f, "Product: {}", self.product())
+    }
+}
+
+/// Solves each block of `input` as a lock whose dial begins as `start`.
+pub fn solve_locks(
+    input: &Input,
+    start: &State,
+    counting: ZeroCounting,
+) -> AocResult<LockCombination> {
+    let zero_counts = input
+        .blocks()
+        .into_iter()
+        .map(|block| {
+            let instructions = block
+                .into_iter()
+                .map(parse)
+                .collect::<AocResult<Vec<Instruction>>>()?;
+            Ok(start.clone().apply_multiple(instructions, counting))
+        })
+        .collect::<AocResult<Vec<u32>>>()?;
+    Ok(LockCombination { zero_counts })
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Instruction>;
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Vec<Instruction>> {
+        parse_instructions(input)
+    }
+
+    fn part1(&self, input: &Vec<Instruction>) -> AocResult<Answer> {
+        let zeros = State::new().apply_multiple(input.clone(), ZeroCounting::After);
+        Ok(Answer::from(zeros))
+    }
+
+    fn part2(&self, input: &Vec<Instruction>) -> AocResult<Answer> {
+        let zeros = State::new().apply_multiple(input.clone(), ZeroCounting::Both);
+        Ok(Answer::from(zeros))
+    }
+
+    fn param_names(&self) -> &'static [&'static str] {
+        &["start"]
+    }
+
+    fn part1_with(&self, input: &Vec<Instruction>, params: &Params) -> AocResult<Answer> {
+        let zeros = start_state(params)?.apply_multiple(input.clone(), ZeroCounting::After);
+        Ok(Answer::from(zeros))
+    }
+
+    fn part2_with(&self, input: &Vec<Instruction>, params: &Params) -> AocResult<Answer> {
+        let zeros = start_state(params)?.apply_multiple(input.clone(), ZeroCounting::Both);
+        Ok(Answer::from(zeros))
+    }
+}
+
+/// The dial state before any rotation, pointing at the `start` param if
+/// given.
+fn start_state(params: &Params) -> AocResult<State> {
+    let num = params.get_or("start", State::new().num)?;
+    if num >= DIAL_SIZE {
+        return Err(AocError::InvalidArgument(format!(
+            "start {} is off the dial, expected 0 to {}",
+            num,
+            DIAL_SIZE - 1
+        )));
+    }
+    Ok(State::starting_at(num))
+}
+
+pub fn describe() -> Description {
+    use clap::CommandFactory;
+    Description {
+        day: 1,
+        title: "Secret Entrance",
+        command: Config::command(),
+        modes: vec![
+            ModeDescription {
+                name: "after",
+                help: "count the times the dial is left pointing at 0 after a rotation",
+            },
+            ModeDescription {
+                name: "during",
+                help: "also count every time the dial passes 0 during a rotation",
+            },
+        ],
+        grammar: grammar(),
+        examples: vec![
+            Example {
+                args: "--input data/day01/test_input.txt",
+                help: "solve part 1 for the example input",
+            },
+            Example {
+                args: "--mode during -vv",
+                help: "solve part 2, narrating each rotation",
+            },
+        ],
+        worked: Vec::new(),
+        stars: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Direction, Point};
+
+    fn read_test_file() -> String {
+        input::read_to_string("data/day01/test_input.txt").expect("Failed to read test input file")
+    }
+
+    fn read_test_instructions() -> Vec<Instruction> {
+        read_instructions_file("data/day01/test_input.txt").expect("Failed to read test input file")
+    }
+
+    #[test]
+    fn test_read_file() {
+        let _ = read_test_file();
+    }
+
+    #[test]
+    fn test_parse_op() {
+        let (_remainder, o) = parse_op("L").expect("parser");
+        assert_eq!(o, Operation::Left);
+    }
+
+    #[test]
+    fn test_parse_count() {
+        let (_remainder, count) = parse_count("123").expect("parser");
+        assert_eq!(count, 123);
+        assert_eq!(parse_count_relaxed("0x7B"), Ok(("", 123)));
+        assert_eq!(
+            parse_relaxed("L1_000").expect("instruction"),
+            Instruction::new(Operation::Left, 1000)
+        );
+        assert!(parse_relaxed("L0x").is_err());
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        let (remainder, ell) = parse_instruction("L8").expect("parser");
+        assert_eq!(remainder, "");
+        assert_eq!(ell, (Operation::Left, 8));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let instruction: Instruction = "R48".parse().expect("instruction");
+        assert_eq!(instruction, Instruction::new(Operation::Right, 48));
+        assert_eq!(
+            instruction.to_string().parse::<Instruction>().ok(),
+            Some(instruction)
+        );
+        assert!("R48x".parse::<Instruction>().is_err());
+        assert!("".parse::<Instruction>().is_err());
+        assert_eq!(
+            "during".parse::<Mode>().ok(),
+            Some(Mode::CountZerosDuringRotation)
+        );
+        assert!("sometimes".parse::<Mode>().is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_dialect() {
+        use clap::Parser;
+        let config = Config::try_parse_from(["day01", "--dialect", "extended"]).expect("valid");
+        assert_eq!(config.dialect, Dialect::Extended);
+        assert!(Config::try_parse_from(["day01", "--dialect", "bogus"]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_mode_names() {
+        use serde::Deserialize;
+        use serde::de::IntoDeserializer;
+        use serde::de::value::{Error, StrDeserializer};
+        for mode in [
+            Mode::CountZerosAfterRotation,
+            Mode::CountZerosDuringRotation,
+        ] {
+            let name: StrDeserializer<Error> = mode.name().into_deserializer();
+            assert_eq!(Mode::deserialize(name), Ok(mode));
+        }
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+        let instructions = read_test_instructions();
+        assert_eq!(instructions.len(), 10);
+    }
+
+    #[test]
+    fn test_parse_instructions_reports_every_bad_line() {
+        let error = parse_instructions("L68\nX1\nR48\nL\n").expect_err("bad lines");
+        let AocError::Multiple(errors) = &error else {
+            panic!("expected several errors, got {}", error);
+        };
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[..],
+            [
+                AocError::Parse {
+                    line: 2,
+                    column: 1,
+                    ..
+                },
+                AocError::Parse {
+                    line: 4,
+                    column: 2,
+                    ..
+                }
+            ]
+        ));
+        assert!(
+            error
+                .to_string()
+                .starts_with("2 errors:\n  Parse error at line 2, column 1 of 'X1'")
+        );
+    }
+
+    #[test]
+    fn test_apply_instruction() {
+        let mut state = State::new();
+        state.apply(Instruction {
+            operation: Operation::Left,
+            argument: 68,
+        });
+        assert_eq!(state, State::starting_at(82));
+    }
+
+    #[test]
+    fn test_apply_test_data() {
+        let mut state = State::new();
+        let instructions = read_test_instructions();
+        let zero_count = state.apply_multiple(instructions, ZeroCounting::After);
+        assert_eq!(zero_count, 3);
+    }
+
+    #[test]
+    fn test_apply_instruction_count_during() {
+        let mut state = State::new();
+        let zero_count = state.apply(Instruction {
+            operation: Operation::Left,
+            argument: 68,
+        });
+        assert_eq!(zero_count, 1);
+    }
+
+    #[test]
+    fn test_apply_test_data_count_during() {
+        let mut state = State::new();
+        let instructions = read_test_instructions();
+        let zero_count = state.apply_multiple(instructions, ZeroCounting::Both);
+        assert_eq!(zero_count, 6);
+    }
+
+    #[test]
+    fn test_apply_stream() {
+        for (counting, expected) in [(ZeroCounting::After, 3), (ZeroCounting::Both, 6)] {
+            let instructions =
+                input::lines_iter("data/day01/test_input.txt", parse).expect("test input");
+            let mut state = State::new();
+            assert_eq!(
+                state
+                    .apply_stream(instructions, counting)
+                    .expect("zero count"),
+                (expected, 10)
+            );
+            let mut whole = State::new();
+            whole.apply_multiple(read_test_instructions(), counting);
+            assert_eq!(state, whole);
+        }
+        let mut state = State::new();
+        let instructions = ["L10", "x", "R5"].map(parse);
+        assert!(
+            state
+                .apply_stream(instructions, ZeroCounting::After)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_apply_multiple_with_events() {
+        let mut state = State::new();
+        let instructions = read_test_instructions();
+        let (zero_count, events) =
+            state.apply_multiple_with_events(&instructions, ZeroCounting::Both);
+        assert_eq!(zero_count, 6);
+        assert_eq!(events.len(), instructions.len());
+        assert_eq!(
+            events[0].narrate(ZeroCounting::Both),
+            "- The dial is rotated L68 to point at 82; during this rotation, it points at 0 1 times."
+        );
+        assert_eq!(
+            events[0].narrate(ZeroCounting::After),
+            "- The dial is rotated L68 to point at 82."
+        );
+        assert_eq!(events.last().map(|event| event.dial), Some(state.num));
+    }
+
+    #[test]
+    fn test_simulation_until_zero() {
+        let instructions = read_test_instructions();
+        let simulation = Simulation::new(State::new(), &instructions, ZeroCounting::After);
+        let mut stepper = Stepper::with_history(simulation, 4);
+        assert!(stepper.run_until(|s| s.state.num == 0));
+        assert_eq!(stepper.state().position(), 3);
+        assert!(stepper.rollback(1));
+        assert_eq!(stepper.state().state, State::starting_at(52));
+    }
+
+    #[test]
+    fn test_first_zero() {
+        let instructions = read_test_instructions();
+        let after =
+            first_zero(&State::new(), &instructions, ZeroCounting::After).expect("zero after");
+        assert_eq!(after.index, 2);
+        assert_eq!(after.to_string(), "instruction 3 (R48)");
+        let during =
+            first_zero(&State::new(), &instructions, ZeroCounting::Both).expect("zero during");
+        assert_eq!(during.index, 0);
+        assert_eq!(
+            first_zero(&State::new(), &instructions[..2], ZeroCounting::After),
+            None
+        );
+    }
+
+    #[test]
+    fn test_solve_locks() {
+        let input = Input::new("L68\nL30\nR48\n\nL50\nR100\n\n\nR50\n");
+        let start = State::new();
+        let locks = solve_locks(&input, &start, ZeroCounting::After).expect("locks");
+        assert_eq!(locks.zero_counts, vec![1, 2, 1]);
+        assert_eq!(locks.sum(), 4);
+        assert_eq!(locks.product(), 2);
+        let locks = solve_locks(&input, &start, ZeroCounting::Both).expect("locks");
+        assert_eq!(locks.zero_counts, vec![2, 2, 1]);
+        assert_eq!(
+            locks.to_string(),
+            "Lock 1: 2\nLock 2: 2\nLock 3: 1\nSum: 5\nProduct: 4"
+        );
+        assert!(solve_locks(&Input::new("L1\n\nX2\n"), &start, ZeroCounting::After).is_err());
+        let small = Input::new("L5\n\nR3\n");
+        let locks = solve_locks(&small, &State::with_size(10), ZeroCounting::After).expect("locks");
+        assert_eq!(locks.zero_counts, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_walk_instructions() {
+        let mut turtle = Turtle::default();
+        for instruction in ["R2", "L3", "L1"] {
+            parse(instruction).expect("instruction").walk(&mut turtle);
+        }
+        assert_eq!(turtle.position(), Point::new(1, -3));
+        assert_eq!(turtle.heading(), Direction::Left);
+    }
+
+    #[test]
+    fn test_other_dial_sizes() {
+        for size in 1..=12 {
+            for start in 0..size {
+                for argument in 0..=30 {
+                    for operation in [Operation::Left, Operation::Right] {
+                        let (mut dial, mut passes) = (start, 0);
+                        for notch in 1..=argument {
+                            dial = match operation {
+                                Operation::Left => (dial + size - 1) % size,
+                                Operation::Right => (dial + 1) % size,
+                            };
+                            passes += u32::from(dial == 0 && notch < argument);
+                        }
+                        let event = State { num: start, size }
+                            .rotate(Instruction::new(operation, argument));
+                        assert_eq!((event.dial, event.passes), (dial, passes));
+                    }
+                }
+            }
+        }
+        let instructions = parse_instructions("R7\nL20\nR13\n").expect("instructions");
+        let mut state = State::with_size(10);
+        assert_eq!(
+            state.apply_multiple(instructions.clone(), ZeroCounting::Both),
+            4
+        );
+        assert_eq!(state.num, 5);
+        assert_eq!(
+            State::with_size(10).apply_multiple(instructions.clone(), ZeroCounting::Target(7)),
+            4
+        );
+        assert_eq!(
+            State::with_size(10).position_after_repeats(&instructions, 3),
+            5
+        );
+    }
+
+    #[test]
+    fn test_position_after_repeats() {
+        let instructions = read_test_instructions();
+        let mut state = State::new();
+        for _ in 0..7 {
+            for &instruction in &instructions {
+                state.apply(instruction);
+            }
+        }
+        assert_eq!(
+            State::new().position_after_repeats(&instructions, 7),
+            state.num
+        );
+        assert_eq!(State::new().position_after_repeats(&instructions, 0), 50);
+    }
+
+    #[test]
+    fn test_big_rotation() {
+        let mut state = State::new();
+        let zero_count = state.apply(Instruction {
+            operation: Operation::Right,
+            argument: 1000,
+        });
+        assert_eq!(state.num, 50);
+        assert_eq!(zero_count, 10);
+    }
+
+    #[test]
+    fn test_fiddly_bits() {
+        let cases = vec![
+            (Operation::Left, 5, 5, 0, 0),
+            (Operation::Right, 5, 95, 0, 0),
+            (Operation::Left, 5, 0, 95, 0),
+            (Operation::Right, 5, 95, 0, 0),
+            (Operation::Right, 5, 0, 5, 0),
+            (Operation::Left, 100, 5, 5, 1),
+            (Operation::Right, 100, 5, 5, 1),
+        ];
+        let mut state = State::new();
+        for (op, arg, num, expected_num, expected_zeros) in cases {
+            state.num = num;
+            let zero_count = state.apply(Instruction {
+                operation: op,
+                argument: arg,
+            });
+            assert_eq!(state.num, expected_num);
+            assert_eq!(zero_count, expected_zeros);
+        }
+    }
+}
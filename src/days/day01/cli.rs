@@ -0,0 +1,223 @@
+use super::hooks::TerminalBell;
+use super::macros::{Dialect, expand};
+use super::stats::{InstructionStats, Wear};
+use super::trace::{self, Trace};
+use super::{Config, DIAL_SIZE, first_zero, parse, parse_instructions, solve_locks};
+use crate::artifacts;
+use crate::console;
+use crate::crash;
+use crate::error::AocError;
+use crate::input::{self, Input};
+use crate::json::{self, ToJson};
+use crate::logging;
+use crate::result::AocResult;
+use crate::run::{self, OutputFormat, Part, Phases, RunReport};
+use crate::sniff;
+use tracing::info_span;
+
+/// Runs the day 1 command line with `args`, printing to stdout. Shared by
+/// the `day01` binary and `aoc day01`. Returns false if `--bench`
+/// regressed against `--compare-baseline`.
+pub fn run(args: Config) -> AocResult<bool> {
+    let common = &args.common;
+    if let Some(target) = args.target
+        && target >= args.dial_size
+    {
+        return Err(AocError::InvalidArgument(format!(
+            "--target {} is off a dial numbered 0 to {}",
+            target,
+            args.dial_size - 1
+        )));
+    }
+    let counting = args.counting();
+    logging::init(common.verbosity, common.log_format);
+    let input = common.input_path(1);
+    crash::install(1, &input);
+    let _span = info_span!("day", day = 1).entered();
+    if let Some(part) = common.part {
+        run::run_part(1, &input, part, common)?;
+        return Ok(true);
+    }
+    if common.bench {
+        return run::run_bench(1, &input, Part::Both, common);
+    }
+    if args.stream {
+        run_stream(&args, &input)?;
+        return Ok(true);
+    }
+    let mut run_dir = common.run_dir(1);
+    let mut phases = Phases::new();
+    let content = phases.time(
+        "read",
+        || input::read_to_string(&input),
+        |r| r.as_ref().map_or(0, |c| c.len()),
+    )?;
+    crash::set_input(&content);
+    let content = match args.dialect {
+        Dialect::Vanilla => content,
+        Dialect::Extended => phases
+            .time(
+                "expand",
+                || expand(&content),
+                |r| r.as_ref().map_or(0, |c| c.len()),
+            )
+            .map_err(|e| e.in_file(&input))?,
+    };
+    sniff::check(1, &content).map_err(|e| e.in_file(&input))?;
+    if args.locks {
+        let locks = solve_locks(&Input::new(content), &args.start(), counting)
+            .map_err(|e| e.in_file(&input))?;
+        match common.output {
+            OutputFormat::Text => println!("{}", locks),
+            OutputFormat::Json => println!(
+                "{}",
+                run::part_json(1, args.mode.part(), &[("locks", locks.to_json())])
+            ),
+        }
+        return Ok(true);
+    }
+    let instructions = phases
+        .time(
+            "parse",
+            || parse_instructions(&content),
+            |r| r.as_ref().map_or(0, |i| i.len()),
+        )
+        .map_err(|e| e.in_file(&input))?;
+    if let Some(path) = &common.dump_parsed {
+        let path = artifacts::place(&mut run_dir, "parsed", path)?;
+        json::dump(&path, &instructions)?;
+    }
+    if args.stats {
+        let stats = InstructionStats::from(&instructions[..]);
+        match common.output {
+            OutputFormat::Text => print!("{}", stats),
+            OutputFormat::Json => println!(
+                "{}",
+                run::part_json(1, args.mode.part(), &[("stats", stats.to_json())])
+            ),
+        }
+        return Ok(true);
+    }
+    if let Some(path) = &args.trace {
+        let path = artifacts::place(&mut run_dir, trace::ARTIFACT, path)?;
+        let trace = Trace::record(&args.start(), &instructions, args.mode, args.target);
+        std::fs::write(&path, trace.render(args.trace_format))
+            .map_err(|e| AocError::io(&path, e))?;
+    }
+    if args.first_zero {
+        let found = first_zero(&args.start(), &instructions, counting);
+        match common.output {
+            OutputFormat::Text => match found {
+                Some(found) => println!("First zero: {}", found),
+                None => println!("First zero: never"),
+            },
+            OutputFormat::Json => println!(
+                "{}",
+                run::part_json(
+                    1,
+                    args.mode.part(),
+                    &[(
+                        "first_zero",
+                        found.map_or("null".to_string(), |f| f.to_json())
+                    )]
+                )
+            ),
+        }
+        return Ok(true);
+    }
+    let count = instructions.len();
+    let wear = Wear::from(&instructions[..]);
+    let mut state = args.start();
+    let zero_count = phases.time(
+        "solve",
+        || {
+            if args.bell {
+                let mut bell = TerminalBell::new(std::io::stderr());
+                state.apply_multiple_with_hooks(&instructions, counting, &mut bell)
+            } else if logging::narrative_enabled() {
+                let (zero_count, events) =
+                    state.apply_multiple_with_events(&instructions, counting);
+                console::render(events.iter().map(|event| event.narrate(counting)));
+                zero_count
+            } else {
+                state.apply_multiple(instructions, counting)
+            }
+        },
+        |_| count,
+    );
+    let answer = phases.time("report", || zero_count.to_string(), |_| 1);
+    let report = RunReport {
+        day: 1,
+        part: args.mode.part(),
+        mode: args.mode.name().to_string(),
+        settings: target_setting(&args),
+        metrics: wear.metrics(),
+        answer,
+        phases: phases.into_vec(),
+    };
+    match common.output {
+        OutputFormat::Text => {
+            println!("Zero count: {}", common.group_digits.apply(&report.answer));
+            common.print_timings(&report.timings());
+        }
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
+    artifacts::finish(run_dir, &report)?;
+    Ok(true)
+}
+
+/// Handles `--stream`: solves the instructions as they are read, without
+/// the whole-input checks that need the text in memory.
+fn run_stream(args: &Config, input: &str) -> AocResult<()> {
+    let common = &args.common;
+    let mut phases = Phases::new();
+    let mut state = args.start();
+    let mut wear = Wear::default();
+    let (zero_count, _) = phases
+        .time(
+            "solve",
+            || {
+                let instructions = input::lines_iter(input, parse)?.inspect(|instruction| {
+                    if let Ok(instruction) = instruction {
+                        wear.record(*instruction);
+                    }
+                });
+                state.apply_stream(instructions, args.counting())
+            },
+            |r| r.as_ref().map_or(0, |(_, count)| *count),
+        )
+        .map_err(|e| e.in_file(input))?;
+    let answer = phases.time("report", || zero_count.to_string(), |_| 1);
+    let report = RunReport {
+        day: 1,
+        part: args.mode.part(),
+        mode: args.mode.name().to_string(),
+        settings: [("stream", "true".to_string())]
+            .into_iter()
+            .chain(target_setting(args))
+            .collect(),
+        metrics: wear.metrics(),
+        answer,
+        phases: phases.into_vec(),
+    };
+    match common.output {
+        OutputFormat::Text => {
+            println!("Zero count: {}", common.group_digits.apply(&report.answer));
+            common.print_timings(&report.timings());
+        }
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
+    artifacts::finish(common.run_dir(1), &report)
+}
+
+/// `--target` and `--dial-size` for the run report, where they differ from
+/// counting zeros on the puzzle's dial.
+fn target_setting(args: &Config) -> Vec<(&'static str, String)> {
+    let dial_size =
+        (args.dial_size != DIAL_SIZE).then(|| ("dial_size", args.dial_size.to_string()));
+    args.target
+        .map(|target| ("target", target.to_string()))
+        .into_iter()
+        .chain(dial_size)
+        .collect()
+}
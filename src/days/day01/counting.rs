@@ -0,0 +1,94 @@
+use std::fmt;
+
+use super::{Mode, RotationEvent, State};
+
+/// What a run counts as the dial turns. Each rotation contributes
+/// `hits`, so simulations, streams and traces all count the same way.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ZeroCounting {
+    /// Rotations that leave the dial at 0.
+    After,
+    /// Times a rotation carries the dial past 0 without stopping there.
+    During,
+    /// `After` and `During` together: every time the dial points at 0.
+    Both,
+    /// Every time the dial points at `n`, passing it or stopping there.
+    Target(u32),
+}
+
+impl ZeroCounting {
+    /// How many times the rotation in `event`, which started from `start`,
+    /// counts.
+    pub fn hits(self, start: &State, event: &RotationEvent) -> u32 {
+        let stopped = u32::from(event.dial == 0);
+        match self {
+            ZeroCounting::After => stopped,
+            ZeroCounting::During => event.passes,
+            ZeroCounting::Both => stopped + event.passes,
+            ZeroCounting::Target(n) => {
+                // Turning the dial so `n` sits at 0 reduces this to `Both`.
+                let size = start.size;
+                let mut shifted = State {
+                    num: (start.num + size - n % size) % size,
+                    size,
+                };
+                let event = shifted.rotate(event.instruction);
+                u32::from(event.dial == 0) + event.passes
+            }
+        }
+    }
+
+    /// Whether passes through 0 mid-rotation count, and so are worth
+    /// narrating.
+    pub fn counts_passes(self) -> bool {
+        matches!(self, ZeroCounting::During | ZeroCounting::Both)
+    }
+}
+
+impl From<Mode> for ZeroCounting {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::CountZerosAfterRotation => ZeroCounting::After,
+            Mode::CountZerosDuringRotation => ZeroCounting::Both,
+        }
+    }
+}
+
+impl fmt::Display for ZeroCounting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZeroCounting::After => write!(f, "after"),
+            ZeroCounting::During => write!(f, "during"),
+            ZeroCounting::Both => write!(f, "both"),
+            ZeroCounting::Target(n) => write!(f, "target {}", n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::parse_instructions;
+
+    fn count(counting: ZeroCounting, instructions: &str) -> u32 {
+        let instructions = parse_instructions(instructions).expect("instructions");
+        State::new().apply_multiple(instructions, counting)
+    }
+
+    #[test]
+    fn test_strategies() {
+        let example = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        assert_eq!(count(ZeroCounting::After, example), 3);
+        assert_eq!(count(ZeroCounting::During, example), 3);
+        assert_eq!(count(ZeroCounting::Both, example), 6);
+        assert_eq!(count(ZeroCounting::Target(0), example), 6);
+        assert_eq!(count(ZeroCounting::Target(50), "R10\nL20\nR10\nR100\n"), 3);
+        assert_eq!(count(ZeroCounting::Target(99), "R49\nR1\nL2\n"), 2);
+        assert_eq!(
+            ZeroCounting::from(Mode::CountZerosDuringRotation),
+            ZeroCounting::Both
+        );
+        assert_eq!(ZeroCounting::Target(7).to_string(), "target 7");
+        assert!(!ZeroCounting::After.counts_passes());
+    }
+}
@@ -0,0 +1,85 @@
+use std::io::Write;
+
+use super::RotationEvent;
+
+/// Callbacks a `Simulation` makes as it runs, for consumers such as a UI,
+/// notifications or statistics that want to follow along without changing
+/// how rotations are applied. Every method does nothing by default.
+pub trait SimulationHooks {
+    /// Called after every rotation.
+    fn on_instruction_applied(&mut self, _event: &RotationEvent) {}
+
+    /// Called after a rotation the simulation's `ZeroCounting` counted, with
+    /// the number of times it counted.
+    fn on_zero_crossing(&mut self, _event: &RotationEvent, _times: u32) {}
+}
+
+/// Hooks that ignore every event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHooks;
+
+impl SimulationHooks for NoHooks {}
+
+/// Rings the terminal bell each time the dial reaches 0.
+#[derive(Debug)]
+pub struct TerminalBell<W: Write> {
+    out: W,
+}
+
+impl<W: Write> TerminalBell<W> {
+    pub fn new(out: W) -> Self {
+        TerminalBell { out }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> SimulationHooks for TerminalBell<W> {
+    fn on_zero_crossing(&mut self, _event: &RotationEvent, times: u32) {
+        for _ in 0..times {
+            let _ = self.out.write_all(b"\x07");
+        }
+        let _ = self.out.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::{Simulation, State, ZeroCounting, parse_instructions};
+
+    #[derive(Default)]
+    struct Counter {
+        applied: usize,
+        zeros: u32,
+    }
+
+    impl SimulationHooks for Counter {
+        fn on_instruction_applied(&mut self, _event: &RotationEvent) {
+            self.applied += 1;
+        }
+
+        fn on_zero_crossing(&mut self, _event: &RotationEvent, times: u32) {
+            self.zeros += times;
+        }
+    }
+
+    #[test]
+    fn test_hooks() {
+        let instructions = parse_instructions("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n")
+            .expect("instructions");
+        for (counting, zeros) in [(ZeroCounting::After, 3), (ZeroCounting::Both, 6)] {
+            let mut counter = Counter::default();
+            let mut simulation = Simulation::new(State::new(), &instructions, counting);
+            simulation.run_with_hooks(&mut counter);
+            assert_eq!(counter.applied, instructions.len());
+            assert_eq!(counter.zeros, zeros);
+            assert_eq!(simulation.zero_count(), zeros);
+        }
+        let mut bell = TerminalBell::new(Vec::new());
+        State::new().apply_multiple_with_hooks(&instructions, ZeroCounting::After, &mut bell);
+        assert_eq!(bell.into_inner(), b"\x07\x07\x07");
+    }
+}
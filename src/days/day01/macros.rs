@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use super::{Instruction, parse_relaxed};
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// Most instructions an extended input may expand to, so a few nested
+/// repeats can't exhaust memory.
+pub const MAX_EXPANDED: usize = 10_000_000;
+
+/// Which input language to accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// One instruction per line, as in the puzzle.
+    #[default]
+    Vanilla,
+    /// Adds named macros, see `expand`.
+    Extended,
+}
+
+impl From<&str> for Dialect {
+    fn from(s: &str) -> Self {
+        match s {
+            "extended" => Dialect::Extended,
+            _ => Dialect::Vanilla,
+        }
+    }
+}
+
+/// Expands the extended dialect into vanilla input, one instruction per
+/// line, keeping blank lines so lock blocks survive.
+///
+/// `DEF name = <items>` defines a macro and produces no output. Any other
+/// line is a list of items. An item is an instruction such as `L10`, or
+/// the name of a macro defined on an earlier line. `xN` after an item
+/// repeats it `N` times, so `spin x3` runs `spin` three times.
+/// Instruction counts may be hex or underscore-grouped, as in `R0x1F` or
+/// `L1_000`.
+pub fn expand(input: &str) -> AocResult<String> {
+    let mut macros: HashMap<&str, Vec<Instruction>> = HashMap::new();
+    let mut out = String::new();
+    let mut expanded = 0;
+    for (number, line) in input.lines().enumerate() {
+        let at_line = |e: AocError| match e {
+            AocError::ParseError(_) | AocError::NomError(_) | AocError::Parse { .. } => {
+                e.at_line(number + 1, line)
+            }
+            e => e,
+        };
+        let line = line.trim();
+        if let Some(definition) = line.strip_prefix("DEF ") {
+            let (name, body) = definition.split_once('=').ok_or_else(|| {
+                at_line(AocError::ParseError(
+                    "expected 'DEF name = ...'".to_string(),
+                ))
+            })?;
+            let name = name.trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(at_line(AocError::ParseError(format!(
+                    "invalid macro name '{}'",
+                    name
+                ))));
+            }
+            let body = expand_items(body, &macros).map_err(at_line)?;
+            macros.insert(name, body);
+            continue;
+        }
+        for instruction in expand_items(line, &macros).map_err(at_line)? {
+            expanded += 1;
+            if expanded > MAX_EXPANDED {
+                return Err(AocError::Unsupported(format!(
+                    "input expands to more than {} instructions",
+                    MAX_EXPANDED
+                )));
+            }
+            out.push_str(&instruction.to_string());
+            out.push('\n');
+        }
+        if line.is_empty() {
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+fn expand_items(
+    items: &str,
+    macros: &HashMap<&str, Vec<Instruction>>,
+) -> AocResult<Vec<Instruction>> {
+    let mut out: Vec<Instruction> = Vec::new();
+    let mut last: Option<Vec<Instruction>> = None;
+    for token in items.split_whitespace() {
+        if let Some(times) = repeat_count(token) {
+            let item = last.take().ok_or_else(|| {
+                AocError::ParseError(format!("'{}' does not follow an item", token))
+            })?;
+            if item.len().saturating_mul(times) > MAX_EXPANDED {
+                return Err(AocError::Unsupported(format!(
+                    "'{}' expands to more than {} instructions",
+                    token, MAX_EXPANDED
+                )));
+            }
+            // The item was pushed once already.
+            for _ in 1..times {
+                out.extend_from_slice(&item);
+            }
+            continue;
+        }
+        let item = match macros.get(token) {
+            Some(body) => body.clone(),
+            None if token.starts_with(['L', 'R']) => vec![parse_relaxed(token)?],
+            None => {
+                return Err(AocError::ParseError(format!("unknown macro '{}'", token)));
+            }
+        };
+        out.extend_from_slice(&item);
+        last = Some(item);
+    }
+    Ok(out)
+}
+
+/// `N` for a repeat token `xN` with `N` at least 1.
+fn repeat_count(token: &str) -> Option<usize> {
+    token
+        .strip_prefix('x')?
+        .parse()
+        .ok()
+        .filter(|&times| times >= 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::{State, ZeroCounting, parse_instructions};
+
+    #[test]
+    fn test_expand() {
+        let input = "DEF spin = L10 R20\nDEF twice = spin x2\nspin x3\nL5\n\ntwice R1\n";
+        assert_eq!(
+            expand(input).expect("expanded"),
+            "L10\nR20\nL10\nR20\nL10\nR20\nL5\n\nL10\nR20\nL10\nR20\nR1\n"
+        );
+        let plain = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        assert_eq!(expand(plain).expect("expanded"), plain);
+        assert_eq!(
+            expand("R0x1F L1_000\nDEF big = R0X10 x2\nbig\n").expect("expanded"),
+            "R31\nL1000\nR16\nR16\n"
+        );
+        let expanded = expand("DEF back = L68 L30\nback R48\n").expect("expanded");
+        let instructions = parse_instructions(&expanded).expect("instructions");
+        assert_eq!(
+            State::new().apply_multiple(instructions, ZeroCounting::After),
+            1
+        );
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let error = expand("L1\nspin\n").expect_err("unknown macro");
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 1 of 'spin': Parse error: unknown macro 'spin'"
+        );
+        assert!(expand("x2\n").is_err());
+        assert!(expand("DEF spin L1\n").is_err());
+        assert!(expand("DEF bad name = L1\n").is_err());
+        assert!(expand("L1 x0\n").is_err());
+        assert!(expand("L1__0\n").is_err());
+        assert!(expand("R0x\n").is_err());
+        assert!(matches!(
+            expand("DEF a = L1 x10000\nDEF b = a x10000\nb\n"),
+            Err(AocError::Unsupported(_))
+        ));
+        assert_eq!(Dialect::from("extended"), Dialect::Extended);
+    }
+}
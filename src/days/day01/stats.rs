@@ -0,0 +1,237 @@
+use std::fmt;
+
+use super::hooks::SimulationHooks;
+use super::{Instruction, Operation, RotationEvent};
+use crate::json::{self, ToJson};
+
+/// Width of the widest histogram bar in the text report.
+const BAR_WIDTH: usize = 40;
+
+/// Shape of an instruction list: how many turns go each way, how big they
+/// are, and where they leave the dial overall.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InstructionStats {
+    pub left: usize,
+    pub right: usize,
+    /// `histogram[d]` counts arguments with `d` digits, `0` counted as one
+    /// digit.
+    pub histogram: Vec<usize>,
+    pub min_argument: Option<u32>,
+    pub max_argument: Option<u32>,
+    /// Total clicks turned right minus total turned left.
+    pub net_rotation: i64,
+    pub wear: Wear,
+}
+
+impl InstructionStats {
+    pub fn total(&self) -> usize {
+        self.left + self.right
+    }
+
+    fn bucket_label(digits: usize) -> String {
+        match digits {
+            1 => "0-9".to_string(),
+            d => format!("{}-{}", 10u64.pow(d as u32 - 1), 10u64.pow(d as u32) - 1),
+        }
+    }
+}
+
+impl From<&[Instruction]> for InstructionStats {
+    fn from(instructions: &[Instruction]) -> Self {
+        let mut stats = InstructionStats::default();
+        for instruction in instructions {
+            let argument = instruction.argument;
+            match instruction.operation {
+                Operation::Left => {
+                    stats.left += 1;
+                    stats.net_rotation -= argument as i64;
+                }
+                Operation::Right => {
+                    stats.right += 1;
+                    stats.net_rotation += argument as i64;
+                }
+            }
+            let digits = (argument.checked_ilog10().unwrap_or(0) + 1) as usize;
+            if stats.histogram.len() <= digits {
+                stats.histogram.resize(digits + 1, 0);
+            }
+            stats.histogram[digits] += 1;
+            stats.min_argument = Some(stats.min_argument.map_or(argument, |m| m.min(argument)));
+            stats.max_argument = Some(stats.max_argument.map_or(argument, |m| m.max(argument)));
+            stats.wear.record(*instruction);
+        }
+        stats
+    }
+}
+
+/// How hard a run works the dial: how far it turns in all, how often it
+/// reverses and its longest single turn. Follows a `Simulation` as
+/// `SimulationHooks`, or is built from the instructions directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Wear {
+    /// Notches turned, whichever way.
+    pub notches: u64,
+    /// Rotations turning the other way from the one before.
+    pub direction_changes: u32,
+    pub longest_rotation: u32,
+    last: Option<Operation>,
+}
+
+impl Wear {
+    pub fn record(&mut self, instruction: Instruction) {
+        self.notches += instruction.argument as u64;
+        self.longest_rotation = self.longest_rotation.max(instruction.argument);
+        if self.last.is_some_and(|last| last != instruction.operation) {
+            self.direction_changes += 1;
+        }
+        self.last = Some(instruction.operation);
+    }
+
+    /// The metrics as `RunReport` metrics.
+    pub fn metrics(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("notches", self.notches),
+            ("direction_changes", self.direction_changes.into()),
+            ("longest_rotation", self.longest_rotation.into()),
+        ]
+    }
+}
+
+impl From<&[Instruction]> for Wear {
+    fn from(instructions: &[Instruction]) -> Self {
+        let mut wear = Wear::default();
+        for &instruction in instructions {
+            wear.record(instruction);
+        }
+        wear
+    }
+}
+
+impl SimulationHooks for Wear {
+    fn on_instruction_applied(&mut self, event: &RotationEvent) {
+        self.record(event.instruction);
+    }
+}
+
+impl fmt::Display for Wear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} notches, {} direction changes, longest rotation {}",
+            self.notches, self.direction_changes, self.longest_rotation
+        )
+    }
+}
+
+impl ToJson for Wear {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("notches", self.notches.to_json()),
+            ("direction_changes", self.direction_changes.to_json()),
+            ("longest_rotation", self.longest_rotation.to_json()),
+        ])
+    }
+}
+
+impl fmt::Display for InstructionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Instructions: {} ({} L, {} R)",
+            self.total(),
+            self.left,
+            self.right
+        )?;
+        if let (Some(min), Some(max)) = (self.min_argument, self.max_argument) {
+            writeln!(f, "Arguments: {} to {}", min, max)?;
+        }
+        writeln!(f, "Net rotation: {}", self.net_rotation)?;
+        writeln!(f, "Wear: {}", self.wear)?;
+        let widest = self.histogram.iter().copied().max().unwrap_or(0).max(1);
+        for (digits, &count) in self.histogram.iter().enumerate().skip(1) {
+            writeln!(
+                f,
+                "{:>11} {:>6} {}",
+                Self::bucket_label(digits),
+                count,
+                "#".repeat(count * BAR_WIDTH / widest)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ToJson for InstructionStats {
+    fn to_json(&self) -> String {
+        let histogram: Vec<String> = self
+            .histogram
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(digits, &count)| {
+                json::object(&[
+                    ("bucket", Self::bucket_label(digits).to_json()),
+                    ("count", (count as u64).to_json()),
+                ])
+            })
+            .collect();
+        let optional = |value: Option<u32>| value.map_or("null".to_string(), |v| v.to_json());
+        json::object(&[
+            ("left", (self.left as u64).to_json()),
+            ("right", (self.right as u64).to_json()),
+            ("min_argument", optional(self.min_argument)),
+            ("max_argument", optional(self.max_argument)),
+            ("net_rotation", self.net_rotation.to_string()),
+            ("wear", self.wear.to_json()),
+            ("histogram", format!("[{}]", histogram.join(","))),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::{State, ZeroCounting, parse_instructions};
+
+    #[test]
+    fn test_stats() {
+        let instructions =
+            parse_instructions("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\nR1000\n")
+                .expect("instructions");
+        let stats = InstructionStats::from(&instructions[..]);
+        assert_eq!((stats.left, stats.right), (7, 4));
+        assert_eq!(stats.histogram, vec![0, 2, 8, 0, 1]);
+        assert_eq!(stats.min_argument, Some(1));
+        assert_eq!(stats.max_argument, Some(1000));
+        assert_eq!(
+            stats.net_rotation,
+            48 + 60 + 14 + 1000 - 68 - 30 - 5 - 55 - 1 - 99 - 82
+        );
+        let text = stats.to_string();
+        assert!(text.starts_with("Instructions: 11 (7 L, 4 R)\n"));
+        assert!(text.contains("Wear: 1462 notches, 7 direction changes, longest rotation 1000\n"));
+        assert!(text.contains("      10-99      8 ########################################\n"));
+        assert!(text.contains("    100-999      0 \n"));
+        assert_eq!(
+            stats.to_json(),
+            "{\"left\":7,\"right\":4,\"min_argument\":1,\"max_argument\":1000,\
+             \"net_rotation\":782,\"wear\":{\"notches\":1462,\"direction_changes\":7,\
+             \"longest_rotation\":1000},\"histogram\":[{\"bucket\":\"0-9\",\"count\":2},\
+             {\"bucket\":\"10-99\",\"count\":8},{\"bucket\":\"100-999\",\"count\":0},\
+             {\"bucket\":\"1000-9999\",\"count\":1}]}"
+        );
+        assert!(InstructionStats::from(&[][..]).to_json().contains("null"));
+
+        let mut followed = Wear::default();
+        State::new().apply_multiple_with_hooks(&instructions, ZeroCounting::After, &mut followed);
+        assert_eq!(followed, stats.wear);
+        assert_eq!(
+            followed.metrics(),
+            [
+                ("notches", 1462),
+                ("direction_changes", 7),
+                ("longest_rotation", 1000)
+            ]
+        );
+    }
+}
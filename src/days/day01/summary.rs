@@ -0,0 +1,200 @@
+use super::{Instruction, State, ZeroCounting};
+
+/// The effect of running a block of instructions, for every possible
+/// starting position of the dial at once.
+///
+/// Summaries of consecutive blocks `combine` associatively into the summary
+/// of the whole sequence, so blocks can be summarised independently (or in
+/// parallel) and folded together afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationSummary {
+    net_offset: u32,
+    /// For each start position, times the dial is left at zero and times it
+    /// moves through zero without stopping there. One entry per notch.
+    zero_crossings_fn: Vec<(u64, u64)>,
+}
+
+impl Default for RotationSummary {
+    fn default() -> Self {
+        Self::identity(super::DIAL_SIZE)
+    }
+}
+
+impl RotationSummary {
+    /// Summary of running no instructions on a dial of `size` notches.
+    pub fn identity(size: u32) -> Self {
+        RotationSummary {
+            net_offset: 0,
+            zero_crossings_fn: vec![(0, 0); size as usize],
+        }
+    }
+
+    /// Summary of `instruction` on a dial of `size` notches.
+    pub fn of_instruction(instruction: Instruction, size: u32) -> Self {
+        let zero_crossings_fn = (0..size)
+            .map(|start| {
+                let mut state = State { num: start, size };
+                let passes = state.apply(instruction);
+                ((state.num == 0) as u64, passes as u64)
+            })
+            .collect();
+        let mut end = State { num: 0, size };
+        end.apply(instruction);
+        RotationSummary {
+            net_offset: end.num,
+            zero_crossings_fn,
+        }
+    }
+
+    /// Summary of `instructions` run in order on a dial of `size` notches.
+    pub fn of_instructions(instructions: &[Instruction], size: u32) -> Self {
+        instructions
+            .iter()
+            .map(|&instruction| RotationSummary::of_instruction(instruction, size))
+            .fold(RotationSummary::identity(size), |acc, next| {
+                acc.combine(&next)
+            })
+    }
+
+    /// Notches on the dial the summary is for.
+    pub fn size(&self) -> u32 {
+        self.zero_crossings_fn.len() as u32
+    }
+
+    /// How far the block turns the dial to the right, modulo its size.
+    pub fn net_offset(&self) -> u32 {
+        self.net_offset
+    }
+
+    pub fn end_position(&self, start: u32) -> u32 {
+        (start + self.net_offset) % self.size()
+    }
+
+    /// What `counting` counts when the block starts at `start`.
+    pub fn zero_crossings(&self, start: u32, counting: ZeroCounting) -> u64 {
+        let size = self.size();
+        let (after, during) = self.zero_crossings_fn[(start % size) as usize];
+        match counting {
+            ZeroCounting::After => after,
+            ZeroCounting::During => during,
+            ZeroCounting::Both => after + during,
+            // Pointing at `n` from `start` is pointing at 0 from `n` less.
+            ZeroCounting::Target(n) => {
+                self.zero_crossings(start % size + size - n % size, ZeroCounting::Both)
+            }
+        }
+    }
+
+    /// Summary of running `self` and then `next`, which must be for the
+    /// same size of dial.
+    pub fn combine(&self, next: &RotationSummary) -> RotationSummary {
+        assert_eq!(self.size(), next.size(), "summaries of different dials");
+        let zero_crossings_fn = self
+            .zero_crossings_fn
+            .iter()
+            .enumerate()
+            .map(|(start, &(a_after, a_during))| {
+                let middle = self.end_position(start as u32) as usize;
+                let (b_after, b_during) = next.zero_crossings_fn[middle];
+                (a_after + b_after, a_during + b_during)
+            })
+            .collect();
+        RotationSummary {
+            net_offset: (self.net_offset + next.net_offset) % self.size(),
+            zero_crossings_fn,
+        }
+    }
+}
+
+/// The summary on the puzzle's dial.
+impl From<&[Instruction]> for RotationSummary {
+    fn from(instructions: &[Instruction]) -> Self {
+        RotationSummary::of_instructions(instructions, super::DIAL_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::{Operation, parse_instructions};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_matches_simulation() {
+        let instructions = parse_instructions("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n")
+            .expect("instructions");
+        let summary = RotationSummary::from(instructions.as_slice());
+        assert_eq!(summary.zero_crossings(50, ZeroCounting::After), 3);
+        assert_eq!(summary.zero_crossings(50, ZeroCounting::Both), 6);
+        assert_eq!(summary.size(), 100);
+        assert_eq!(RotationSummary::default(), RotationSummary::identity(100));
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        (prop::bool::ANY, 0u32..1000).prop_map(|(left, argument)| {
+            let operation = if left {
+                Operation::Left
+            } else {
+                Operation::Right
+            };
+            Instruction::new(operation, argument)
+        })
+    }
+
+    fn instructions() -> impl Strategy<Value = Vec<Instruction>> {
+        prop::collection::vec(instruction(), 0..30)
+    }
+
+    /// A dial size, `instructions` and two cut points splitting them into
+    /// three ranges.
+    fn split_instructions() -> impl Strategy<Value = (u32, Vec<Instruction>, usize, usize)> {
+        (1u32..150, instructions()).prop_flat_map(|(size, instructions)| {
+            let len = instructions.len();
+            (Just(size), Just(instructions), 0..=len, 0..=len)
+                .prop_map(|(size, instructions, i, j)| (size, instructions, i.min(j), i.max(j)))
+        })
+    }
+
+    /// A dial size with a start position and a target on it.
+    fn dial() -> impl Strategy<Value = (u32, u32, u32)> {
+        (1u32..150).prop_flat_map(|size| (Just(size), 0..size, 0..size))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_combine_is_associative((size, instructions, i, j) in split_instructions()) {
+            let a = RotationSummary::of_instructions(&instructions[..i], size);
+            let b = RotationSummary::of_instructions(&instructions[i..j], size);
+            let c = RotationSummary::of_instructions(&instructions[j..], size);
+            prop_assert_eq!(a.combine(&b).combine(&c), a.combine(&b.combine(&c)));
+            prop_assert_eq!(
+                a.combine(&b).combine(&c),
+                RotationSummary::of_instructions(&instructions, size)
+            );
+        }
+
+        #[test]
+        fn prop_identity(size in 1u32..150, instructions in instructions()) {
+            let summary = RotationSummary::of_instructions(&instructions, size);
+            let identity = RotationSummary::identity(size);
+            prop_assert_eq!(identity.combine(&summary), summary.clone());
+            prop_assert_eq!(summary.combine(&identity), summary);
+        }
+
+        #[test]
+        fn prop_matches_simulation(instructions in instructions(), (size, start, target) in dial()) {
+            let summary = RotationSummary::of_instructions(&instructions, size);
+            for counting in [
+                ZeroCounting::After,
+                ZeroCounting::During,
+                ZeroCounting::Both,
+                ZeroCounting::Target(target),
+            ] {
+                let mut state = State { num: start, size };
+                let expected = state.apply_multiple(instructions.clone(), counting);
+                prop_assert_eq!(summary.zero_crossings(start, counting), expected as u64);
+                prop_assert_eq!(summary.end_position(start), state.num);
+            }
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::take_till,
+    character::complete::{char, multispace0},
+    multi::separated_list0,
+    sequence::{delimited, separated_pair},
+};
+
+use super::{DIAL_SIZE, Instruction, Mode, RotationEvent, State, ZeroCounting, parse};
+use crate::artifacts;
+use crate::error::AocError;
+use crate::parse::{parse_complete, signed};
+use crate::result::AocResult;
+
+/// Identifies a day01 trace file in its header line.
+pub const TRACE_SCHEMA: &str = "aoc25/day01-trace";
+
+/// Bumped whenever a field is added, removed or changes meaning. Version
+/// 2 added `dial_size` and `target`; version 1 traces are of the puzzle's
+/// dial.
+pub const TRACE_VERSION: i64 = 2;
+
+/// Kind of the traces `--trace` writes into a run directory.
+pub const ARTIFACT: &str = "trace";
+
+/// Deletes the traces written into the run directories under `base`, or
+/// only lists them if `dry_run`, returning their paths. Traces written
+/// without `--out-dir` aren't tracked.
+pub fn clean(base: &Path, dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    artifacts::clean_artifacts(base, ARTIFACT, dry_run)
+}
+
+/// One rotation of the dial as recorded in a trace.
+pub type TraceStep = RotationEvent;
+
+/// How `--trace` writes the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraceFormat {
+    /// `Trace::to_jsonl`, which `replay` reads back.
+    #[default]
+    Jsonl,
+    /// `Trace::to_csv`, for plotting the dial's path.
+    Csv,
+}
+
+impl From<&str> for TraceFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "csv" => TraceFormat::Csv,
+            _ => TraceFormat::Jsonl,
+        }
+    }
+}
+
+/// A day01 run, stored as JSON Lines: a header object followed by one
+/// object per step.
+///
+/// ```text
+/// {"schema":"aoc25/day01-trace","version":2,"mode":"during","dial_size":100,"start":50}
+/// {"step":1,"instruction":"L68","dial":82,"passes":1}
+/// ```
+///
+/// A run with `--target` adds `"target":n` to the header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub mode: Mode,
+    /// The number counted instead of 0, from `--target`.
+    pub target: Option<u32>,
+    /// The dial before the first step.
+    pub start: State,
+    pub steps: Vec<TraceStep>,
+}
+
+impl Trace {
+    /// Records `instructions` turning the dial from `start`, counting as
+    /// `mode` and `target` do in `Config::counting`.
+    pub fn record(
+        start: &State,
+        instructions: &[Instruction],
+        mode: Mode,
+        target: Option<u32>,
+    ) -> Self {
+        let mut state = start.clone();
+        let steps = instructions
+            .iter()
+            .map(|&instruction| state.rotate(instruction))
+            .collect();
+        Trace {
+            mode,
+            target,
+            start: start.clone(),
+            steps,
+        }
+    }
+
+    /// What the trace counts.
+    pub fn counting(&self) -> ZeroCounting {
+        self.target
+            .map_or(ZeroCounting::from(self.mode), ZeroCounting::Target)
+    }
+
+    pub fn zero_count(&self) -> u32 {
+        let counting = self.counting();
+        let mut start = self.start.clone();
+        self.steps
+            .iter()
+            .map(|step| {
+                let hits = counting.hits(&start, step);
+                start.num = step.dial;
+                hits
+            })
+            .sum()
+    }
+
+    pub fn to_jsonl(&self) -> String {
+        let target = self
+            .target
+            .map(|n| format!(",\"target\":{}", n))
+            .unwrap_or_default();
+        let mut out = format!(
+            "{{\"schema\":\"{}\",\"version\":{},\"mode\":\"{}\"{},\"dial_size\":{},\"start\":{}}}\n",
+            TRACE_SCHEMA,
+            TRACE_VERSION,
+            self.mode.name(),
+            target,
+            self.start.size,
+            self.start.num
+        );
+        for (i, step) in self.steps.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{{\"step\":{},\"instruction\":\"{}\",\"dial\":{},\"passes\":{}}}",
+                i + 1,
+                step.instruction,
+                step.dial,
+                step.passes
+            );
+        }
+        out
+    }
+
+    /// A header row, a row for where the dial starts and then a row per
+    /// step with the zeros counted so far in the trace's mode.
+    ///
+    /// ```text
+    /// step,instruction,dial,passes,zero_count
+    /// 0,,50,0,0
+    /// 1,L68,82,1,1
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut out = format!(
+            "step,instruction,dial,passes,zero_count\n0,,{},0,0\n",
+            self.start.num
+        );
+        let counting = self.counting();
+        let mut state = self.start.clone();
+        let mut zero_count = 0;
+        for (i, step) in self.steps.iter().enumerate() {
+            zero_count += counting.hits(&state, step);
+            state.num = step.dial;
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{}",
+                i + 1,
+                step.instruction,
+                step.dial,
+                step.passes,
+                zero_count
+            );
+        }
+        out
+    }
+
+    /// The trace written as `format`.
+    pub fn render(&self, format: TraceFormat) -> String {
+        match format {
+            TraceFormat::Jsonl => self.to_jsonl(),
+            TraceFormat::Csv => self.to_csv(),
+        }
+    }
+
+    /// Reads a trace written by `to_jsonl`, checking that each step is
+    /// the rotation its instruction makes on a dial of the header's size.
+    pub fn parse_jsonl(input: &str) -> AocResult<Self> {
+        let mut lines = input.lines().filter(|line| !line.trim().is_empty());
+        let header = parse_object(lines.next().ok_or_else(|| invalid("empty trace"))?)?;
+        if header.text("schema")? != TRACE_SCHEMA {
+            return Err(invalid("not a day01 trace"));
+        }
+        let version = header.number("version")?;
+        let (dial_size, target) = match version {
+            1 => (DIAL_SIZE, None),
+            TRACE_VERSION => (header.unsigned("dial_size")?, header.optional("target")?),
+            _ => return Err(invalid(&format!("unsupported trace version {}", version))),
+        };
+        if dial_size == 0 {
+            return Err(invalid("dial_size must be at least 1"));
+        }
+        let mode = match header.text("mode")? {
+            "after" => Mode::CountZerosAfterRotation,
+            "during" => Mode::CountZerosDuringRotation,
+            other => return Err(invalid(&format!("unknown mode '{}'", other))),
+        };
+        let start = State {
+            num: header.unsigned("start")?,
+            size: dial_size,
+        };
+        if start.num >= dial_size {
+            return Err(invalid(&format!(
+                "start {} is off a dial of {} notches",
+                start.num, dial_size
+            )));
+        }
+        let mut state = start.clone();
+        let steps = lines
+            .enumerate()
+            .map(|(i, line)| {
+                let object = parse_object(line)?;
+                if object.number("step")? != i as i64 + 1 {
+                    return Err(invalid(&format!("step {} is out of order", i + 1)));
+                }
+                let step = TraceStep {
+                    instruction: parse(object.text("instruction")?)?,
+                    dial: object.unsigned("dial")?,
+                    passes: object.unsigned("passes")?,
+                };
+                if state.rotate(step.instruction) != step {
+                    return Err(invalid(&format!(
+                        "step {} does not match a dial of {} notches",
+                        i + 1,
+                        dial_size
+                    )));
+                }
+                Ok(step)
+            })
+            .collect::<AocResult<Vec<TraceStep>>>()?;
+        Ok(Trace {
+            mode,
+            target,
+            start,
+            steps,
+        })
+    }
+
+    /// The run as the same narrative `-vv` prints while solving.
+    pub fn narrate(&self) -> String {
+        let mut out = format!("- The dial starts by pointing at {}.\n", self.start.num);
+        for step in &self.steps {
+            let _ = writeln!(out, "{}", step.narrate(self.counting()));
+        }
+        let _ = writeln!(out, "Zero count: {}", self.zero_count());
+        out
+    }
+}
+
+fn invalid(message: &str) -> AocError {
+    AocError::ParseError(format!("invalid trace: {}", message))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonValue<'a> {
+    Text(&'a str),
+    Number(i64),
+}
+
+struct JsonObject<'a>(Vec<(&'a str, JsonValue<'a>)>);
+
+impl<'a> JsonObject<'a> {
+    fn get(&self, key: &str) -> AocResult<JsonValue<'a>> {
+        self.0
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|&(_, v)| v)
+            .ok_or_else(|| invalid(&format!("missing field '{}'", key)))
+    }
+
+    fn text(&self, key: &str) -> AocResult<&'a str> {
+        match self.get(key)? {
+            JsonValue::Text(s) => Ok(s),
+            _ => Err(invalid(&format!("field '{}' should be a string", key))),
+        }
+    }
+
+    fn number(&self, key: &str) -> AocResult<i64> {
+        match self.get(key)? {
+            JsonValue::Number(n) => Ok(n),
+            _ => Err(invalid(&format!("field '{}' should be a number", key))),
+        }
+    }
+
+    fn unsigned(&self, key: &str) -> AocResult<u32> {
+        u32::try_from(self.number(key)?)
+            .map_err(|_| invalid(&format!("field '{}' is out of range", key)))
+    }
+
+    /// `unsigned`, or `None` if the field is missing.
+    fn optional(&self, key: &str) -> AocResult<Option<u32>> {
+        if self.0.iter().any(|(k, _)| *k == key) {
+            self.unsigned(key).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn json_string(input: &str) -> IResult<&str, &str> {
+    delimited(char('"'), take_till(|c| c == '"' || c == '\\'), char('"')).parse(input)
+}
+
+fn json_value(input: &str) -> IResult<&str, JsonValue<'_>> {
+    alt((
+        json_string.map(JsonValue::Text),
+        signed::<i64>.map(JsonValue::Number),
+    ))
+    .parse(input)
+}
+
+/// A flat JSON object of strings and integers, which is all a trace line
+/// contains.
+fn json_object(input: &str) -> IResult<&str, JsonObject<'_>> {
+    let ws = |c: char| delimited(multispace0, char(c), multispace0);
+    delimited(
+        ws('{'),
+        separated_list0(ws(','), separated_pair(json_string, ws(':'), json_value)),
+        ws('}'),
+    )
+    .map(JsonObject)
+    .parse(input)
+}
+
+fn parse_object(line: &str) -> AocResult<JsonObject<'_>> {
+    parse_complete(line, json_object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::read_instructions_file;
+
+    fn test_trace() -> Trace {
+        let instructions = read_instructions_file("data/day01/test_input.txt").expect("test input");
+        Trace::record(
+            &State::new(),
+            &instructions,
+            Mode::CountZerosDuringRotation,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_record_matches_simulation() {
+        let trace = test_trace();
+        assert_eq!(trace.steps.len(), 10);
+        assert_eq!(trace.zero_count(), 6);
+        let after = Trace {
+            mode: Mode::CountZerosAfterRotation,
+            ..trace
+        };
+        assert_eq!(after.zero_count(), 3);
+    }
+
+    #[test]
+    fn test_jsonl_round_trip() {
+        let trace = test_trace();
+        let jsonl = trace.to_jsonl();
+        assert!(jsonl.starts_with(
+            "{\"schema\":\"aoc25/day01-trace\",\"version\":2,\"mode\":\"during\",\"dial_size\":100,\"start\":50}\n\
+             {\"step\":1,\"instruction\":\"L68\",\"dial\":82,\"passes\":1}\n"
+        ));
+        assert_eq!(Trace::parse_jsonl(&jsonl).expect("trace"), trace);
+
+        let v1 = jsonl.replace("\"version\":2", "\"version\":1");
+        assert_eq!(Trace::parse_jsonl(&v1).expect("version 1 trace"), trace);
+    }
+
+    #[test]
+    fn test_other_dial_sizes() {
+        let instructions =
+            crate::days::day01::parse_instructions("L3\nR9\nL1\n").expect("instructions");
+        let start = State::with_size(7);
+        let trace = Trace::record(
+            &start,
+            &instructions,
+            Mode::CountZerosDuringRotation,
+            Some(2),
+        );
+        assert_eq!(
+            trace.zero_count(),
+            start
+                .clone()
+                .apply_multiple(instructions, ZeroCounting::Target(2))
+        );
+        let jsonl = trace.to_jsonl();
+        assert!(jsonl.starts_with(
+            "{\"schema\":\"aoc25/day01-trace\",\"version\":2,\"mode\":\"during\",\"target\":2,\"dial_size\":7,\"start\":3}\n"
+        ));
+        assert_eq!(Trace::parse_jsonl(&jsonl).expect("trace"), trace);
+        assert!(
+            Trace::parse_jsonl(&jsonl.replace("\"dial_size\":7", "\"dial_size\":100")).is_err()
+        );
+        assert!(Trace::parse_jsonl(&jsonl.replace("\"dial_size\":7", "\"dial_size\":0")).is_err());
+    }
+
+    #[test]
+    fn test_csv() {
+        let instructions = crate::days::day01::parse_instructions(
+            "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n",
+        )
+        .expect("instructions");
+        let trace = Trace::record(
+            &State::new(),
+            &instructions,
+            Mode::CountZerosDuringRotation,
+            None,
+        );
+        let csv = trace.render(TraceFormat::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("step,instruction,dial,passes,zero_count")
+        );
+        assert_eq!(lines.next(), Some("0,,50,0,0"));
+        assert_eq!(lines.next(), Some("1,L68,82,1,1"));
+        assert_eq!(csv.lines().count(), trace.steps.len() + 2);
+        assert_eq!(
+            csv.lines().last(),
+            Some(format!("10,L82,32,1,{}", trace.zero_count()).as_str())
+        );
+        assert_eq!(trace.render(TraceFormat::from("jsonl")), trace.to_jsonl());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_traces() {
+        let header = "{\"schema\": \"aoc25/day01-trace\", \"version\": 2, \"mode\": \"after\", \"dial_size\": 100, \"start\": 50}";
+        assert!(Trace::parse_jsonl(header).is_ok());
+        assert!(Trace::parse_jsonl("").is_err());
+        assert!(Trace::parse_jsonl(&header.replace("2,", "3,")).is_err());
+        assert!(Trace::parse_jsonl(&header.replace("50}", "100}")).is_err());
+        assert!(Trace::parse_jsonl(&header.replace("day01", "day02")).is_err());
+        let skipped = format!(
+            "{}\n{{\"step\":2,\"instruction\":\"L1\",\"dial\":49,\"passes\":0}}",
+            header
+        );
+        assert!(Trace::parse_jsonl(&skipped).is_err());
+    }
+
+    #[test]
+    fn test_narrate() {
+        let narrative = test_trace().narrate();
+        let mut lines = narrative.lines();
+        assert_eq!(lines.next(), Some("- The dial starts by pointing at 50."));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "- The dial is rotated L68 to point at 82; during this rotation, it points at 0 1 times."
+            )
+        );
+        assert_eq!(narrative.lines().last(), Some("Zero count: 6"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+use super::{Instruction, Operation, State};
+
+/// Most mismatches `exhaustive` keeps as examples.
+pub const MAX_EXAMPLES: usize = 10;
+
+/// The reference `State::rotate` is checked against: turns the dial from
+/// `dial` one notch at a time. Returns where it stops and how many times
+/// it pointed at 0 before the last notch.
+pub fn rotate_by_notches(dial: u32, instruction: Instruction) -> (u32, u32) {
+    let mut dial = dial;
+    let mut passes = 0;
+    for notch in 1..=instruction.argument {
+        dial = match instruction.operation {
+            Operation::Left => (dial + 99) % 100,
+            Operation::Right => (dial + 1) % 100,
+        };
+        if dial == 0 && notch < instruction.argument {
+            passes += 1;
+        }
+    }
+    (dial, passes)
+}
+
+/// A rotation where `State::rotate` disagrees with `rotate_by_notches`,
+/// as `(dial, passes)` pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    pub start: u32,
+    pub instruction: Instruction,
+    pub expected: (u32, u32),
+    pub actual: (u32, u32),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from {}: expected dial {} after {} passes, got dial {} after {} passes",
+            self.instruction,
+            self.start,
+            self.expected.0,
+            self.expected.1,
+            self.actual.0,
+            self.actual.1
+        )
+    }
+}
+
+/// The outcome of `exhaustive`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub checked: u64,
+    pub failed: u64,
+    /// The first `MAX_EXAMPLES` mismatches.
+    pub examples: Vec<Mismatch>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Compares `State::rotate` with `rotate_by_notches` from every dial
+/// position, in both directions, for every argument up to `max_argument`.
+pub fn exhaustive(max_argument: u32) -> Verification {
+    let mut verification = Verification {
+        checked: 0,
+        failed: 0,
+        examples: vec![],
+    };
+    for start in 0..100 {
+        for operation in [Operation::Left, Operation::Right] {
+            for argument in 0..=max_argument {
+                let instruction = Instruction::new(operation, argument);
+                let event = State::starting_at(start).rotate(instruction);
+                let expected = rotate_by_notches(start, instruction);
+                let actual = (event.dial, event.passes);
+                verification.checked += 1;
+                if actual != expected {
+                    verification.failed += 1;
+                    if verification.examples.len() < MAX_EXAMPLES {
+                        verification.examples.push(Mismatch {
+                            start,
+                            instruction,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    verification
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_by_notches() {
+        assert_eq!(
+            rotate_by_notches(50, Instruction::new(Operation::Left, 68)),
+            (82, 1)
+        );
+        assert_eq!(
+            rotate_by_notches(0, Instruction::new(Operation::Right, 100)),
+            (0, 0)
+        );
+        assert_eq!(
+            rotate_by_notches(0, Instruction::new(Operation::Right, 0)),
+            (0, 0)
+        );
+        assert_eq!(
+            rotate_by_notches(95, Instruction::new(Operation::Right, 60)),
+            (55, 1)
+        );
+    }
+
+    #[test]
+    fn test_exhaustive_small() {
+        let verification = exhaustive(250);
+        assert_eq!(verification.checked, 100 * 2 * 251);
+        assert!(verification.passed(), "{:?}", verification.examples);
+    }
+
+    /// Slow: about 100 million notches. Run with `cargo test -- --ignored`
+    /// or `aoc25 verify day01`.
+    #[test]
+    #[ignore]
+    fn test_exhaustive() {
+        let verification = exhaustive(1000);
+        assert!(verification.passed(), "{:?}", verification.examples);
+    }
+}
@@ -0,0 +1,1494 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::cli::CommonConfig;
+use crate::describe::{Description, Example, Grammar, ModeDescription, Rules};
+use crate::error::AocError;
+use crate::input;
+use crate::interval::IntervalSet;
+use crate::json::{self, ToJson};
+use crate::logging::ThrottledLog;
+use crate::math;
+use crate::par;
+use crate::params::Params;
+use crate::parse::{list, parse_complete, range, separator, unsigned};
+use crate::progress::{NoProgress, Progress};
+use crate::result::AocResult;
+use crate::solution::{Answer, Solution};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use nom::{
+    IResult, Parser,
+    character::complete::{char, multispace0},
+    combinator::{cut, opt},
+    multi::many0,
+    sequence::{preceded, terminated},
+};
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use tracing::{debug, warn};
+
+pub mod cli;
+pub mod cost;
+pub mod density;
+pub mod fast;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod report;
+pub mod scan;
+pub mod validity;
+
+pub use fast::count_sum_analytic;
+use report::{RangeReport, ScanReport};
+use validity::Validity;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdRange {
+    start: u64,
+    end: u64,
+}
+
+impl IdRange {
+    /// Number of IDs in the range.
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.end - self.start).saturating_add(1)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// Every ID in the range, in order.
+    pub fn ids(&self) -> std::ops::RangeInclusive<u64> {
+        self.start..=self.end
+    }
+
+    /// The range cut into consecutive pieces of at most `size` IDs.
+    pub fn chunks(&self, size: u64) -> impl Iterator<Item = IdRange> + use<> {
+        let (end, size) = (self.end, size.max(1));
+        let mut next = (!self.is_empty()).then_some(self.start);
+        std::iter::from_fn(move || {
+            let start = next?;
+            let chunk_end = start.saturating_add(size - 1).min(end);
+            next = chunk_end.checked_add(1).filter(|&id| id <= end);
+            Some(IdRange {
+                start,
+                end: chunk_end,
+            })
+        })
+    }
+}
+
+impl ToJson for IdRange {
+    fn to_json(&self) -> String {
+        json::object(&[("start", self.start.to_json()), ("end", self.end.to_json())])
+    }
+}
+
+impl fmt::Display for IdRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl FromStr for IdRange {
+    type Err = AocError;
+
+    /// A closed range, `N-M`.
+    fn from_str(s: &str) -> AocResult<Self> {
+        parse_complete(s, parse_id_range)
+    }
+}
+
+/// Digit count open-ended ranges stop at unless told otherwise.
+pub const DEFAULT_MAX_DIGITS: u32 = 18;
+
+/// Largest usable digit count: every 19-digit ID still fits in a `u64`.
+pub const MAX_DIGITS_LIMIT: u32 = 19;
+
+/// A range as written in the input: `N-M`, or `N-` for every ID from `N`
+/// up to a digit-count cap chosen when solving.
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RangeSpec {
+    Closed(IdRange),
+    From(u64),
+}
+
+impl RangeSpec {
+    pub fn is_open(&self) -> bool {
+        matches!(self, RangeSpec::From(_))
+    }
+
+    /// The IDs this covers, ending an open range at the largest ID with
+    /// `max_digits` digits.
+    pub fn capped(&self, max_digits: u32) -> AocResult<IdRange> {
+        match *self {
+            RangeSpec::Closed(range) => Ok(range),
+            RangeSpec::From(start) => {
+                if !(1..=MAX_DIGITS_LIMIT).contains(&max_digits) {
+                    return Err(AocError::InvalidArgument(format!(
+                        "max digits {} is outside 1 to {}",
+                        max_digits, MAX_DIGITS_LIMIT
+                    )));
+                }
+                Ok(IdRange {
+                    start,
+                    end: 10u64.pow(max_digits) - 1,
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for RangeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeSpec::Closed(range) => write!(f, "{}", range),
+            RangeSpec::From(start) => write!(f, "{}-", start),
+        }
+    }
+}
+
+/// The closed ranges in `specs`, or `None` if any is open-ended and so
+/// needs `calc_count_sum_exact`.
+pub fn closed_ranges(specs: &[RangeSpec]) -> Option<Vec<IdRange>> {
+    specs
+        .iter()
+        .map(|spec| match spec {
+            RangeSpec::Closed(range) => Some(*range),
+            RangeSpec::From(_) => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Mode {
+    Two,
+    Multiple,
+}
+
+impl Mode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Two => "two",
+            Mode::Multiple => "multiple",
+        }
+    }
+
+    /// The puzzle part this mode answers.
+    pub fn part(self) -> u32 {
+        match self {
+            Mode::Two => 1,
+            Mode::Multiple => 2,
+        }
+    }
+
+    /// The registered rule this mode selects.
+    pub fn validity(self) -> &'static dyn Validity {
+        match self {
+            Mode::Two => &validity::Two,
+            Mode::Multiple => &validity::Multiple,
+        }
+    }
+}
+
+impl From<&str> for Mode {
+    fn from(s: &str) -> Self {
+        match s {
+            "two" => Mode::Two,
+            "multiple" => Mode::Multiple,
+            _ => Mode::Two,
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = AocError;
+
+    /// Unlike `From<&str>`, rejects anything but `two` and `multiple`.
+    fn from_str(s: &str) -> AocResult<Self> {
+        match s {
+            "two" | "multiple" => Ok(Mode::from(s)),
+            _ => Err(AocError::InvalidArgument(format!(
+                "unknown mode '{}', expected 'two' or 'multiple'",
+                s
+            ))),
+        }
+    }
+}
+
+/// How `--list` prints the invalid IDs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ListFormat {
+    /// One ID per line.
+    Ids,
+    /// Runs of consecutive IDs as `start-end`, comma separated: the same
+    /// format as the puzzle input, so a list can be read back in.
+    Ranges,
+}
+
+impl From<&str> for ListFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "ids" => ListFormat::Ids,
+            "ranges" => ListFormat::Ranges,
+            _ => ListFormat::Ids,
+        }
+    }
+}
+
+/// How closed ranges are counted. Open-ended ranges always use `Fast`.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Algorithm {
+    /// Check every ID in the range.
+    #[default]
+    Naive,
+    /// Count each digit count's repeated-block IDs directly with
+    /// `count_sum_analytic`.
+    Fast,
+    /// `Naive` if `cost::resolve` expects it to finish within the time
+    /// budget, otherwise `Fast`.
+    Auto,
+}
+
+impl Algorithm {
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Naive => "naive",
+            Algorithm::Fast => "fast",
+            Algorithm::Auto => "auto",
+        }
+    }
+}
+
+impl From<&str> for Algorithm {
+    fn from(s: &str) -> Self {
+        match s {
+            "fast" => Algorithm::Fast,
+            "auto" => Algorithm::Auto,
+            _ => Algorithm::Naive,
+        }
+    }
+}
+
+#[derive(clap::Parser, Debug, Clone)]
+pub struct Config {
+    #[command(flatten)]
+    pub common: CommonConfig,
+
+    #[clap(
+        short,
+        long,
+        default_value = "two",
+        value_parser = PossibleValuesParser::new(["two", "multiple"]).map(|s| Mode::from(s.as_str())),
+        help = "Mode: 'two' or 'multiple'"
+    )]
+    pub mode: Mode,
+
+    #[clap(
+        long,
+        help = "Scan on the GPU if built with the 'gpu' feature; with --bench, compare against the CPU"
+    )]
+    pub gpu: bool,
+
+    #[clap(
+        long,
+        value_parser = PossibleValuesParser::new(["ids", "ranges"]).map(|s| ListFormat::from(s.as_str())),
+        help = "List the invalid IDs: 'ids' or 'ranges'"
+    )]
+    pub list: Option<ListFormat>,
+
+    #[clap(
+        long,
+        help = "Write the invalid-ID density per digit count as CSV to this path"
+    )]
+    pub density: Option<String>,
+
+    #[clap(long, help = "Scan the ranges on several threads, splitting wide ones")]
+    pub parallel: bool,
+
+    #[clap(
+        long,
+        default_value = "auto",
+        conflicts_with = "gpu",
+        value_parser = PossibleValuesParser::new(["naive", "fast", "auto"]).map(|s| Algorithm::from(s.as_str())),
+        help = "Counting algorithm: 'naive' checks every ID, 'fast' counts them analytically, 'auto' checks every ID unless that would exceed --time-budget"
+    )]
+    pub algorithm: Algorithm,
+
+    #[clap(
+        long,
+        value_name = "MS",
+        default_value_t = cost::DEFAULT_TIME_BUDGET_MS,
+        help = "Milliseconds checking every ID may take before --algorithm auto counts analytically"
+    )]
+    pub time_budget: u64,
+
+    #[clap(
+        long,
+        default_value_t = DEFAULT_MAX_DIGITS,
+        help = "Digit count that open-ended 'N-' ranges stop at"
+    )]
+    pub max_digits: u32,
+
+    #[clap(
+        long,
+        default_value_t = 1,
+        help = "Log a summary every N ranges and the ranges with the most invalid IDs, instead of a line per range"
+    )]
+    pub log_every: usize,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["list", "gpu", "bench"],
+        help = "Print each range's invalid-ID count, sum, density and first and last few IDs, checking every ID"
+    )]
+    pub report: bool,
+}
+
+impl Config {
+    /// The algorithm `--algorithm` comes to for `ranges`, which are `None`
+    /// if some are open-ended.
+    pub fn algorithm_for(&self, ranges: Option<&[IdRange]>) -> Algorithm {
+        cost::resolve(
+            self.algorithm,
+            ranges,
+            self.mode,
+            Duration::from_millis(self.time_budget),
+        )
+    }
+}
+
+pub fn grammar() -> Rules {
+    Rules(vec![
+        (
+            "input",
+            Grammar::separated(
+                Grammar::rule("range"),
+                Grammar::seq([Grammar::lit(","), Grammar::rule("whitespace*")]),
+            ),
+        ),
+        (
+            "range",
+            Grammar::seq([
+                Grammar::Digits,
+                Grammar::lit("-"),
+                Grammar::alt([Grammar::Digits, Grammar::lit("")]),
+            ]),
+        ),
+    ])
+}
+
+pub fn parse_id_range(s: &str) -> IResult<&str, IdRange> {
+    range.map(|(start, end)| IdRange { start, end }).parse(s)
+}
+
+pub fn parse_id_range_sequence(input: &str) -> IResult<&str, Vec<IdRange>> {
+    list(',', parse_id_range).parse(input)
+}
+
+/// `N-M`, or `N-` for a range with no upper bound.
+pub fn parse_range_spec(s: &str) -> IResult<&str, RangeSpec> {
+    let (s, start) = terminated(unsigned, char('-')).parse(s)?;
+    let (s, end) = opt(unsigned).parse(s)?;
+    Ok((
+        s,
+        match end {
+            Some(end) => RangeSpec::Closed(IdRange { start, end }),
+            None => RangeSpec::From(start),
+        },
+    ))
+}
+
+/// Comma-separated `parse_range_spec`s. A comma must be followed by
+/// another range, so a bad range fails where it goes wrong instead of
+/// ending the list early.
+pub fn parse_range_spec_sequence(input: &str) -> IResult<&str, Vec<RangeSpec>> {
+    let (input, first) = parse_range_spec(input)?;
+    let (input, mut rest) = many0(preceded(separator(','), cut(parse_range_spec))).parse(input)?;
+    rest.insert(0, first);
+    Ok((input, rest))
+}
+
+pub fn parse_input_file(path: &str) -> AocResult<Vec<IdRange>> {
+    let content = input::read_to_string(path)?;
+    parse_input(&content).map_err(|e| match e {
+        AocError::ParseError(e) => {
+            AocError::ParseError(format!("Failed to parse input file {}: {}", path, e))
+        }
+        e => e,
+    })
+}
+
+/// Parses closed ranges only; see `parse_range_specs` to also accept
+/// open-ended ones.
+pub fn parse_input(content: &str) -> AocResult<Vec<IdRange>> {
+    let specs = parse_range_specs(content)?;
+    match specs.iter().find(|spec| spec.is_open()) {
+        Some(open) => Err(AocError::ParseError(format!(
+            "open-ended range {} needs the analytic counter",
+            open
+        ))),
+        None => Ok(closed_ranges(&specs).unwrap_or_default()),
+    }
+}
+
+/// Parses the whole of `content`, apart from trailing whitespace, as
+/// ranges. Errors give the line and column of the first bad character.
+pub fn parse_range_specs(content: &str) -> AocResult<Vec<RangeSpec>> {
+    parse_complete(content, |s| {
+        terminated(parse_range_spec_sequence, multispace0).parse(s)
+    })
+}
+
+pub fn id_is_valid(id: u64, mode: Mode) -> bool {
+    mode.validity().is_invalid(id).is_none()
+}
+
+/// Repetition counts worth checking for IDs with `digits` digits under
+/// `mode`'s rule.
+pub fn candidate_frequencies(digits: u32, mode: Mode) -> Vec<u32> {
+    mode.validity().frequencies(digits)
+}
+
+fn id_is_valid_at(id: u64, digits: u32, freqs: &[u32]) -> bool {
+    witnessing_period(id, digits, freqs).is_none()
+}
+
+/// Length of a block that `id` repeats to make up all its digits, trying
+/// `freqs` in order, or `None` if the ID is valid.
+fn witnessing_period(id: u64, digits: u32, freqs: &[u32]) -> Option<u32> {
+    debug!(
+        "Validating id {} with {} digits at freqs {:?}",
+        id, digits, freqs
+    );
+    for &freq in freqs {
+        debug!("Checking id {} for freq {}", id, freq);
+        let mut valid_at_freq = false;
+        let period = digits / freq;
+        let pivot = 10u64.pow(period);
+        let right = id % pivot;
+        let mut id_pivoted = id;
+        debug!("  period {}, pivot {}, right {}", period, pivot, right);
+        for i in 1..freq {
+            debug!("    iteration {}, id {}", i, id_pivoted);
+            id_pivoted /= pivot;
+            if id_pivoted % pivot != right {
+                debug!("      id {} valid at iteration {}", id_pivoted, i);
+                valid_at_freq = true;
+                break;
+            }
+        }
+
+        if !valid_at_freq {
+            return Some(period);
+        }
+    }
+
+    None
+}
+
+/// `range` split at powers of ten into pieces whose IDs all share a digit
+/// count, paired with that count.
+pub fn digit_buckets(range: &IdRange) -> impl Iterator<Item = (u32, IdRange)> + use<> {
+    let end = range.end;
+    let mut next = (range.start <= end).then_some(range.start);
+    std::iter::from_fn(move || {
+        let start = next?;
+        let digits = start.checked_ilog10().unwrap_or(0) + 1;
+        let bucket_end = match 10u64.checked_pow(digits) {
+            Some(limit) => end.min(limit - 1),
+            None => end,
+        };
+        next = bucket_end.checked_add(1).filter(|&n| n <= end);
+        Some((
+            digits,
+            IdRange {
+                start,
+                end: bucket_end,
+            },
+        ))
+    })
+}
+
+pub fn invalid_ids_in_range(range: &IdRange, mode: Mode) -> impl Iterator<Item = u64> + use<> {
+    invalid_ids_with_reason(range, mode).map(|(id, _)| id)
+}
+
+/// All invalid IDs across `ranges`, with runs of consecutive IDs coalesced
+/// into intervals. Overlapping input ranges are merged, so each ID appears
+/// once.
+pub fn invalid_id_intervals(ranges: &[IdRange], mode: Mode) -> AocResult<IntervalSet> {
+    interval_set(
+        ranges
+            .iter()
+            .flat_map(|range| invalid_ids_in_range(range, mode)),
+    )
+}
+
+/// `invalid_id_intervals` with the ranges scanned on several threads. The
+/// IDs are merged only once every range is done, in input order.
+pub fn invalid_id_intervals_parallel(ranges: &[IdRange], mode: Mode) -> AocResult<IntervalSet> {
+    let per_range = par::map_ordered(ranges, |range| {
+        invalid_ids_in_range(range, mode).collect::<Vec<u64>>()
+    });
+    interval_set(per_range.into_iter().flatten())
+}
+
+fn interval_set(ids: impl Iterator<Item = u64>) -> AocResult<IntervalSet> {
+    let mut set = IntervalSet::new();
+    for id in ids {
+        let id = i64::try_from(id)
+            .map_err(|_| AocError::Unsupported(format!("ID {} is too large to list", id)))?;
+        set.insert_value(id);
+    }
+    Ok(set)
+}
+
+/// Invalid IDs in `range`, each paired with the period that proves it
+/// invalid: the length of the block the ID repeats. When several periods
+/// work the one with the fewest repetitions is given, e.g. `3` for `111111`.
+///
+/// Scans `range` one digit-count bucket at a time, skipping buckets where no
+/// repetition count divides the digit count, since every ID in them is
+/// valid.
+pub fn invalid_ids_with_reason(
+    range: &IdRange,
+    mode: Mode,
+) -> impl Iterator<Item = (u64, u32)> + use<> {
+    digit_buckets(range).flat_map(move |(digits, bucket)| {
+        let freqs = candidate_frequencies(digits, mode);
+        (!freqs.is_empty())
+            .then(|| {
+                (bucket.start..=bucket.end)
+                    .filter_map(move |id| Some((id, witnessing_period(id, digits, &freqs)?)))
+            })
+            .into_iter()
+            .flatten()
+    })
+}
+
+/// Count and sum of the invalid IDs in `range`, using
+/// `count_sum_small_range` for ranges narrower than
+/// `SMALL_RANGE_THRESHOLD`.
+pub fn count_sum_invalid_ids_in_range(range: &IdRange, mode: Mode) -> AocResult<RangeReport> {
+    let start = Instant::now();
+    let (count, sum) = if range.len() < SMALL_RANGE_THRESHOLD {
+        count_sum_small_range(range, mode)?
+    } else {
+        let acc = (0u64, 0u64);
+        invalid_ids_in_range(range, mode).try_fold(acc, |(count, sum), id| {
+            Ok::<_, AocError>((count + 1, math::add(sum, id, "invalid ID sum")?))
+        })?
+    };
+    Ok(RangeReport {
+        range: *range,
+        count,
+        sum,
+        duration: start.elapsed(),
+        cache_hit: false,
+    })
+}
+
+/// Ranges with fewer IDs than this are scanned by comparing digit bytes.
+pub const SMALL_RANGE_THRESHOLD: u64 = 4096;
+
+/// Count and sum of the invalid IDs in `range`, formatting each ID into a
+/// stack buffer once and comparing its blocks as byte slices instead of
+/// dividing repeatedly.
+pub fn count_sum_small_range(range: &IdRange, mode: Mode) -> AocResult<(u64, u64)> {
+    let mut buf = [0u8; 20];
+    let (mut count, mut sum) = (0u64, 0u64);
+    for (digits, bucket) in digit_buckets(range) {
+        let freqs = candidate_frequencies(digits, mode);
+        if freqs.is_empty() {
+            continue;
+        }
+        for id in bucket.start..=bucket.end {
+            if repeats_block(format_digits(id, &mut buf), &freqs) {
+                count += 1;
+                sum = math::add(sum, id, "invalid ID sum")?;
+            }
+        }
+    }
+    Ok((count, sum))
+}
+
+/// The decimal digits of `id`, written into the end of `buf`.
+fn format_digits(mut id: u64, buf: &mut [u8; 20]) -> &[u8] {
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (id % 10) as u8;
+        id /= 10;
+        if id == 0 {
+            return &buf[start..];
+        }
+    }
+}
+
+/// Whether `digits` is a single block repeated `freq` times for any of
+/// `freqs`.
+fn repeats_block(digits: &[u8], freqs: &[u32]) -> bool {
+    freqs.iter().any(|&freq| {
+        let period = digits.len() / freq as usize;
+        let (block, rest) = digits.split_at(period);
+        rest.chunks_exact(period).all(|chunk| chunk == block)
+    })
+}
+
+/// What a `period`-digit block is multiplied by to repeat it out to
+/// `digits` digits, e.g. `10101` for period 2 and 6 digits.
+fn repeat_multiplier(digits: u32, period: u32) -> u128 {
+    let shift = 10u128.pow(period);
+    (0..digits / period).map(|i| shift.pow(i)).sum()
+}
+
+/// Every `digit_count`-digit ID made of a `period`-digit block repeated,
+/// in increasing order. Empty unless `period` divides `digit_count` at
+/// least twice, or if such IDs don't fit in a `u64`.
+pub fn invalid_ids_generated(digit_count: u32, period: u32) -> impl Iterator<Item = u64> + use<> {
+    let shape = (period > 0
+        && digit_count.is_multiple_of(period)
+        && digit_count / period >= 2
+        && digit_count <= MAX_DIGITS_LIMIT)
+        .then(|| {
+            let multiplier = repeat_multiplier(digit_count, period) as u64;
+            (multiplier, 10u64.pow(period - 1)..10u64.pow(period))
+        });
+    shape
+        .into_iter()
+        .flat_map(|(multiplier, blocks)| blocks.map(move |block| block * multiplier))
+}
+
+/// Count and sum of invalid IDs across `specs` using the analytic counter,
+/// so open-ended ranges end at `max_digits` digits.
+pub fn calc_count_sum_exact(
+    specs: &[RangeSpec],
+    mode: Mode,
+    max_digits: u32,
+) -> AocResult<(u128, u128)> {
+    let (mut total_count, mut total_sum) = (0u128, 0u128);
+    let mut log = ThrottledLog::new("ranges", "invalid IDs");
+    for spec in specs {
+        let (count, sum) = count_sum_analytic(&spec.capped(max_digits)?, mode);
+        log.item(count.min(u64::MAX as u128) as u64, || {
+            format!("- {} has {} invalid IDs", spec, count)
+        });
+        total_count += count;
+        total_sum += sum;
+    }
+    log.finish();
+    Ok((total_count, total_sum))
+}
+
+/// A report of the invalid IDs in each of `ranges`, scanned in order.
+pub fn calc_count_sum(ranges: &[IdRange], mode: Mode) -> AocResult<ScanReport> {
+    calc_count_sum_with_progress(ranges, mode, &mut NoProgress)
+}
+
+/// `calc_count_sum`, reporting progress as the number of IDs scanned.
+pub fn calc_count_sum_with_progress(
+    ranges: &[IdRange],
+    mode: Mode,
+    progress: &mut dyn Progress,
+) -> AocResult<ScanReport> {
+    progress.set_total(ranges.iter().map(IdRange::len).sum());
+    let mut scan = ScanReport::default();
+    let mut log = ThrottledLog::new("ranges", "invalid IDs");
+    for range in ranges {
+        let report = count_sum_invalid_ids_in_range(range, mode)?;
+        log.item(report.count, || {
+            format!("- {} has {} invalid IDs", range, report.count)
+        });
+        scan.push(report)?;
+        progress.inc(range.len());
+    }
+    log.finish();
+    progress.finish();
+    Ok(scan)
+}
+
+/// Ranges wider than this are cut into pieces of this many IDs before a
+/// parallel scan, so a single wide range still keeps every thread busy.
+pub const PARALLEL_CHUNK: u64 = 1 << 20;
+
+/// `calc_count_sum` with the ranges scanned on several threads.
+pub fn calc_count_sum_parallel(ranges: &[IdRange], mode: Mode) -> AocResult<ScanReport> {
+    calc_count_sum_chunked(ranges, mode, PARALLEL_CHUNK)
+}
+
+/// `calc_count_sum` with every range cut into pieces of at most `chunk`
+/// IDs and the pieces scanned on several threads. Pieces are cut lazily as
+/// threads ask for work, so a very wide range never has all its pieces in
+/// memory at once. Each range's report adds up its pieces, so its duration
+/// is thread time rather than wall time.
+pub fn calc_count_sum_chunked(ranges: &[IdRange], mode: Mode, chunk: u64) -> AocResult<ScanReport> {
+    let empty = || -> Vec<RangeReport> {
+        ranges
+            .iter()
+            .map(|range| RangeReport {
+                range: *range,
+                count: 0,
+                sum: 0,
+                duration: Duration::ZERO,
+                cache_hit: false,
+            })
+            .collect()
+    };
+    let reports = ranges
+        .iter()
+        .enumerate()
+        .flat_map(|(index, range)| range.chunks(chunk).map(move |piece| (index, piece)))
+        .par_bridge()
+        .try_fold(empty, |mut reports, (index, piece)| -> AocResult<_> {
+            let found = count_sum_invalid_ids_in_range(&piece, mode)?;
+            add_piece(&mut reports[index], &found)?;
+            Ok(reports)
+        })
+        .try_reduce(empty, |mut reports, other| {
+            for (report, piece) in reports.iter_mut().zip(&other) {
+                add_piece(report, piece)?;
+            }
+            Ok(reports)
+        })?;
+    ScanReport::from_reports(reports)
+}
+
+/// Adds the count, sum and time of `piece` to `report`.
+fn add_piece(report: &mut RangeReport, piece: &RangeReport) -> AocResult<()> {
+    report.count += piece.count;
+    report.sum = math::add(report.sum, piece.sum, "invalid ID sum")?;
+    report.duration += piece.duration;
+    Ok(())
+}
+
+/// `calc_count_sum` for `Mode::Two` and `Mode::Multiple` from a single scan.
+/// An ID repeated twice is also repeated "two or more" times, so the
+/// `Multiple` check only runs for IDs the `Two` check finds valid, and
+/// buckets with no `Multiple` candidates are skipped for both.
+pub fn calc_count_sum_both(ranges: &[IdRange]) -> AocResult<((u64, u64), (u64, u64))> {
+    let (mut two, mut multiple) = ((0u64, 0u64), (0u64, 0u64));
+    for range in ranges {
+        for (digits, bucket) in digit_buckets(range) {
+            let two_freqs = candidate_frequencies(digits, Mode::Two);
+            let multiple_freqs = candidate_frequencies(digits, Mode::Multiple);
+            if multiple_freqs.is_empty() {
+                continue;
+            }
+            for id in bucket.start..=bucket.end {
+                if !id_is_valid_at(id, digits, &two_freqs) {
+                    two = (two.0 + 1, math::add(two.1, id, "invalid ID sum")?);
+                    multiple = (multiple.0 + 1, math::add(multiple.1, id, "invalid ID sum")?);
+                } else if !id_is_valid_at(id, digits, &multiple_freqs) {
+                    multiple = (multiple.0 + 1, math::add(multiple.1, id, "invalid ID sum")?);
+                }
+            }
+        }
+    }
+    Ok((two, multiple))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<RangeSpec>;
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Vec<RangeSpec>> {
+        parse_range_specs(input)
+    }
+
+    fn part1(&self, input: &Vec<RangeSpec>) -> AocResult<Answer> {
+        self.part1_with(input, &Params::new())
+    }
+
+    fn part2(&self, input: &Vec<RangeSpec>) -> AocResult<Answer> {
+        self.part2_with(input, &Params::new())
+    }
+
+    fn solve_both(&self, input: &Vec<RangeSpec>) -> AocResult<(Answer, Answer)> {
+        match closed_ranges(input) {
+            Some(ranges) => {
+                let (two, multiple) = calc_count_sum_both(&ranges)?;
+                Ok((Answer::from(two.1), Answer::from(multiple.1)))
+            }
+            None => self.solve_both_with(input, &Params::new()),
+        }
+    }
+
+    fn param_names(&self) -> &'static [&'static str] {
+        &["max_digits"]
+    }
+
+    fn part1_with(&self, input: &Vec<RangeSpec>, params: &Params) -> AocResult<Answer> {
+        total_invalid(input, Mode::Two, params)
+    }
+
+    fn part2_with(&self, input: &Vec<RangeSpec>, params: &Params) -> AocResult<Answer> {
+        total_invalid(input, Mode::Multiple, params)
+    }
+}
+
+/// Sum of invalid IDs in `specs`, by brute force unless a range is
+/// open-ended.
+fn total_invalid(specs: &[RangeSpec], mode: Mode, params: &Params) -> AocResult<Answer> {
+    match closed_ranges(specs) {
+        Some(ranges) => Ok(Answer::from(calc_count_sum(&ranges, mode)?.sum)),
+        None => {
+            let max_digits = params.get_or("max_digits", DEFAULT_MAX_DIGITS)?;
+            let (_, sum) = calc_count_sum_exact(specs, mode, max_digits)?;
+            Ok(Answer::from(sum))
+        }
+    }
+}
+
+/// Where the brute-force scan runs.
+pub enum Backend {
+    Cpu,
+    #[cfg(feature = "gpu")]
+    Gpu(gpu::GpuScanner),
+}
+
+impl Backend {
+    /// The GPU if `gpu` is requested, the `gpu` feature is built and an
+    /// adapter is available; the CPU otherwise.
+    pub fn select(gpu: bool) -> Self {
+        if !gpu {
+            return Backend::Cpu;
+        }
+        #[cfg(feature = "gpu")]
+        match gpu::GpuScanner::new() {
+            Ok(scanner) => return Backend::Gpu(scanner),
+            Err(e) => warn!("No GPU available, using the CPU: {}", e),
+        }
+        #[cfg(not(feature = "gpu"))]
+        warn!("Built without the 'gpu' feature, using the CPU");
+        Backend::Cpu
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Backend::Cpu => "CPU",
+            #[cfg(feature = "gpu")]
+            Backend::Gpu(scanner) => scanner.adapter_name(),
+        }
+    }
+
+    /// Like `calc_count_sum`, falling back to the CPU if the GPU fails.
+    pub fn calc_count_sum(&self, ranges: &[IdRange], mode: Mode) -> AocResult<ScanReport> {
+        self.calc_count_sum_with_progress(ranges, mode, &mut NoProgress)
+    }
+
+    /// Like `calc_count_sum_with_progress`. The GPU scans every range in
+    /// one dispatch, so it reports all progress at once when done.
+    pub fn calc_count_sum_with_progress(
+        &self,
+        ranges: &[IdRange],
+        mode: Mode,
+        progress: &mut dyn Progress,
+    ) -> AocResult<ScanReport> {
+        match self {
+            Backend::Cpu => calc_count_sum_with_progress(ranges, mode, progress),
+            #[cfg(feature = "gpu")]
+            Backend::Gpu(scanner) => match scanner.calc_count_sum(ranges, mode) {
+                Ok(result) => {
+                    let total = ranges.iter().map(IdRange::len).sum();
+                    progress.set_total(total);
+                    progress.inc(total);
+                    progress.finish();
+                    Ok(result)
+                }
+                Err(e) => {
+                    warn!("GPU scan failed, using the CPU: {}", e);
+                    calc_count_sum_with_progress(ranges, mode, progress)
+                }
+            },
+        }
+    }
+}
+
+pub fn describe() -> Description {
+    use clap::CommandFactory;
+    Description {
+        day: 2,
+        title: "Gift Shop",
+        command: Config::command(),
+        modes: vec![
+            ModeDescription {
+                name: "two",
+                help: "an ID is invalid if it is some digits repeated exactly twice",
+            },
+            ModeDescription {
+                name: "multiple",
+                help: "an ID is invalid if it is some digits repeated two or more times",
+            },
+        ],
+        grammar: grammar(),
+        examples: vec![
+            Example {
+                args: "--input data/day02/test_input.txt",
+                help: "solve part 1 for the example input",
+            },
+            Example {
+                args: "--mode multiple -v",
+                help: "solve part 2, logging the invalid ID count per range",
+            },
+            Example {
+                args: "--bench --iterations 100",
+                help: "time 100 runs of the solver",
+            },
+            Example {
+                args: "--bench --gpu --iterations 10",
+                help: "compare CPU and GPU scan times (needs the 'gpu' feature)",
+            },
+        ],
+        worked: Vec::new(),
+        stars: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::{self, Part};
+
+    fn parse_test_input_file() -> Vec<IdRange> {
+        parse_input_file("data/day02/test_input.txt").expect("Failed to parse test input file")
+    }
+
+    #[test]
+    fn test_small_range_matches_modular_scan() {
+        let ranges = parse_input(
+            "1-2000,11-22,95-115,998-1012,222220-222224,1698522-1698528,\
+             446443-446449,565653-565659,824824821-824824827,2121212118-2121212124,\
+             99990-100010",
+        )
+        .expect("ranges");
+        for mode in [Mode::Two, Mode::Multiple] {
+            for range in &ranges {
+                let modular = invalid_ids_in_range(range, mode)
+                    .fold((0, 0), |(count, sum), id| (count + 1, sum + id));
+                assert_eq!(
+                    count_sum_small_range(range, mode).expect("range"),
+                    modular,
+                    "{} in {:?} mode",
+                    range,
+                    mode
+                );
+            }
+        }
+        let mut buf = [0u8; 20];
+        assert_eq!(format_digits(0, &mut buf), b"0");
+        assert_eq!(format_digits(u64::MAX, &mut buf), b"18446744073709551615");
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    #[test]
+    fn test_parse_id_range() {
+        let input = "123-456";
+        let (_remainder, range) = parse_id_range(input).expect("parser");
+        assert_eq!(range.start, 123);
+        assert_eq!(range.end, 456);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let range: IdRange = "95-115".parse().expect("range");
+        assert_eq!(
+            range,
+            IdRange {
+                start: 95,
+                end: 115
+            }
+        );
+        assert_eq!(range.to_string().parse::<IdRange>().ok(), Some(range));
+        assert!("95-".parse::<IdRange>().is_err());
+        assert!("95-115,".parse::<IdRange>().is_err());
+        assert_eq!("multiple".parse::<Mode>().ok(), Some(Mode::Multiple));
+        assert!("three".parse::<Mode>().is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_list_format() {
+        use clap::Parser;
+        let config = Config::try_parse_from(["day02", "--list", "ranges"]).expect("valid");
+        assert_eq!(config.list, Some(ListFormat::Ranges));
+        assert!(Config::try_parse_from(["day02", "--list", "bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_id_range_sequence() {
+        let input = "11-22,95-115,998-1012";
+        let (_remainder, ranges) = parse_id_range_sequence(input).expect("parser");
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0], IdRange { start: 11, end: 22 });
+        assert_eq!(
+            ranges[1],
+            IdRange {
+                start: 95,
+                end: 115
+            }
+        );
+        assert_eq!(
+            ranges[2],
+            IdRange {
+                start: 998,
+                end: 1012
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_test_input() {
+        let ranges = parse_test_input_file();
+        assert_eq!(ranges.len(), 11);
+    }
+
+    #[test]
+    fn test_id_is_valid() {
+        let fixtures = vec![(55, false), (6464, false), (123123, false), (101, true)];
+        for (id, expected) in fixtures {
+            let result = id_is_valid(id, Mode::Two);
+            assert_eq!(
+                result, expected,
+                "id_is_valid({}) returned {}, expected {}",
+                id, result, expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_id_is_valid_multiple_mode() {
+        let fixtures = vec![
+            (55, false),
+            (6464, false),
+            (123123, false),
+            (123123123, false),
+            (1212121212, false),
+            (1111111, false),
+            (101, true),
+        ];
+        for (id, expected) in fixtures {
+            let result = id_is_valid(id, Mode::Multiple);
+            assert_eq!(
+                result, expected,
+                "id_is_valid({}) returned {}, expected {}",
+                id, result, expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_sum_both_matches_separate_scans() {
+        let ranges = parse_input("1-100000,999990-1001000,123123-123130\n").expect("ranges");
+        assert_eq!(
+            calc_count_sum_both(&ranges).expect("scan"),
+            (
+                calc_count_sum(&ranges, Mode::Two).expect("scan").totals(),
+                calc_count_sum(&ranges, Mode::Multiple)
+                    .expect("scan")
+                    .totals()
+            )
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_checked_sums_overflow() {
+        // Two 19-digit invalid IDs whose sum no longer fits in a u64.
+        let ranges = parse_input(
+            "9999999999999999990-9999999999999999999,8888888888888888880-8888888888888888889\n",
+        )
+        .expect("ranges");
+        assert!(matches!(
+            calc_count_sum(&ranges, Mode::Multiple),
+            Err(AocError::Overflow(_))
+        ));
+        assert!(calc_count_sum_parallel(&ranges, Mode::Multiple).is_err());
+        assert!(matches!(
+            calc_count_sum_both(&ranges),
+            Err(AocError::Overflow(_))
+        ));
+        assert_eq!(
+            calc_count_sum(&ranges[..1], Mode::Multiple)
+                .expect("scan")
+                .totals(),
+            (1, 9_999_999_999_999_999_999)
+        );
+    }
+
+    #[test]
+    fn test_solve_both_matches_separate_parts() {
+        let ranges = parse_test_input_file();
+        assert_eq!(
+            calc_count_sum_both(&ranges).expect("scan"),
+            (
+                calc_count_sum(&ranges, Mode::Two).expect("scan").totals(),
+                calc_count_sum(&ranges, Mode::Multiple)
+                    .expect("scan")
+                    .totals()
+            )
+        );
+        let specs: Vec<RangeSpec> = ranges.iter().copied().map(RangeSpec::Closed).collect();
+        let (one, two) = Day02.solve_both(&specs).expect("both parts");
+        assert_eq!(one, Day02.part1(&specs).expect("part 1"));
+        assert_eq!(two.to_string(), "4174379265");
+    }
+
+    #[test]
+    fn test_backend_without_gpu() {
+        let ranges = parse_test_input_file();
+        let backend = Backend::select(false);
+        assert_eq!(backend.name(), "CPU");
+        assert_eq!(
+            backend
+                .calc_count_sum(&ranges, Mode::Two)
+                .expect("scan")
+                .totals(),
+            calc_count_sum(&ranges, Mode::Two).expect("scan").totals()
+        );
+    }
+
+    #[test]
+    fn test_invalid_ids_with_reason() {
+        let range = IdRange {
+            start: 95,
+            end: 115,
+        };
+        let two: Vec<(u64, u32)> = invalid_ids_with_reason(&range, Mode::Two).collect();
+        assert_eq!(two, vec![(99, 1)]);
+        let multiple: Vec<(u64, u32)> = invalid_ids_with_reason(&range, Mode::Multiple).collect();
+        assert_eq!(multiple, vec![(99, 1), (111, 1)]);
+        let range = IdRange {
+            start: 111111,
+            end: 111111,
+        };
+        let reasons: Vec<(u64, u32)> = invalid_ids_with_reason(&range, Mode::Multiple).collect();
+        assert_eq!(reasons, vec![(111111, 3)]);
+        let range = IdRange {
+            start: 121212,
+            end: 121212,
+        };
+        let reasons: Vec<(u64, u32)> = invalid_ids_with_reason(&range, Mode::Multiple).collect();
+        assert_eq!(reasons, vec![(121212, 2)]);
+    }
+
+    #[test]
+    fn test_invalid_id_intervals_round_trip() {
+        let ranges = parse_test_input_file();
+        let set = invalid_id_intervals(&ranges, Mode::Two).expect("intervals");
+        assert_eq!(
+            set.to_string(),
+            "11-11,22-22,99-99,1010-1010,222222-222222,446446-446446,\
+             38593859-38593859,1188511885-1188511885"
+        );
+        let (count, sum) = calc_count_sum(&ranges, Mode::Two).expect("scan").totals();
+        assert_eq!(set.total_len(), count);
+        assert_eq!(set.values().sum::<i64>() as u64, sum);
+
+        // The listing reads back in as input naming exactly the invalid IDs.
+        let relisted = parse_input(&set.to_string()).expect("listing parses");
+        assert_eq!(
+            calc_count_sum(&relisted, Mode::Two).expect("scan").totals(),
+            (count, sum)
+        );
+        assert_eq!(
+            invalid_id_intervals(&relisted, Mode::Two).expect("intervals"),
+            set
+        );
+
+        let overlapping = parse_input("10-40,20-60").expect("ranges");
+        let set = invalid_id_intervals(&overlapping, Mode::Two).expect("intervals");
+        assert_eq!(set.to_string(), "11-11,22-22,33-33,44-44,55-55");
+    }
+
+    #[test]
+    fn test_parse_open_ended_range() {
+        let specs = parse_range_specs("11-22,95-,998-1012").expect("specs");
+        assert_eq!(specs[1], RangeSpec::From(95));
+        assert_eq!(specs[1].to_string(), "95-");
+        assert!(closed_ranges(&specs).is_none());
+        assert_eq!(
+            specs[1].capped(3).expect("capped"),
+            IdRange {
+                start: 95,
+                end: 999
+            }
+        );
+        assert!(specs[1].capped(MAX_DIGITS_LIMIT + 1).is_err());
+        assert!(parse_input("95-").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_bad_character() {
+        let at = |content| match parse_range_specs(content).expect_err("bad ranges") {
+            AocError::Parse { line, column, .. } => (line, column),
+            error => panic!("expected a parse error, got {}", error),
+        };
+        assert_eq!(at("11-22,\n-15"), (2, 1));
+        assert_eq!(at("11-22,95-1x5\n"), (1, 11));
+        assert_eq!(at("11-22,"), (1, 7));
+        assert!(parse_range_specs("11-22,\n  95-115\n\n").is_ok());
+    }
+
+    #[test]
+    fn test_count_sum_analytic_matches_brute_force() {
+        let mut ranges = parse_test_input_file();
+        ranges.push(IdRange {
+            start: 1,
+            end: 1_000_000,
+        });
+        ranges.push(IdRange {
+            start: 9_999_990,
+            end: 10_100_000,
+        });
+        for range in &ranges {
+            for mode in [Mode::Two, Mode::Multiple] {
+                let RangeReport { count, sum, .. } =
+                    count_sum_invalid_ids_in_range(range, mode).expect("range");
+                assert_eq!(
+                    count_sum_analytic(range, mode),
+                    (count as u128, sum as u128),
+                    "{} in {:?} mode",
+                    range,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_ids_generated() {
+        assert_eq!(
+            invalid_ids_generated(4, 2).take(3).collect::<Vec<_>>(),
+            [1010, 1111, 1212]
+        );
+        assert_eq!(invalid_ids_generated(6, 2).count(), 90);
+        assert_eq!(invalid_ids_generated(6, 3).last(), Some(999999));
+        assert_eq!(
+            invalid_ids_generated(19, 1).last(),
+            Some(9999999999999999999)
+        );
+        assert_eq!(invalid_ids_generated(6, 4).count(), 0);
+        assert_eq!(invalid_ids_generated(6, 6).count(), 0);
+        assert_eq!(invalid_ids_generated(6, 0).count(), 0);
+        assert_eq!(invalid_ids_generated(20, 10).count(), 0);
+        for digits in 2..=8 {
+            for period in 1..digits {
+                let ids: Vec<u64> = invalid_ids_generated(digits, period).collect();
+                assert!(ids.windows(2).all(|w| w[0] < w[1]));
+                assert!(ids.iter().all(|&id| id.ilog10() + 1 == digits));
+                assert!(ids.iter().all(|&id| !id_is_valid(id, Mode::Multiple)));
+            }
+        }
+
+        let mut ranges = parse_test_input_file();
+        ranges.push(IdRange {
+            start: 1,
+            end: 2_000_000,
+        });
+        for range in &ranges {
+            for mode in [Mode::Two, Mode::Multiple] {
+                let mut generated: Vec<u64> = (1..=10)
+                    .flat_map(|digits| {
+                        candidate_frequencies(digits, mode)
+                            .into_iter()
+                            .flat_map(move |freq| invalid_ids_generated(digits, digits / freq))
+                    })
+                    .filter(|id| range.ids().contains(id))
+                    .collect();
+                generated.sort_unstable();
+                generated.dedup();
+                let brute_force: Vec<u64> =
+                    range.ids().filter(|&id| !id_is_valid(id, mode)).collect();
+                assert_eq!(generated, brute_force, "{} in {:?} mode", range, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_ended_exact() {
+        let specs = parse_range_specs("1-").expect("specs");
+        // 9 two-digit, 90 four-digit and 900 six-digit IDs repeated twice.
+        let (count, _) = calc_count_sum_exact(&specs, Mode::Two, 6).expect("exact");
+        assert_eq!(count, 999);
+        let (count, _) = calc_count_sum_exact(&specs, Mode::Two, 18).expect("exact");
+        assert_eq!(count, 999_999_999);
+        let answer = run::solve_with(
+            2,
+            Part::Two,
+            "1-",
+            &Params::parse("max_digits=4").expect("params"),
+        )
+        .expect("answer");
+        let brute = calc_count_sum(
+            &[IdRange {
+                start: 1,
+                end: 9999,
+            }],
+            Mode::Multiple,
+        )
+        .expect("scan")
+        .sum;
+        assert_eq!(answer.to_string(), brute.to_string());
+    }
+
+    #[test]
+    fn test_digit_buckets() {
+        let range = IdRange {
+            start: 95,
+            end: 1012,
+        };
+        let buckets: Vec<(u32, IdRange)> = digit_buckets(&range).collect();
+        assert_eq!(range.ids().count() as u64, range.len());
+        assert_eq!(
+            buckets,
+            vec![
+                (2, IdRange { start: 95, end: 99 }),
+                (
+                    3,
+                    IdRange {
+                        start: 100,
+                        end: 999
+                    }
+                ),
+                (
+                    4,
+                    IdRange {
+                        start: 1000,
+                        end: 1012
+                    }
+                ),
+            ]
+        );
+        let top = IdRange {
+            start: u64::MAX - 1,
+            end: u64::MAX,
+        };
+        assert_eq!(digit_buckets(&top).collect::<Vec<_>>(), vec![(20, top)]);
+        let empty = IdRange { start: 5, end: 4 };
+        assert_eq!(digit_buckets(&empty).count(), 0);
+    }
+
+    #[test]
+    fn test_candidate_frequencies() {
+        assert!(candidate_frequencies(7, Mode::Two).is_empty());
+        assert_eq!(candidate_frequencies(6, Mode::Two), vec![2]);
+        assert_eq!(candidate_frequencies(6, Mode::Multiple), vec![2, 3, 6]);
+        assert_eq!(candidate_frequencies(7, Mode::Multiple), vec![7]);
+        assert!(candidate_frequencies(1, Mode::Multiple).is_empty());
+    }
+
+    #[test]
+    fn test_bucketed_scan_matches_per_id_check() {
+        let range = IdRange {
+            start: 1,
+            end: 12_000,
+        };
+        for mode in [Mode::Two, Mode::Multiple] {
+            let naive: Vec<u64> = (range.start..=range.end)
+                .filter(|&id| !id_is_valid(id, mode))
+                .collect();
+            let bucketed: Vec<u64> = invalid_ids_in_range(&range, mode).collect();
+            assert_eq!(bucketed, naive);
+        }
+    }
+
+    #[test]
+    fn test_count_sum_invalid_ids_in_range() {
+        let range = IdRange { start: 11, end: 22 };
+        let RangeReport { count, sum, .. } =
+            count_sum_invalid_ids_in_range(&range, Mode::Two).expect("range");
+        assert_eq!(count, 2);
+        assert_eq!(sum, 11 + 22);
+
+        let range = IdRange {
+            start: 95,
+            end: 115,
+        };
+        let RangeReport { count, sum, .. } =
+            count_sum_invalid_ids_in_range(&range, Mode::Two).expect("range");
+        assert_eq!(count, 1);
+        assert_eq!(sum, 99);
+    }
+
+    #[test]
+    fn test_chunked_scan_matches_sequential() {
+        let range = IdRange { start: 5, end: 25 };
+        let pieces: Vec<IdRange> = range.chunks(8).collect();
+        assert_eq!(
+            pieces,
+            vec![
+                IdRange { start: 5, end: 12 },
+                IdRange { start: 13, end: 20 },
+                IdRange { start: 21, end: 25 },
+            ]
+        );
+        assert_eq!(IdRange { start: 3, end: 2 }.chunks(8).count(), 0);
+        let top = IdRange {
+            start: u64::MAX - 2,
+            end: u64::MAX,
+        };
+        assert_eq!(top.chunks(2).map(|piece| piece.len()).sum::<u64>(), 3);
+
+        let ranges = vec![
+            IdRange {
+                start: 1,
+                end: 150_000,
+            },
+            IdRange { start: 11, end: 22 },
+            IdRange { start: 30, end: 20 },
+        ];
+        for mode in [Mode::Two, Mode::Multiple] {
+            let found = |scan: ScanReport| -> Vec<(u64, u64)> {
+                scan.ranges.iter().map(|r| (r.count, r.sum)).collect()
+            };
+            let sequential = calc_count_sum(&ranges, mode).expect("scan");
+            assert_eq!(
+                found(calc_count_sum_chunked(&ranges, mode, 7_000).expect("chunked")),
+                found(sequential.clone())
+            );
+            assert_eq!(
+                calc_count_sum_parallel(&ranges, mode)
+                    .expect("scan")
+                    .totals(),
+                sequential.totals()
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_sum_invalid_ids_in_test_input() {
+        let ranges = parse_test_input_file();
+        let expected = (8, 1227775554);
+        let (total_count, total_sum) = calc_count_sum(&ranges[..], Mode::Two)
+            .expect("scan")
+            .totals();
+        assert_eq!((total_count, total_sum), expected);
+    }
+
+    #[test]
+    fn test_coun_sum_invalid_ids_multiple_mode_in_test_input() {
+        let ranges = parse_test_input_file();
+        let expected = (13, 4174379265);
+        let (total_count, total_sum) = calc_count_sum(&ranges[..], Mode::Multiple)
+            .expect("scan")
+            .totals();
+        assert_eq!((total_count, total_sum), expected);
+    }
+}
@@ -0,0 +1,218 @@
+use super::report::{Breakdown, REPORT_SAMPLE};
+use super::{
+    Algorithm, Backend, Config, ListFormat, calc_count_sum, calc_count_sum_exact,
+    calc_count_sum_parallel, closed_ranges, density, invalid_id_intervals,
+    invalid_id_intervals_parallel, parse_range_specs,
+};
+use crate::artifacts;
+use crate::bench::{BenchReport, BenchmarkResult};
+use crate::crash;
+use crate::error::AocError;
+use crate::input;
+use crate::json::{self, ToJson};
+use crate::logging;
+use crate::result::AocResult;
+use crate::run::{self, OutputFormat, Phases, RunReport};
+use crate::sniff;
+use tracing::{info, info_span};
+
+/// Runs the day 2 command line with `config`, printing to stdout. Shared by
+/// the `day02` binary and `aoc day02`. Returns false if `--bench`
+/// regressed against `--compare-baseline`.
+pub fn run(config: Config) -> AocResult<bool> {
+    let common = &config.common;
+    logging::init(common.verbosity, common.log_format);
+    logging::set_log_every(config.log_every);
+    let input = common.input_path(2);
+    crash::install(2, &input);
+    let _span = info_span!("day", day = 2).entered();
+    if let Some(part) = common.part {
+        run::run_part(2, &input, part, common)?;
+        return Ok(true);
+    }
+
+    let mut run_dir = common.run_dir(2);
+    let mut phases = Phases::new();
+    let content = phases.time(
+        "read",
+        || input::read_to_string(&input),
+        |r| r.as_ref().map_or(0, |c| c.len()),
+    )?;
+    crash::set_input(&content);
+    sniff::check(2, &content).map_err(|e| e.in_file(&input))?;
+    let specs = phases
+        .time(
+            "parse",
+            || parse_range_specs(&content),
+            |r| r.as_ref().map_or(0, |r| r.len()),
+        )
+        .map_err(|e| e.in_file(&input))?;
+    if let Some(path) = &common.dump_parsed {
+        let path = artifacts::place(&mut run_dir, "parsed", path)?;
+        json::dump(&path, &specs)?;
+    }
+    if let Some(path) = &config.density {
+        let path = artifacts::place(&mut run_dir, density::ARTIFACT, path)?;
+        let ranges = specs
+            .iter()
+            .map(|spec| spec.capped(config.max_digits))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.in_file(&input))?;
+        let curve = density::density_curve(&ranges, config.mode);
+        std::fs::write(&path, density::to_csv(&curve)).map_err(|e| AocError::io(&path, e))?;
+    }
+    info!("Parsed {} ID ranges from input file {}", specs.len(), input);
+
+    // Open-ended ranges can only be counted analytically, which the listing
+    // and GPU scan don't do.
+    let closed = closed_ranges(&specs);
+    let brute_force = || {
+        closed.as_deref().ok_or_else(|| {
+            AocError::Unsupported(
+                "open-ended ranges are only supported by the analytic counter".to_string(),
+            )
+        })
+    };
+    let backend = Backend::select(config.gpu);
+    let algorithm = if config.list.is_some() || config.gpu {
+        Algorithm::Naive
+    } else {
+        config.algorithm_for(closed.as_deref())
+    };
+    if let Some(format) = config.list {
+        let ranges = brute_force()?;
+        let invalid = if config.parallel {
+            invalid_id_intervals_parallel(ranges, config.mode)
+        } else {
+            invalid_id_intervals(ranges, config.mode)
+        }?;
+        match (common.output, format) {
+            (OutputFormat::Text, ListFormat::Ids) => {
+                for id in invalid.values() {
+                    println!("{}", id);
+                }
+            }
+            (OutputFormat::Text, ListFormat::Ranges) => println!("{}", invalid),
+            (OutputFormat::Json, format) => {
+                let listed = match format {
+                    ListFormat::Ids => (
+                        "invalid_ids",
+                        invalid.values().collect::<Vec<_>>().to_json(),
+                    ),
+                    ListFormat::Ranges => {
+                        let ranges: Vec<String> = invalid
+                            .iter()
+                            .map(|iv| {
+                                json::object(&[
+                                    ("start", iv.start.to_json()),
+                                    ("end", iv.end.to_json()),
+                                ])
+                            })
+                            .collect();
+                        ("invalid_ranges", format!("[{}]", ranges.join(",")))
+                    }
+                };
+                println!("{}", run::part_json(2, config.mode.part(), &[listed]));
+            }
+        }
+    } else if config.report {
+        let ranges = brute_force()?;
+        let breakdown = phases.time(
+            "solve",
+            || Breakdown::scan(ranges, config.mode, REPORT_SAMPLE, config.parallel),
+            |_| ranges.len(),
+        )?;
+        match common.output {
+            OutputFormat::Text => print!("{}", breakdown.to_table()),
+            OutputFormat::Json => println!(
+                "{}",
+                run::part_json(2, config.mode.part(), &[("breakdown", breakdown.to_json())])
+            ),
+        }
+    } else if common.bench {
+        let ranges = brute_force()?;
+        let options = common.bench_options();
+        let mut report = BenchReport {
+            day: 2,
+            stages: vec![(
+                "scan",
+                BenchmarkResult::run(&options, || calc_count_sum(ranges, config.mode)),
+            )],
+        };
+        let mut contender = None;
+        if config.gpu {
+            info!("Offloading to {}", backend.name());
+            report.stages.push((
+                "offload",
+                BenchmarkResult::run(&options, || backend.calc_count_sum(ranges, config.mode)),
+            ));
+            contender = Some(backend.name());
+        } else if algorithm == Algorithm::Fast {
+            report.stages.push((
+                "fast",
+                BenchmarkResult::run(&options, || {
+                    calc_count_sum_exact(&specs, config.mode, config.max_digits)
+                }),
+            ));
+            contender = Some("Analytic counter");
+        }
+        let passed = common.finish_bench(&report)?;
+        if let (OutputFormat::Text, Some(name), [(_, scan), (_, other)]) =
+            (common.output, contender, &report.stages[..])
+        {
+            println!(
+                "\n{} speedup: {:.2}x",
+                name,
+                scan.duration().as_secs_f64() / other.duration().as_secs_f64()
+            );
+        }
+        return Ok(passed);
+    } else {
+        let (total_count, total_sum) = phases.time(
+            "solve",
+            || match (&closed, config.gpu) {
+                (None, false) => calc_count_sum_exact(&specs, config.mode, config.max_digits),
+                (Some(_), false) if algorithm == Algorithm::Fast => {
+                    calc_count_sum_exact(&specs, config.mode, config.max_digits)
+                }
+                (Some(ranges), false) if config.parallel => {
+                    let scan = calc_count_sum_parallel(ranges, config.mode)?;
+                    Ok((scan.count as u128, scan.sum as u128))
+                }
+                _ => {
+                    let mut progress = common.progress("IDs");
+                    let scan = backend.calc_count_sum_with_progress(
+                        brute_force()?,
+                        config.mode,
+                        progress.as_mut(),
+                    )?;
+                    Ok((scan.count as u128, scan.sum as u128))
+                }
+            },
+            |_| specs.len(),
+        )?;
+        let answer = phases.time("report", || total_sum.to_string(), |_| 1);
+        let report = RunReport {
+            day: 2,
+            part: config.mode.part(),
+            mode: config.mode.name().to_string(),
+            settings: vec![("algorithm", algorithm.name().to_string())],
+            metrics: vec![],
+            answer,
+            phases: phases.into_vec(),
+        };
+        match common.output {
+            OutputFormat::Text => {
+                println!("Total invalid IDs: {}", total_count);
+                println!(
+                    "Sum of invalid IDs: {}",
+                    common.group_digits.apply(&report.answer)
+                );
+                common.print_timings(&report.timings());
+            }
+            OutputFormat::Json => println!("{}", report.to_json()),
+        }
+        artifacts::finish(run_dir, &report)?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,114 @@
+//! Estimates of how long checking every ID would take, so `--algorithm
+//! auto` can count analytically when the brute-force scan would blow the
+//! time budget.
+
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::{Algorithm, IdRange, Mode, candidate_frequencies, digit_buckets};
+use crate::fmt::Elapsed;
+
+/// How long `--algorithm auto` lets the brute-force scan take, in
+/// milliseconds, unless `--time-budget` says otherwise.
+pub const DEFAULT_TIME_BUDGET_MS: u64 = 1000;
+
+/// Rough cost of visiting one ID in a release build, before checking it.
+const NANOS_PER_ID: u128 = 1;
+
+/// Rough cost of comparing one digit of an ID against one candidate block
+/// length in a release build.
+const NANOS_PER_DIGIT_CHECK: u128 = 1;
+
+/// Estimated time to check every ID in `range`: each ID costs a visit plus
+/// a comparison per digit for every block length its digit count allows.
+pub fn scan_cost(range: &IdRange, mode: Mode) -> Duration {
+    let nanos: u128 = digit_buckets(range)
+        .map(|(digits, bucket)| {
+            let checks = digits as u128 * candidate_frequencies(digits, mode).len() as u128;
+            bucket.len() as u128 * (NANOS_PER_ID + checks * NANOS_PER_DIGIT_CHECK)
+        })
+        .sum();
+    Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+}
+
+/// Estimated time to check every ID in `ranges`.
+pub fn total_scan_cost(ranges: &[IdRange], mode: Mode) -> Duration {
+    ranges
+        .iter()
+        .map(|range| scan_cost(range, mode))
+        .fold(Duration::ZERO, Duration::saturating_add)
+}
+
+/// The algorithm to run for `requested`: `Algorithm::Auto` becomes `Naive`
+/// if checking every ID in `ranges` should fit in `budget` and `Fast`
+/// otherwise, with a warning. `ranges` is `None` if some are open-ended,
+/// which only `Fast` can count.
+pub fn resolve(
+    requested: Algorithm,
+    ranges: Option<&[IdRange]>,
+    mode: Mode,
+    budget: Duration,
+) -> Algorithm {
+    if requested != Algorithm::Auto {
+        return requested;
+    }
+    let Some(ranges) = ranges else {
+        return Algorithm::Fast;
+    };
+    let estimate = total_scan_cost(ranges, mode);
+    if estimate <= budget {
+        return Algorithm::Naive;
+    }
+    warn!(
+        "Checking every ID would take about {}, over the {} budget; counting analytically instead",
+        Elapsed(estimate),
+        Elapsed(budget)
+    );
+    Algorithm::Fast
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_cost() {
+        let small = IdRange { start: 11, end: 22 };
+        // 12 two-digit IDs, each a visit and one check of both digits.
+        assert_eq!(scan_cost(&small, Mode::Two), Duration::from_nanos(12 * 3));
+        let odd = IdRange {
+            start: 100,
+            end: 999,
+        };
+        assert_eq!(scan_cost(&odd, Mode::Two), Duration::from_nanos(900));
+        assert!(scan_cost(&odd, Mode::Multiple) > scan_cost(&odd, Mode::Two));
+        let huge = IdRange {
+            start: 1,
+            end: u64::MAX,
+        };
+        assert!(scan_cost(&huge, Mode::Multiple) > Duration::from_secs(3600));
+        assert_eq!(
+            total_scan_cost(&[small, small], Mode::Two),
+            scan_cost(&small, Mode::Two) * 2
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let budget = Duration::from_millis(DEFAULT_TIME_BUDGET_MS);
+        let small = [IdRange { start: 11, end: 22 }];
+        let huge = [IdRange {
+            start: 1,
+            end: 10u64.pow(15),
+        }];
+        let auto = |ranges| resolve(Algorithm::Auto, ranges, Mode::Multiple, budget);
+        assert_eq!(auto(Some(&small[..])), Algorithm::Naive);
+        assert_eq!(auto(Some(&huge[..])), Algorithm::Fast);
+        assert_eq!(auto(None), Algorithm::Fast);
+        assert_eq!(
+            resolve(Algorithm::Naive, Some(&huge), Mode::Two, budget),
+            Algorithm::Naive
+        );
+    }
+}
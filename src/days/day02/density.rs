@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{IdRange, Mode, count_sum_analytic, digit_buckets};
+use crate::artifacts;
+
+/// Kind of the curves `--density` writes into a run directory.
+pub const ARTIFACT: &str = "density";
+
+/// Deletes the curves written into the run directories under `base`, or
+/// only lists them if `dry_run`, returning their paths.
+pub fn clean(base: &Path, dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    artifacts::clean_artifacts(base, ARTIFACT, dry_run)
+}
+
+/// Invalid IDs among the covered IDs with one digit count, i.e. within one
+/// `10^(k-1)..10^k` bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DensityBucket {
+    pub digits: u32,
+    /// IDs in the bucket covered by at least one input range.
+    pub ids: u128,
+    pub invalid: u128,
+}
+
+impl DensityBucket {
+    /// Fraction of the covered IDs that are invalid.
+    pub fn density(&self) -> f64 {
+        if self.ids == 0 {
+            0.0
+        } else {
+            self.invalid as f64 / self.ids as f64
+        }
+    }
+}
+
+/// `ranges` sorted with overlapping and adjacent ones merged, so no ID is
+/// counted twice.
+pub fn union(ranges: &[IdRange]) -> Vec<IdRange> {
+    let mut sorted: Vec<IdRange> = ranges.iter().copied().filter(|r| !r.is_empty()).collect();
+    sorted.sort_by_key(|range| range.start);
+    let mut merged: Vec<IdRange> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(1) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Invalid-ID density for each digit count the union of `ranges` reaches,
+/// counted analytically so ranges of any size are cheap.
+pub fn density_curve(ranges: &[IdRange], mode: Mode) -> Vec<DensityBucket> {
+    let mut buckets: BTreeMap<u32, DensityBucket> = BTreeMap::new();
+    for range in union(ranges) {
+        for (digits, bucket) in digit_buckets(&range) {
+            let (invalid, _) = count_sum_analytic(&bucket, mode);
+            let entry = buckets.entry(digits).or_insert(DensityBucket {
+                digits,
+                ids: 0,
+                invalid: 0,
+            });
+            entry.ids += bucket.len() as u128;
+            entry.invalid += invalid;
+        }
+    }
+    buckets.into_values().collect()
+}
+
+/// The curve as CSV with a header row, one row per digit count.
+pub fn to_csv(curve: &[DensityBucket]) -> String {
+    let mut out = String::from("digits,from,to,ids,invalid,density\n");
+    for bucket in curve {
+        let from = 10u128.pow(bucket.digits - 1) * u128::from(bucket.digits > 1);
+        let to = 10u128.pow(bucket.digits) - 1;
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{:.6e}",
+            bucket.digits,
+            from,
+            to,
+            bucket.ids,
+            bucket.invalid,
+            bucket.density()
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day02::{calc_count_sum, parse_input};
+
+    #[test]
+    fn test_union() {
+        let ranges = parse_input("30-40,1-10,11-12,5-8,50-50").expect("ranges");
+        let merged: Vec<String> = union(&ranges).iter().map(|r| r.to_string()).collect();
+        assert_eq!(merged, vec!["1-12", "30-40", "50-50"]);
+    }
+
+    #[test]
+    fn test_density_curve() {
+        let ranges = parse_input("1-99,95-115,50-60").expect("ranges");
+        let curve = density_curve(&ranges, Mode::Two);
+        assert_eq!(
+            curve,
+            vec![
+                DensityBucket {
+                    digits: 1,
+                    ids: 9,
+                    invalid: 0
+                },
+                DensityBucket {
+                    digits: 2,
+                    ids: 90,
+                    invalid: 9
+                },
+                DensityBucket {
+                    digits: 3,
+                    ids: 16,
+                    invalid: 0
+                },
+            ]
+        );
+        let total: u128 = curve.iter().map(|b| b.invalid).sum();
+        assert_eq!(
+            total,
+            calc_count_sum(&union(&ranges), Mode::Two)
+                .expect("scan")
+                .count as u128
+        );
+        assert_eq!(
+            to_csv(&curve[1..2]),
+            "digits,from,to,ids,invalid,density\n2,10,99,90,9,1.000000e-1\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Counting invalid IDs without visiting them, one digit count at a time.
+
+use super::{IdRange, Mode, candidate_frequencies, digit_buckets, repeat_multiplier};
+
+/// Count and sum of the invalid IDs in `range` without visiting them.
+///
+/// IDs of `digits` digits that repeat a `period`-digit block are exactly
+/// the multiples `x * m` of `m = 1 0..01 0..01` (the block shifted into
+/// each repetition) with `x` a `period`-digit block, so each period
+/// contributes an arithmetic series. In `Mode::Multiple` an ID repeating a
+/// block of period `p` also repeats blocks of every multiple of `p`, so
+/// IDs are attributed to their shortest period to count each once.
+pub fn count_sum_analytic(range: &IdRange, mode: Mode) -> (u128, u128) {
+    let (mut count, mut sum) = (0u128, 0u128);
+    for (digits, bucket) in digit_buckets(range) {
+        let periods: Vec<u32> = candidate_frequencies(digits, mode)
+            .iter()
+            .rev()
+            .map(|freq| digits / freq)
+            .collect();
+        match mode {
+            Mode::Two => {
+                for &period in &periods {
+                    let (c, s) = count_sum_repeating(&bucket, digits, period);
+                    count += c;
+                    sum += s;
+                }
+            }
+            Mode::Multiple => {
+                // `periods` ascends and holds every proper divisor of
+                // `digits`, so each divisor's shortest-period total is
+                // known before it is needed.
+                let mut shortest: Vec<(u32, u128, u128)> = Vec::with_capacity(periods.len());
+                for &period in &periods {
+                    let (mut c, mut s) = count_sum_repeating(&bucket, digits, period);
+                    for &(shorter, sc, ss) in &shortest {
+                        if period % shorter == 0 {
+                            c -= sc;
+                            s -= ss;
+                        }
+                    }
+                    shortest.push((period, c, s));
+                    count += c;
+                    sum += s;
+                }
+            }
+        }
+    }
+    (count, sum)
+}
+
+/// Count and sum of the `digits`-digit IDs in `bucket` made of a
+/// `period`-digit block repeated.
+fn count_sum_repeating(bucket: &IdRange, digits: u32, period: u32) -> (u128, u128) {
+    let shift = 10u128.pow(period);
+    let multiplier = repeat_multiplier(digits, period);
+    let low = (shift / 10).max((bucket.start as u128).div_ceil(multiplier));
+    let high = (shift - 1).min(bucket.end as u128 / multiplier);
+    if low > high {
+        return (0, 0);
+    }
+    let count = high - low + 1;
+    // One of `low + high` and `count` is even.
+    let blocks = if count.is_multiple_of(2) {
+        (low + high) * (count / 2)
+    } else {
+        (low + high) / 2 * count
+    };
+    (count, blocks * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day02::count_sum_invalid_ids_in_range;
+
+    #[test]
+    fn test_matches_naive_on_small_ranges() {
+        for start in (1..2_000).step_by(37) {
+            for width in [0, 1, 9, 90, 1_111, 12_345] {
+                let range = IdRange {
+                    start,
+                    end: start + width,
+                };
+                for mode in [Mode::Two, Mode::Multiple] {
+                    let naive = count_sum_invalid_ids_in_range(&range, mode).expect("range");
+                    assert_eq!(
+                        count_sum_analytic(&range, mode),
+                        (naive.count as u128, naive.sum as u128),
+                        "{} in {:?} mode",
+                        range,
+                        mode
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            count_sum_analytic(&IdRange { start: 22, end: 11 }, Mode::Two),
+            (0, 0)
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::time::Instant;
+
+use wgpu::util::DeviceExt;
+
+use super::report::{RangeReport, ScanReport};
+use super::{IdRange, Mode, candidate_frequencies, digit_buckets};
+use crate::error::AocError;
+use crate::math;
+use crate::result::AocResult;
+
+const MAX_DIGITS: usize = 20;
+const WORKGROUP_SIZE: u32 = 64;
+/// IDs per dispatch, kept under the 65535 workgroup-per-dimension limit.
+const CHUNK: u64 = 1 << 21;
+
+/// Each invocation adds its index to the chunk's base ID, held as decimal
+/// digits (least significant first) since WGSL has no 64-bit integers, then
+/// checks every candidate frequency against the digit array.
+const SHADER: &str = r#"
+struct Params {
+    digits: u32,
+    freq_mask: u32,
+    count: u32,
+    pad: u32,
+    base: array<u32, 20>,
+}
+
+@group(0) @binding(0) var<storage, read> params: Params;
+@group(0) @binding(1) var<storage, read_write> invalid: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let index = gid.x;
+    if (index >= params.count) {
+        return;
+    }
+    var id: array<u32, 20>;
+    var carry = index;
+    for (var i = 0u; i < 20u; i++) {
+        let v = params.base[i] + carry % 10u;
+        carry = carry / 10u + v / 10u;
+        id[i] = v % 10u;
+    }
+    var result = 0u;
+    for (var freq = 2u; freq <= params.digits; freq++) {
+        if ((params.freq_mask & (1u << freq)) == 0u) {
+            continue;
+        }
+        let period = params.digits / freq;
+        var repeated = true;
+        for (var j = period; j < params.digits; j++) {
+            if (id[j] != id[j % period]) {
+                repeated = false;
+                break;
+            }
+        }
+        if (repeated) {
+            result = 1u;
+            break;
+        }
+    }
+    invalid[index] = result;
+}
+"#;
+
+fn gpu_error(context: &str, e: impl std::fmt::Display) -> AocError {
+    AocError::IoError(format!("gpu {}: {}", context, e))
+}
+
+/// Brute-force invalid ID scan on the GPU using a wgpu compute shader.
+pub struct GpuScanner {
+    adapter_name: String,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuScanner {
+    /// Fails if no adapter or device is available, so callers can fall
+    /// back to the CPU scan.
+    pub fn new() -> AocResult<Self> {
+        pollster::block_on(Self::new_async())
+    }
+
+    async fn new_async() -> AocResult<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| gpu_error("adapter", e))?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("day02"),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| gpu_error("device", e))?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("day02 scan"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("day02 scan"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Ok(GpuScanner {
+            adapter_name: adapter.get_info().name,
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    pub fn count_sum_invalid_ids_in_range(
+        &self,
+        range: &IdRange,
+        mode: Mode,
+    ) -> AocResult<RangeReport> {
+        let started = Instant::now();
+        let (mut count, mut sum) = (0u64, 0u64);
+        for (digits, bucket) in digit_buckets(range) {
+            let freqs = candidate_frequencies(digits, mode);
+            if freqs.is_empty() {
+                continue;
+            }
+            let freq_mask = freqs.iter().fold(0u32, |mask, &f| mask | 1 << f);
+            let mut start = bucket.start;
+            loop {
+                let len = (bucket.end - start).min(CHUNK - 1) + 1;
+                for offset in self.scan_chunk(start, len as u32, digits, freq_mask)? {
+                    count += 1;
+                    sum = math::add(sum, start + offset as u64, "invalid ID sum")?;
+                }
+                match (start + len - 1).checked_add(1) {
+                    Some(next) if next <= bucket.end => start = next,
+                    _ => break,
+                }
+            }
+        }
+        Ok(RangeReport {
+            range: *range,
+            count,
+            sum,
+            duration: started.elapsed(),
+            cache_hit: false,
+        })
+    }
+
+    pub fn calc_count_sum(&self, ranges: &[IdRange], mode: Mode) -> AocResult<ScanReport> {
+        let reports = ranges
+            .iter()
+            .map(|range| self.count_sum_invalid_ids_in_range(range, mode))
+            .collect::<AocResult<Vec<_>>>()?;
+        ScanReport::from_reports(reports)
+    }
+
+    /// Offsets from `base` of the invalid IDs among `base..base + count`.
+    fn scan_chunk(
+        &self,
+        base: u64,
+        count: u32,
+        digits: u32,
+        freq_mask: u32,
+    ) -> AocResult<Vec<u32>> {
+        let mut params = vec![digits, freq_mask, count, 0];
+        let mut rest = base;
+        for _ in 0..MAX_DIGITS {
+            params.push((rest % 10) as u32);
+            rest /= 10;
+        }
+        let params: Vec<u8> = params.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let size = count as u64 * 4;
+
+        let params_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("params"),
+                contents: &params,
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("invalid"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(count.div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::Wait)
+            .map_err(|e| gpu_error("poll", e))?;
+        receiver
+            .recv()
+            .map_err(|e| gpu_error("readback", e))?
+            .map_err(|e| gpu_error("readback", e))?;
+
+        let offsets = slice
+            .get_mapped_range()
+            .chunks_exact(4)
+            .enumerate()
+            .filter(|(_, flag)| flag.iter().any(|&b| b != 0))
+            .map(|(i, _)| i as u32)
+            .collect();
+        readback.unmap();
+        Ok(offsets)
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::time::Duration;
+
+use super::{IdRange, Mode, invalid_ids_in_range};
+use crate::json::{self, ToJson};
+use crate::math;
+use crate::par;
+use crate::result::AocResult;
+
+/// How many of the first and last invalid IDs `--report` shows per range.
+pub const REPORT_SAMPLE: usize = 3;
+
+/// What scanning one range found, and how long it took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeReport {
+    pub range: IdRange,
+    pub count: u64,
+    pub sum: u64,
+    pub duration: Duration,
+    /// Whether `count` and `sum` were reused rather than scanned. Nothing
+    /// caches scans yet, so this is always false.
+    pub cache_hit: bool,
+}
+
+impl ToJson for RangeReport {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("range", self.range.to_json()),
+            ("count", self.count.to_json()),
+            ("sum", self.sum.to_json()),
+            ("duration_us", self.duration.as_micros().to_string()),
+            ("cache_hit", self.cache_hit.to_string()),
+        ])
+    }
+}
+
+/// Every range's report in input order, with their totals.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScanReport {
+    pub ranges: Vec<RangeReport>,
+    pub count: u64,
+    pub sum: u64,
+    pub duration: Duration,
+}
+
+impl ScanReport {
+    /// The reports in order, with their totals.
+    pub fn from_reports(reports: impl IntoIterator<Item = RangeReport>) -> AocResult<Self> {
+        let mut scan = ScanReport::default();
+        for report in reports {
+            scan.push(report)?;
+        }
+        Ok(scan)
+    }
+
+    /// Appends `report`, adding it to the totals.
+    pub fn push(&mut self, report: RangeReport) -> AocResult<()> {
+        self.count = math::add(self.count, report.count, "invalid ID count")?;
+        self.sum = math::add(self.sum, report.sum, "invalid ID sum")?;
+        self.duration += report.duration;
+        self.ranges.push(report);
+        Ok(())
+    }
+
+    /// Total count and sum of invalid IDs.
+    pub fn totals(&self) -> (u64, u64) {
+        (self.count, self.sum)
+    }
+
+    /// One row per range with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("start,end,count,sum,duration_us,cache_hit\n");
+        for report in &self.ranges {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                report.range.start,
+                report.range.end,
+                report.count,
+                report.sum,
+                report.duration.as_micros(),
+                report.cache_hit
+            );
+        }
+        out
+    }
+}
+
+impl ToJson for ScanReport {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("count", self.count.to_json()),
+            ("sum", self.sum.to_json()),
+            ("duration_us", self.duration.as_micros().to_string()),
+            ("ranges", self.ranges.to_json()),
+        ])
+    }
+}
+
+/// What `--report` shows for one range: the count and sum of its invalid
+/// IDs, how densely they occur and the first and last few of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeBreakdown {
+    pub range: IdRange,
+    pub count: u64,
+    pub sum: u64,
+    /// Up to `sample` of the smallest invalid IDs.
+    pub first: Vec<u64>,
+    /// Up to `sample` of the largest invalid IDs not already in `first`.
+    pub last: Vec<u64>,
+}
+
+impl RangeBreakdown {
+    /// Checks every ID in `range`, keeping `sample` IDs from each end.
+    pub fn scan(range: &IdRange, mode: Mode, sample: usize) -> AocResult<Self> {
+        let mut breakdown = RangeBreakdown {
+            range: *range,
+            count: 0,
+            sum: 0,
+            first: Vec::with_capacity(sample),
+            last: vec![],
+        };
+        let mut last = VecDeque::with_capacity(sample);
+        for id in invalid_ids_in_range(range, mode) {
+            breakdown.count += 1;
+            breakdown.sum = math::add(breakdown.sum, id, "invalid ID sum")?;
+            if breakdown.first.len() < sample {
+                breakdown.first.push(id);
+            } else if sample > 0 {
+                if last.len() == sample {
+                    last.pop_front();
+                }
+                last.push_back(id);
+            }
+        }
+        breakdown.last = last.into();
+        Ok(breakdown)
+    }
+
+    /// Fraction of the range's IDs that are invalid.
+    pub fn density(&self) -> f64 {
+        match self.range.len() {
+            0 => 0.0,
+            len => self.count as f64 / len as f64,
+        }
+    }
+}
+
+impl ToJson for RangeBreakdown {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("range", self.range.to_json()),
+            ("count", self.count.to_json()),
+            ("sum", self.sum.to_json()),
+            ("density", format!("{:e}", self.density())),
+            ("first", self.first.to_json()),
+            ("last", self.last.to_json()),
+        ])
+    }
+}
+
+/// Every range's breakdown in input order, with their totals.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Breakdown {
+    pub ranges: Vec<RangeBreakdown>,
+    pub count: u64,
+    pub sum: u64,
+}
+
+impl Breakdown {
+    /// Scans each of `ranges`, on several threads if `parallel`.
+    pub fn scan(ranges: &[IdRange], mode: Mode, sample: usize, parallel: bool) -> AocResult<Self> {
+        let scan = |range: &IdRange| RangeBreakdown::scan(range, mode, sample);
+        let ranges = if parallel {
+            par::map_ordered(ranges, scan)
+                .into_iter()
+                .collect::<AocResult<Vec<_>>>()
+        } else {
+            ranges.iter().map(scan).collect()
+        }?;
+        let mut breakdown = Breakdown::default();
+        for range in &ranges {
+            breakdown.count = math::add(breakdown.count, range.count, "invalid ID count")?;
+            breakdown.sum = math::add(breakdown.sum, range.sum, "invalid ID sum")?;
+        }
+        breakdown.ranges = ranges;
+        Ok(breakdown)
+    }
+
+    /// A table with a row per range and a totals row.
+    pub fn to_table(&self) -> String {
+        let ids = |ids: &[u64]| {
+            let ids: Vec<String> = ids.iter().map(u64::to_string).collect();
+            ids.join(" ")
+        };
+        let mut rows =
+            vec![["Range", "Count", "Sum", "Density", "First", "Last"].map(String::from)];
+        for range in &self.ranges {
+            rows.push([
+                range.range.to_string(),
+                range.count.to_string(),
+                range.sum.to_string(),
+                format!("{:.3e}", range.density()),
+                ids(&range.first),
+                ids(&range.last),
+            ]);
+        }
+        rows.push([
+            "Total".to_string(),
+            self.count.to_string(),
+            self.sum.to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ]);
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+            .collect();
+        let mut out = String::new();
+        for row in &rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            let _ = writeln!(out, "{}", cells.join("  ").trim_end());
+        }
+        out
+    }
+}
+
+impl ToJson for Breakdown {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("count", self.count.to_json()),
+            ("sum", self.sum.to_json()),
+            ("ranges", self.ranges.to_json()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_report() {
+        let report = |start, end, count, sum| RangeReport {
+            range: IdRange { start, end },
+            count,
+            sum,
+            duration: Duration::from_micros(5),
+            cache_hit: false,
+        };
+        let scan = ScanReport::from_reports([report(11, 22, 2, 33), report(95, 115, 1, 99)])
+            .expect("scan");
+        assert_eq!(scan.totals(), (3, 132));
+        assert_eq!(scan.duration, Duration::from_micros(10));
+        assert_eq!(
+            scan.to_csv(),
+            "start,end,count,sum,duration_us,cache_hit\n11,22,2,33,5,false\n95,115,1,99,5,false\n"
+        );
+        assert_eq!(
+            scan.to_json(),
+            "{\"count\":3,\"sum\":132,\"duration_us\":10,\"ranges\":[\
+             {\"range\":{\"start\":11,\"end\":22},\"count\":2,\"sum\":33,\"duration_us\":5,\"cache_hit\":false},\
+             {\"range\":{\"start\":95,\"end\":115},\"count\":1,\"sum\":99,\"duration_us\":5,\"cache_hit\":false}]}"
+        );
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let range = IdRange {
+            start: 1,
+            end: 10_000,
+        };
+        let wide = RangeBreakdown::scan(&range, Mode::Two, 3).expect("breakdown");
+        // 11..99 and 1010..9999 repeat a block twice: 9 + 90 IDs.
+        assert_eq!(wide.count, 99);
+        assert_eq!(wide.first, vec![11, 22, 33]);
+        assert_eq!(wide.last, vec![9797, 9898, 9999]);
+        assert_eq!(wide.density(), 99.0 / 10_000.0);
+        let narrow =
+            RangeBreakdown::scan(&IdRange { start: 11, end: 44 }, Mode::Two, 3).expect("breakdown");
+        assert_eq!((narrow.first, narrow.last), (vec![11, 22, 33], vec![44]));
+        let none =
+            RangeBreakdown::scan(&IdRange { start: 12, end: 21 }, Mode::Two, 3).expect("breakdown");
+        assert_eq!((none.count, none.density()), (0, 0.0));
+        assert_eq!(
+            none.to_json(),
+            "{\"range\":{\"start\":12,\"end\":21},\"count\":0,\"sum\":0,\
+             \"density\":0e0,\"first\":[],\"last\":[]}"
+        );
+
+        let ranges = [
+            IdRange { start: 11, end: 22 },
+            IdRange {
+                start: 95,
+                end: 115,
+            },
+        ];
+        let breakdown = Breakdown::scan(&ranges, Mode::Two, 1, false).expect("breakdown");
+        assert_eq!((breakdown.count, breakdown.sum), (3, 132));
+        assert_eq!(
+            Breakdown::scan(&ranges, Mode::Two, 1, true).ok(),
+            Some(breakdown.clone())
+        );
+        assert_eq!(
+            breakdown.to_table(),
+            "Range   Count  Sum  Density   First  Last\n\
+             11-22   2      33   1.667e-1  11     22\n\
+             95-115  1      99   4.762e-2  99\n\
+             Total   3      132\n"
+        );
+    }
+}
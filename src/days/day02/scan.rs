@@ -0,0 +1,184 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{IdRange, Mode, invalid_ids_in_range};
+use crate::error::AocError;
+use crate::json::{self, ToJson};
+use crate::result::AocResult;
+
+/// Where an `InvalidIdScan` is up to: the next ID to examine in range
+/// `range_index`. Written as `range_index:next_id` so it can be saved and
+/// handed back to `InvalidIdScan::resume`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScanCursor {
+    pub range_index: usize,
+    pub next_id: u64,
+}
+
+impl ScanCursor {
+    /// The start of `ranges`.
+    pub fn start(ranges: &[IdRange]) -> Self {
+        ScanCursor {
+            range_index: 0,
+            next_id: ranges.first().map_or(0, |range| range.start),
+        }
+    }
+}
+
+impl fmt::Display for ScanCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.range_index, self.next_id)
+    }
+}
+
+impl FromStr for ScanCursor {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> AocResult<Self> {
+        let invalid = || AocError::ParseError(format!("invalid scan cursor '{}'", s));
+        let (range_index, next_id) = s.trim().split_once(':').ok_or_else(invalid)?;
+        Ok(ScanCursor {
+            range_index: range_index.parse().map_err(|_| invalid())?,
+            next_id: next_id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl ToJson for ScanCursor {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("range_index", (self.range_index as u64).to_json()),
+            ("next_id", self.next_id.to_json()),
+        ])
+    }
+}
+
+/// Invalid IDs across several ranges in order, able to stop at any point
+/// and pick up again later from its `cursor`.
+pub struct InvalidIdScan<'a> {
+    ranges: &'a [IdRange],
+    mode: Mode,
+    cursor: ScanCursor,
+    current: Option<Box<dyn Iterator<Item = u64>>>,
+}
+
+impl<'a> InvalidIdScan<'a> {
+    pub fn new(ranges: &'a [IdRange], mode: Mode) -> Self {
+        Self::resume(ranges, mode, ScanCursor::start(ranges))
+    }
+
+    /// Continues a scan of the same `ranges` from a saved `cursor`.
+    pub fn resume(ranges: &'a [IdRange], mode: Mode, cursor: ScanCursor) -> Self {
+        InvalidIdScan {
+            ranges,
+            mode,
+            cursor,
+            current: None,
+        }
+    }
+
+    /// Where to resume to see the IDs this scan has not returned yet.
+    pub fn cursor(&self) -> ScanCursor {
+        self.cursor
+    }
+
+    /// The next `size` invalid IDs, fewer at the end.
+    pub fn next_page(&mut self, size: usize) -> Vec<u64> {
+        self.by_ref().take(size).collect()
+    }
+
+    fn advance_range(&mut self) {
+        self.current = None;
+        self.cursor.range_index += 1;
+        self.cursor.next_id = self
+            .ranges
+            .get(self.cursor.range_index)
+            .map_or(0, |range| range.start);
+    }
+}
+
+impl Iterator for InvalidIdScan<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            let range = *self.ranges.get(self.cursor.range_index)?;
+            let current = self.current.get_or_insert_with(|| {
+                let rest = IdRange {
+                    start: self.cursor.next_id.max(range.start),
+                    end: range.end,
+                };
+                Box::new(invalid_ids_in_range(&rest, self.mode))
+            });
+            match current.next() {
+                Some(id) => {
+                    match id.checked_add(1).filter(|&next| next <= range.end) {
+                        Some(next) => self.cursor.next_id = next,
+                        None => self.advance_range(),
+                    }
+                    return Some(id);
+                }
+                None => self.advance_range(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day02::{calc_count_sum, parse_input_file};
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = ScanCursor {
+            range_index: 3,
+            next_id: 222222,
+        };
+        assert_eq!(cursor.to_string(), "3:222222");
+        assert_eq!("3:222222".parse::<ScanCursor>().expect("cursor"), cursor);
+        assert_eq!(cursor.to_json(), "{\"range_index\":3,\"next_id\":222222}");
+        assert!("3".parse::<ScanCursor>().is_err());
+        assert!("x:1".parse::<ScanCursor>().is_err());
+    }
+
+    #[test]
+    fn test_resume_matches_uninterrupted_run() {
+        let ranges = parse_input_file("data/day02/test_input.txt").expect("ranges");
+        for mode in [Mode::Two, Mode::Multiple] {
+            let all: Vec<u64> = InvalidIdScan::new(&ranges, mode).collect();
+            let (count, sum) = calc_count_sum(&ranges, mode).expect("scan").totals();
+            assert_eq!(all.len() as u64, count);
+            assert_eq!(all.iter().sum::<u64>(), sum);
+            for stop in 0..=all.len() {
+                let mut scan = InvalidIdScan::new(&ranges, mode);
+                let mut seen = scan.next_page(stop);
+                let saved = scan.cursor().to_string();
+                let cursor = saved.parse().expect("cursor");
+                seen.extend(InvalidIdScan::resume(&ranges, mode, cursor));
+                assert_eq!(seen, all, "stopped after {} in {:?} mode", stop, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_at_top_of_range() {
+        let ranges = [
+            IdRange {
+                start: 9999,
+                end: 9999,
+            },
+            IdRange { start: 11, end: 11 },
+        ];
+        let mut scan = InvalidIdScan::new(&ranges, Mode::Multiple);
+        assert_eq!(scan.next(), Some(9999));
+        assert_eq!(
+            scan.cursor(),
+            ScanCursor {
+                range_index: 1,
+                next_id: 11
+            }
+        );
+        assert_eq!(scan.collect::<Vec<_>>(), vec![11]);
+    }
+}
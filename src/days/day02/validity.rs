@@ -0,0 +1,115 @@
+use std::fmt;
+
+use super::witnessing_period;
+
+/// Why an ID is invalid: its digits are one block of `period` digits
+/// written `repeats` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reason {
+    pub period: u32,
+    pub repeats: u32,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a {}-digit block repeated {} times",
+            self.period, self.repeats
+        )
+    }
+}
+
+/// A rule deciding which IDs are invalid. New rules implement this and
+/// are added to `VALIDITIES` rather than growing a match on `Mode`.
+pub trait Validity: Sync {
+    /// The name the rule is registered under.
+    fn name(&self) -> &'static str;
+
+    /// Repetition counts worth checking for IDs with `digits` digits, in
+    /// the order they are tried.
+    fn frequencies(&self, digits: u32) -> Vec<u32>;
+
+    fn is_invalid(&self, id: u64) -> Option<Reason> {
+        let digits = id.checked_ilog10().unwrap_or(0) + 1;
+        let period = witnessing_period(id, digits, &self.frequencies(digits))?;
+        Some(Reason {
+            period,
+            repeats: digits / period,
+        })
+    }
+}
+
+/// Invalid if made of a block repeated exactly twice.
+pub struct Two;
+
+impl Validity for Two {
+    fn name(&self) -> &'static str {
+        "two"
+    }
+
+    fn frequencies(&self, digits: u32) -> Vec<u32> {
+        dividing(digits, 2..=2)
+    }
+}
+
+/// Invalid if made of a block repeated two or more times.
+pub struct Multiple;
+
+impl Validity for Multiple {
+    fn name(&self) -> &'static str {
+        "multiple"
+    }
+
+    fn frequencies(&self, digits: u32) -> Vec<u32> {
+        dividing(digits, 2..=digits)
+    }
+}
+
+/// The counts in `freqs` that divide `digits` evenly: only those can
+/// produce a repeated block.
+fn dividing(digits: u32, freqs: impl Iterator<Item = u32>) -> Vec<u32> {
+    freqs.filter(|freq| digits.is_multiple_of(*freq)).collect()
+}
+
+/// Every registered rule.
+pub const VALIDITIES: &[&dyn Validity] = &[&Two, &Multiple];
+
+/// The registered rule called `name`.
+pub fn find(name: &str) -> Option<&'static dyn Validity> {
+    VALIDITIES.iter().copied().find(|v| v.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        assert_eq!(find("two").map(|v| v.name()), Some("two"));
+        assert_eq!(find("multiple").map(|v| v.name()), Some("multiple"));
+        assert!(find("base-7").is_none());
+
+        let multiple = find("multiple").expect("registered");
+        assert_eq!(
+            multiple.is_invalid(111111),
+            Some(Reason {
+                period: 3,
+                repeats: 2
+            })
+        );
+        assert_eq!(
+            multiple.is_invalid(824824824),
+            Some(Reason {
+                period: 3,
+                repeats: 3
+            })
+        );
+        assert_eq!(Two.is_invalid(824824824), None);
+        assert_eq!(Two.is_invalid(7), None);
+        assert_eq!(
+            Two.is_invalid(6464).map(|r| r.to_string()),
+            Some("a 2-digit block repeated 2 times".to_string())
+        );
+    }
+}
@@ -0,0 +1,1371 @@
+use core::fmt;
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::cli::CommonConfig;
+use crate::describe::{Description, Example, Grammar, ModeDescription, Rules, WorkedExample};
+use crate::error::AocError;
+use crate::input;
+use crate::math;
+use crate::par;
+use crate::params::Params;
+use crate::progress::{NoProgress, Progress};
+use crate::result::AocResult;
+use crate::solution::{Answer, Solution};
+use alphabet::{Alphabet, DECIMAL};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use tracing::{debug, info};
+
+pub mod alphabet;
+pub mod cli;
+pub mod columns;
+pub mod compare;
+pub mod html;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Mode {
+    Two,
+    Twelve,
+}
+
+impl Mode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Two => "two",
+            Mode::Twelve => "twelve",
+        }
+    }
+
+    /// The puzzle part this mode answers.
+    pub fn part(self) -> u32 {
+        match self {
+            Mode::Two => 1,
+            Mode::Twelve => 2,
+        }
+    }
+
+    /// How many batteries to turn on per line.
+    pub fn digits(self) -> u32 {
+        match self {
+            Mode::Two => 2,
+            Mode::Twelve => 12,
+        }
+    }
+}
+
+impl From<&str> for Mode {
+    fn from(s: &str) -> Self {
+        match s {
+            "two" => Mode::Two,
+            "twelve" => Mode::Twelve,
+            _ => Mode::Two,
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = AocError;
+
+    /// Unlike `From<&str>`, rejects anything but `two` and `twelve`.
+    fn from_str(s: &str) -> AocResult<Self> {
+        match s {
+            "two" | "twelve" => Ok(Mode::from(s)),
+            _ => Err(AocError::InvalidArgument(format!(
+                "unknown mode '{}', expected 'two' or 'twelve'",
+                s
+            ))),
+        }
+    }
+}
+
+/// What to do with blank lines in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlankLines {
+    #[default]
+    Error,
+    Skip,
+}
+
+impl From<&str> for BlankLines {
+    fn from(s: &str) -> Self {
+        match s {
+            "skip" => BlankLines::Skip,
+            _ => BlankLines::Error,
+        }
+    }
+}
+
+#[derive(clap::Parser, Debug, Clone)]
+pub struct Config {
+    #[command(flatten)]
+    pub common: CommonConfig,
+
+    #[clap(
+        short,
+        long,
+        default_value = "two",
+        value_parser = PossibleValuesParser::new(["two", "twelve"]).map(|s| Mode::from(s.as_str())),
+        help = "Mode: 'two' or 'twelve'"
+    )]
+    pub mode: Mode,
+
+    #[clap(
+        long,
+        default_value = "error",
+        value_parser = PossibleValuesParser::new(["error", "skip"]).map(|s| BlankLines::from(s.as_str())),
+        help = "Blank lines: 'error' or 'skip'"
+    )]
+    pub blank_lines: BlankLines,
+
+    #[clap(
+        long,
+        default_value = "earliest",
+        value_parser = PossibleValuesParser::new(["earliest", "latest"]).map(|s| TieBreak::from(s.as_str())),
+        help = "Pick the 'earliest' or 'latest' of equal best batteries"
+    )]
+    pub tie_break: TieBreak,
+
+    #[clap(
+        long,
+        help = "Write the selected batteries as an HTML page to this path"
+    )]
+    pub html: Option<String>,
+
+    #[clap(long, help = "Solve the lines on several threads")]
+    pub parallel: bool,
+
+    #[clap(
+        long,
+        help = "Each row holds several whitespace-separated battery strings, solved separately"
+    )]
+    pub columns: bool,
+
+    #[clap(
+        long,
+        conflicts_with = "columns",
+        help = "Join every line into one battery string and solve that"
+    )]
+    pub concat: bool,
+
+    #[clap(
+        long,
+        default_value = "0123456789",
+        conflicts_with_all = ["concat", "part", "bench"],
+        help = "Battery symbols from least to most valuable, e.g. 0123456789ABCDEF"
+    )]
+    pub alphabet: Alphabet,
+}
+
+fn max_char(s: &str) -> AocResult<(usize, char)> {
+    s.chars()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| {
+            if a >= b {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        })
+        .ok_or(AocError::ParseError(format!("max_char: {}", s)))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatteryLine {
+    pub line: String,
+    /// How many batteries to turn on, from a `K:` prefix on the line.
+    /// Lines without one use the count from the mode.
+    pub digits: Option<u32>,
+}
+
+impl BatteryLine {
+    /// The line's own digit count, or `default` if it has none.
+    pub fn digits_or(&self, default: u32) -> u32 {
+        self.digits.unwrap_or(default)
+    }
+
+    fn largest_digit(s: &str, offset: usize, max_offset: usize) -> AocResult<(usize, u32)> {
+        let mut max = max_char(&s[offset..max_offset])?;
+        max.0 += offset;
+        let num = char::to_digit(max.1, 10)
+            .ok_or_else(|| AocError::ParseError(format!("largest_digit: {}", max.1)))?;
+        Ok((max.0, num))
+    }
+
+    pub fn largest_number(&self, digits: u32) -> AocResult<u64> {
+        if self.line.len() < digits as usize {
+            return Err(AocError::ParseError(format!(
+                "line '{}' has fewer than {} batteries",
+                self.line, digits
+            )));
+        }
+        let mut num: u64 = 0;
+        let mut offset = 0;
+        let first_max_offset = self.line.len() - (digits as usize - 1);
+
+        for (i, max_offset) in (0..digits).zip(first_max_offset..) {
+            debug!("Finding digit {}", i);
+
+            let digit = Self::largest_digit(&self.line, offset, max_offset)?;
+
+            num = math::push_digit(num, digit.1 as u64, "jolt")?;
+            offset = digit.0 + 1;
+        }
+
+        Ok(num)
+    }
+}
+
+const NO_INDEX: u32 = u32::MAX;
+
+/// Which battery to pick when a search window holds the best digit more
+/// than once.
+///
+/// `Earliest` always gives the largest number. `Latest` leaves narrower
+/// windows for the remaining picks and can give a smaller number when the
+/// skipped copies were needed, e.g. `991` picking two batteries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    #[default]
+    Earliest,
+    Latest,
+}
+
+impl TieBreak {
+    pub fn name(self) -> &'static str {
+        match self {
+            TieBreak::Earliest => "earliest",
+            TieBreak::Latest => "latest",
+        }
+    }
+}
+
+impl From<&str> for TieBreak {
+    fn from(s: &str) -> Self {
+        match s {
+            "latest" => TieBreak::Latest,
+            _ => TieBreak::Earliest,
+        }
+    }
+}
+
+/// One greedy pick: the positions searched and the one chosen.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SelectionStep {
+    pub window: RangeInclusive<usize>,
+    pub position: usize,
+}
+
+/// Preprocessed `BatteryLine` answering "where is the next `d` at or after
+/// position `i`" in constant time, so repeated digit-selection queries on
+/// one line don't rescan it. Digits are battery values in the line's
+/// alphabet, `0` up to its radix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatteryLineIndex {
+    digits: Vec<u8>,
+    radix: usize,
+    /// Row `i` of `radix` entries, where entry `d` is the first index `>= i`
+    /// holding digit `d`, with one extra row at the end that is always
+    /// empty.
+    next: Vec<u32>,
+    /// Row `i` of `radix` entries, where entry `d` is the last index `< i`
+    /// holding digit `d`, so row 0 is always empty.
+    prev: Vec<u32>,
+}
+
+impl BatteryLineIndex {
+    pub fn new(line: &BatteryLine) -> AocResult<Self> {
+        Self::with_alphabet(line, &DECIMAL)
+    }
+
+    /// Index of `line` with its batteries valued by `alphabet`.
+    pub fn with_alphabet(line: &BatteryLine, alphabet: &Alphabet) -> AocResult<Self> {
+        let digits = alphabet.values(line)?;
+        let radix = alphabet.radix();
+        let rows = digits.len() + 1;
+        let mut next = vec![NO_INDEX; rows * radix];
+        for i in (0..digits.len()).rev() {
+            next.copy_within((i + 1) * radix..(i + 2) * radix, i * radix);
+            next[i * radix + digits[i] as usize] = i as u32;
+        }
+        let mut prev = vec![NO_INDEX; rows * radix];
+        for i in 0..digits.len() {
+            prev.copy_within(i * radix..(i + 1) * radix, (i + 1) * radix);
+            prev[(i + 1) * radix + digits[i] as usize] = i as u32;
+        }
+        Ok(BatteryLineIndex {
+            digits,
+            radix,
+            next,
+            prev,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn digit_at(&self, position: usize) -> Option<u8> {
+        self.digits.get(position).copied()
+    }
+
+    /// First position at or after `from` holding `digit`.
+    pub fn next_digit(&self, from: usize, digit: u8) -> Option<usize> {
+        let index = *self.next.get(self.row(from, digit)?)?;
+        (index != NO_INDEX).then_some(index as usize)
+    }
+
+    /// Last position at or before `upto` holding `digit`.
+    pub fn prev_digit(&self, upto: usize, digit: u8) -> Option<usize> {
+        let index = *self.prev.get(self.row(upto.checked_add(1)?, digit)?)?;
+        (index != NO_INDEX).then_some(index as usize)
+    }
+
+    /// Where `digit`'s entry in row `row` of `next` or `prev` is.
+    fn row(&self, row: usize, digit: u8) -> Option<usize> {
+        let digit = digit as usize;
+        if digit >= self.radix {
+            return None;
+        }
+        row.checked_mul(self.radix)?.checked_add(digit)
+    }
+
+    /// Every digit value, smallest first.
+    fn values(&self) -> impl DoubleEndedIterator<Item = u8> + Clone {
+        (0..self.radix).map(|d| d as u8)
+    }
+
+    /// Positions of the `count` batteries making the largest number, or
+    /// `None` if the line is too short.
+    pub fn largest_positions(&self, count: usize) -> Option<Vec<usize>> {
+        self.select(count, self.values().rev())
+    }
+
+    /// Positions of the `count` batteries making the smallest number.
+    pub fn smallest_positions(&self, count: usize) -> Option<Vec<usize>> {
+        self.select(count, self.values())
+    }
+
+    pub fn largest_number(&self, count: usize) -> Option<u64> {
+        self.number_at(&self.largest_positions(count)?).ok()
+    }
+
+    pub fn smallest_number(&self, count: usize) -> Option<u64> {
+        self.number_at(&self.smallest_positions(count)?).ok()
+    }
+
+    /// The steps that pick the largest number from `count` batteries, each
+    /// with the window it searched.
+    pub fn largest_steps(&self, count: usize) -> Option<Vec<SelectionStep>> {
+        self.largest_steps_with(count, TieBreak::Earliest)
+    }
+
+    /// `largest_steps`, breaking ties between equal digits in a window with
+    /// `tie_break`.
+    pub fn largest_steps_with(
+        &self,
+        count: usize,
+        tie_break: TieBreak,
+    ) -> Option<Vec<SelectionStep>> {
+        self.select_steps(count, self.values().rev(), tie_break)
+    }
+
+    /// The number picked by `largest_steps_with`.
+    pub fn largest_number_with(&self, count: usize, tie_break: TieBreak) -> Option<u64> {
+        let steps = self.largest_steps_with(count, tie_break)?;
+        let positions: Vec<usize> = steps.iter().map(|step| step.position).collect();
+        self.number_at(&positions).ok()
+    }
+
+    fn select<I>(&self, count: usize, preference: I) -> Option<Vec<usize>>
+    where
+        I: Iterator<Item = u8> + Clone,
+    {
+        let steps = self.select_steps(count, preference, TieBreak::Earliest)?;
+        Some(steps.iter().map(|step| step.position).collect())
+    }
+
+    /// Greedily picks, for each output digit, a position holding the first
+    /// digit in `preference` that still leaves room for the rest, choosing
+    /// among equal digits by `tie_break`.
+    fn select_steps<I>(
+        &self,
+        count: usize,
+        preference: I,
+        tie_break: TieBreak,
+    ) -> Option<Vec<SelectionStep>>
+    where
+        I: Iterator<Item = u8> + Clone,
+    {
+        if count > self.len() {
+            return None;
+        }
+        let mut steps = Vec::with_capacity(count);
+        let mut from = 0;
+        for remaining in (1..=count).rev() {
+            let last = self.len() - remaining;
+            let position = preference.clone().find_map(|d| match tie_break {
+                TieBreak::Earliest => self.next_digit(from, d).filter(|&p| p <= last),
+                TieBreak::Latest => self.prev_digit(last, d).filter(|&p| p >= from),
+            })?;
+            steps.push(SelectionStep {
+                window: from..=last,
+                position,
+            });
+            from = position + 1;
+        }
+        Some(steps)
+    }
+
+    fn number_at(&self, positions: &[usize]) -> AocResult<u64> {
+        positions.iter().try_fold(0, |num, &p| {
+            math::push_digit_in(num, self.digits[p] as u64, self.radix as u64, "jolt")
+        })
+    }
+}
+
+impl fmt::Display for BatteryLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(digits) = self.digits {
+            write!(f, "{}:", digits)?;
+        }
+        write!(f, "{}", self.line)
+    }
+}
+
+impl FromStr for BatteryLine {
+    type Err = AocError;
+
+    /// `batteries` or `K:batteries`, as `parse_battery_line` reads them.
+    fn from_str(s: &str) -> AocResult<Self> {
+        parse_battery_line(s)
+    }
+}
+
+pub fn read_input_file(path: &str) -> AocResult<Vec<BatteryLine>> {
+    read_input_file_with(path, BlankLines::default())
+}
+
+pub fn read_input_file_with(path: &str, blank_lines: BlankLines) -> AocResult<Vec<BatteryLine>> {
+    let content = input::read_to_string(path)?;
+    parse_input(&content, blank_lines).map_err(|e| match e {
+        AocError::EmptyInput(_) => AocError::EmptyInput(path.to_string()),
+        e => e,
+    })
+}
+
+/// Parses one `BatteryLine` per line. Fails with `EmptyInput` if there are
+/// no battery lines at all, and on blank lines unless they are skipped.
+pub fn parse_input(input: &str, blank_lines: BlankLines) -> AocResult<Vec<BatteryLine>> {
+    let lines = AocError::collect_all(
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| blank_lines == BlankLines::Error || !line.trim().is_empty())
+            .map(|(number, line)| {
+                if line.trim().is_empty() {
+                    return Err(
+                        AocError::ParseError("blank line".to_string()).at_line(number + 1, line)
+                    );
+                }
+                parse_battery_line(line).map_err(|e| e.at_line(number + 1, line))
+            }),
+    )?;
+    if lines.is_empty() {
+        return Err(AocError::EmptyInput("no battery lines".to_string()));
+    }
+    Ok(lines)
+}
+
+/// The whole input as one battery line: the lines' batteries joined in
+/// order with the newlines dropped. Blank lines add nothing; `K:` prefixes
+/// are rejected, since there is only the mode's digit count.
+pub fn parse_concatenated(input: &str) -> AocResult<BatteryLine> {
+    let mut batteries = String::with_capacity(input.len());
+    for (number, line) in input.lines().enumerate() {
+        if let Some((column, c)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(AocError::Parse {
+                line: number + 1,
+                column: column + 1,
+                input: line.to_string(),
+                source: Box::new(AocError::ParseError(format!("invalid battery '{}'", c))),
+            });
+        }
+        batteries.push_str(line);
+    }
+    if batteries.is_empty() {
+        return Err(AocError::EmptyInput("no batteries".to_string()));
+    }
+    Ok(BatteryLine {
+        line: batteries,
+        digits: None,
+    })
+}
+
+pub fn grammar() -> Rules {
+    Rules(vec![
+        ("input", Grammar::lines(Grammar::rule("batteries"))),
+        (
+            "batteries",
+            Grammar::seq([Grammar::rule("target"), Grammar::Digits]),
+        ),
+        (
+            "target",
+            Grammar::alt([
+                Grammar::seq([Grammar::Digits, Grammar::lit(":")]),
+                Grammar::lit(""),
+            ]),
+        ),
+    ])
+}
+
+/// Parses `batteries` or `K:batteries`, where `K` is how many batteries to
+/// turn on for this line alone.
+pub fn parse_battery_line(line: &str) -> AocResult<BatteryLine> {
+    let (digits, batteries) = match line.split_once(':') {
+        Some((digits, batteries)) => {
+            let digits = digits.trim().parse().map_err(|_| {
+                AocError::ParseError(format!("invalid digit count '{}' in '{}'", digits, line))
+            })?;
+            (Some(digits), batteries)
+        }
+        None => (None, line),
+    };
+    Ok(BatteryLine {
+        line: batteries.to_string(),
+        digits,
+    })
+}
+
+/// Picks the largest number made from `digits` batteries of a line.
+pub type Selector = fn(&BatteryLine, u32) -> AocResult<u64>;
+
+fn select_by_rescan(line: &BatteryLine, digits: u32) -> AocResult<u64> {
+    line.largest_number(digits)
+}
+
+fn select_by_index(line: &BatteryLine, digits: u32) -> AocResult<u64> {
+    let index = BatteryLineIndex::new(line)?;
+    let positions = index.largest_positions(digits as usize).ok_or_else(|| {
+        AocError::ParseError(format!(
+            "line '{}' has fewer than {} batteries",
+            line, digits
+        ))
+    })?;
+    index.number_at(&positions)
+}
+
+/// Keeps a decreasing stack of digits, popping smaller ones while enough
+/// digits remain to fill `digits` places.
+fn select_by_stack(line: &BatteryLine, digits: u32) -> AocResult<u64> {
+    let batteries = battery_digits(line)?;
+    let keep = check_length(line, &batteries, digits)?;
+    let mut drops = batteries.len() - keep;
+    let mut stack: Vec<u8> = Vec::with_capacity(batteries.len());
+    for &digit in &batteries {
+        while drops > 0 && stack.last().is_some_and(|&top| top < digit) {
+            stack.pop();
+            drops -= 1;
+        }
+        stack.push(digit);
+    }
+    stack[..keep]
+        .iter()
+        .try_fold(0, |num, &digit| math::push_digit(num, digit as u64, "jolt"))
+}
+
+/// `best[j]` holds the largest number made from `j` of the batteries seen
+/// so far, extended by one battery at a time.
+fn select_by_dp(line: &BatteryLine, digits: u32) -> AocResult<u64> {
+    let batteries = battery_digits(line)?;
+    let keep = check_length(line, &batteries, digits)?;
+    let mut best: Vec<Option<u64>> = vec![None; keep + 1];
+    best[0] = Some(0);
+    for (i, &digit) in batteries.iter().enumerate() {
+        for j in (1..=keep.min(i + 1)).rev() {
+            if let Some(prefix) = best[j - 1] {
+                let candidate = math::push_digit(prefix, digit as u64, "jolt")?;
+                best[j] = Some(best[j].map_or(candidate, |b| b.max(candidate)));
+            }
+        }
+    }
+    Ok(best[keep].unwrap_or_default())
+}
+
+fn battery_digits(line: &BatteryLine) -> AocResult<Vec<u8>> {
+    DECIMAL.values(line)
+}
+
+fn check_length(line: &BatteryLine, batteries: &[u8], digits: u32) -> AocResult<usize> {
+    if batteries.len() < digits as usize {
+        return Err(AocError::ParseError(format!(
+            "line '{}' has fewer than {} batteries",
+            line, digits
+        )));
+    }
+    Ok(digits as usize)
+}
+
+/// The largest number from `digits` batteries when the line opens with at
+/// least that many 9s, which no other choice can beat. `None` means the
+/// line needs a real search, which is also where an overflow is reported.
+pub fn nines_prefix_jolt(line: &BatteryLine, digits: u32) -> Option<u64> {
+    top_prefix_jolt(line, digits, &DECIMAL)
+}
+
+/// `nines_prefix_jolt` for any alphabet: the line opens with at least
+/// `digits` of its most valuable symbol.
+pub fn top_prefix_jolt(line: &BatteryLine, digits: u32, alphabet: &Alphabet) -> Option<u64> {
+    let top = alphabet.top();
+    let mut batteries = line.line.chars();
+    let top_value = (alphabet.radix() - 1) as u8;
+    (0..digits).try_fold(0, |num, _| {
+        batteries.next().filter(|&c| c == top)?;
+        alphabet.push(num, top_value).ok()
+    })
+}
+
+/// `nines_prefix_jolt` when it applies, otherwise the monotonic stack. The
+/// rest of the line is still checked so bad batteries are reported either
+/// way.
+fn select_with_fast_path(line: &BatteryLine, digits: u32) -> AocResult<u64> {
+    match nines_prefix_jolt(line, digits) {
+        Some(jolt) => {
+            battery_digits(line)?;
+            Ok(jolt)
+        }
+        None => select_by_stack(line, digits),
+    }
+}
+
+/// Every selection algorithm, by name. The tests run each one against all
+/// day 3 fixtures in the golden-fixture manifest.
+pub const SELECTORS: &[(&str, Selector)] = &[
+    ("windowed-max", select_by_rescan),
+    ("index", select_by_index),
+    ("monotonic-stack", select_by_stack),
+    ("dp", select_by_dp),
+    ("nines-prefix", select_with_fast_path),
+];
+
+/// How many lines `nines_prefix_jolt` settles without a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FastPathStats {
+    pub lines: usize,
+    pub hits: usize,
+}
+
+impl FastPathStats {
+    /// The fast path only fires for `TieBreak::Earliest`; `Latest` can
+    /// skip past the leading 9s and pick a smaller number.
+    pub fn count(
+        lines: &[BatteryLine],
+        mode: Mode,
+        tie_break: TieBreak,
+        alphabet: &Alphabet,
+    ) -> Self {
+        let hits = match tie_break {
+            TieBreak::Earliest => lines
+                .iter()
+                .filter(|line| {
+                    top_prefix_jolt(line, line.digits_or(mode.digits()), alphabet).is_some()
+                })
+                .count(),
+            TieBreak::Latest => 0,
+        };
+        FastPathStats {
+            lines: lines.len(),
+            hits,
+        }
+    }
+}
+
+impl fmt::Display for FastPathStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "nines-prefix fast path settled {} of {} lines",
+            self.hits, self.lines
+        )
+    }
+}
+
+pub fn calc_total_jolt(lines: &[BatteryLine], mode: Mode) -> AocResult<u64> {
+    calc_total_jolt_with_progress(lines, mode, &mut NoProgress)
+}
+
+/// Total jolt picking batteries with `tie_break` and valuing them by
+/// `alphabet`, reporting progress as the number of lines solved.
+pub fn calc_total_jolt_with_tie_break(
+    lines: &[BatteryLine],
+    mode: Mode,
+    tie_break: TieBreak,
+    alphabet: &Alphabet,
+    progress: &mut dyn Progress,
+) -> AocResult<u64> {
+    progress.set_total(lines.len() as u64);
+    let mut total_jolt = 0;
+    for line in lines {
+        total_jolt = add_jolt(total_jolt, line_jolt(line, mode, tie_break, alphabet)?)?;
+        progress.inc(1);
+    }
+    progress.finish();
+    Ok(total_jolt)
+}
+
+/// `calc_total_jolt_with_tie_break` with the lines solved on several
+/// threads.
+pub fn calc_total_jolt_parallel(
+    lines: &[BatteryLine],
+    mode: Mode,
+    tie_break: TieBreak,
+    alphabet: &Alphabet,
+) -> AocResult<u64> {
+    checked_total(par::map_ordered(lines, |line| {
+        line_jolt(line, mode, tie_break, alphabet)
+    }))
+}
+
+/// The sum of `jolts`, failing on the first error or if it overflows.
+fn checked_total(jolts: impl IntoIterator<Item = AocResult<u64>>) -> AocResult<u64> {
+    jolts
+        .into_iter()
+        .try_fold(0, |total, jolt| add_jolt(total, jolt?))
+}
+
+fn add_jolt(total: u64, jolt: u64) -> AocResult<u64> {
+    total
+        .checked_add(jolt)
+        .ok_or_else(|| math::overflow("total jolt", format!("{} + {}", total, jolt)))
+}
+
+fn line_jolt(
+    line: &BatteryLine,
+    mode: Mode,
+    tie_break: TieBreak,
+    alphabet: &Alphabet,
+) -> AocResult<u64> {
+    let digits = line.digits_or(mode.digits());
+    if tie_break == TieBreak::Earliest
+        && let Some(jolt) = top_prefix_jolt(line, digits, alphabet)
+    {
+        alphabet.values(line)?;
+        debug!(
+            "- {} opens with {} '{}'s, giving {}",
+            line,
+            digits,
+            alphabet.top(),
+            jolt
+        );
+        return Ok(jolt);
+    }
+    let digits = digits as usize;
+    let index = BatteryLineIndex::with_alphabet(line, alphabet)?;
+    let steps = index.largest_steps_with(digits, tie_break).ok_or_else(|| {
+        AocError::ParseError(format!("fewer than {} batteries in {}", digits, line))
+    })?;
+    let positions: Vec<usize> = steps.iter().map(|step| step.position).collect();
+    let jolt = index.number_at(&positions)?;
+    debug!("- {} picking the {} gives {}", line, tie_break.name(), jolt);
+    Ok(jolt)
+}
+
+/// `calc_total_jolt`, reporting progress as the number of lines solved.
+pub fn calc_total_jolt_with_progress(
+    lines: &[BatteryLine],
+    mode: Mode,
+    progress: &mut dyn Progress,
+) -> AocResult<u64> {
+    progress.set_total(lines.len() as u64);
+    let mut total_jolt = 0;
+    for line in lines {
+        let jolt = line.largest_number(line.digits_or(mode.digits()))?;
+        total_jolt = add_jolt(total_jolt, jolt)?;
+        info!(
+            "- In {} you can make the largest jolt possible, {}",
+            line, jolt
+        );
+        progress.inc(1);
+    }
+    progress.finish();
+    Ok(total_jolt)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<BatteryLine>;
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Vec<BatteryLine>> {
+        parse_input(input, BlankLines::default())
+    }
+
+    fn part1(&self, input: &Vec<BatteryLine>) -> AocResult<Answer> {
+        Ok(Answer::from(calc_total_jolt(input, Mode::Two)?))
+    }
+
+    fn part2(&self, input: &Vec<BatteryLine>) -> AocResult<Answer> {
+        Ok(Answer::from(calc_total_jolt(input, Mode::Twelve)?))
+    }
+
+    fn param_names(&self) -> &'static [&'static str] {
+        &["digits"]
+    }
+
+    fn part1_with(&self, input: &Vec<BatteryLine>, params: &Params) -> AocResult<Answer> {
+        let digits = params.get_or("digits", Mode::Two.digits())?;
+        Ok(Answer::from(total_jolt_with_digits(input, digits)?))
+    }
+
+    fn part2_with(&self, input: &Vec<BatteryLine>, params: &Params) -> AocResult<Answer> {
+        let digits = params.get_or("digits", Mode::Twelve.digits())?;
+        Ok(Answer::from(total_jolt_with_digits(input, digits)?))
+    }
+}
+
+/// Total jolt turning on `digits` batteries on each line without its own
+/// count.
+fn total_jolt_with_digits(lines: &[BatteryLine], digits: u32) -> AocResult<u64> {
+    checked_total(
+        lines
+            .iter()
+            .map(|line| line.largest_number(line.digits_or(digits))),
+    )
+}
+
+/// A battery line and the largest joltage it makes in `mode`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JoltExample {
+    pub line: &'static str,
+    pub mode: Mode,
+    pub jolt: u64,
+}
+
+/// The worked examples shown by `describe`. The tests check every one
+/// against each of `SELECTORS`, and so does this doctest:
+///
+/// ```
+/// use aoc25::days::day03::{EXAMPLES, parse_battery_line};
+///
+/// for example in EXAMPLES {
+///     let line = parse_battery_line(example.line).unwrap();
+///     assert_eq!(line.largest_number(example.mode.digits()).unwrap(), example.jolt);
+/// }
+/// ```
+pub const EXAMPLES: &[JoltExample] = &[
+    JoltExample {
+        line: "123456",
+        mode: Mode::Two,
+        jolt: 56,
+    },
+    JoltExample {
+        line: "987654321111111",
+        mode: Mode::Two,
+        jolt: 98,
+    },
+    JoltExample {
+        line: "811111111111119",
+        mode: Mode::Two,
+        jolt: 89,
+    },
+    JoltExample {
+        line: "818181911112111",
+        mode: Mode::Two,
+        jolt: 92,
+    },
+    JoltExample {
+        line: "234234234234278",
+        mode: Mode::Twelve,
+        jolt: 434234234278,
+    },
+    JoltExample {
+        line: "818181911112111",
+        mode: Mode::Twelve,
+        jolt: 888911112111,
+    },
+];
+
+pub fn describe() -> Description {
+    use clap::CommandFactory;
+    Description {
+        day: 3,
+        title: "Lobby",
+        command: Config::command(),
+        modes: vec![
+            ModeDescription {
+                name: "two",
+                help: "turn on two batteries per line to make the largest joltage",
+            },
+            ModeDescription {
+                name: "twelve",
+                help: "turn on twelve batteries per line to make the largest joltage",
+            },
+        ],
+        grammar: grammar(),
+        examples: vec![
+            Example {
+                args: "--input data/day03/test_input.txt",
+                help: "solve part 1 for the example input",
+            },
+            Example {
+                args: "--mode twelve -v",
+                help: "solve part 2, logging the joltage chosen for each line",
+            },
+        ],
+        worked: EXAMPLES
+            .iter()
+            .map(|example| WorkedExample {
+                input: example.line,
+                mode: example.mode.name(),
+                answer: example.jolt.to_string(),
+            })
+            .collect(),
+        stars: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::fixtures_for_day;
+
+    fn read_test_input() -> AocResult<Vec<BatteryLine>> {
+        read_input_file("data/day03/test_input.txt")
+    }
+
+    fn read_test_input2() -> AocResult<Vec<BatteryLine>> {
+        read_input_file("data/day03/test_input2.txt")
+    }
+
+    #[test]
+    fn test_example() {
+        let line = BatteryLine {
+            line: "123456".to_string(),
+            digits: None,
+        };
+        let jolt = line.largest_number(2).expect("largest number");
+        assert_eq!(jolt, 56);
+    }
+
+    #[test]
+    fn test_per_line_digit_count() {
+        let lines =
+            parse_input("2:987\n3:12345\n818181911112111\n", BlankLines::Error).expect("lines");
+        assert_eq!(lines[0].digits, Some(2));
+        assert_eq!(lines[1].line, "12345");
+        assert_eq!(lines[2].digits, None);
+        assert_eq!(lines[1].to_string(), "3:12345");
+        assert_eq!(calc_total_jolt(&lines, Mode::Two).ok(), Some(98 + 345 + 92));
+        assert_eq!(
+            calc_total_jolt(&lines, Mode::Twelve).ok(),
+            Some(98 + 345 + 888911112111)
+        );
+        assert_eq!(
+            calc_total_jolt_with_tie_break(
+                &lines,
+                Mode::Two,
+                TieBreak::Earliest,
+                &DECIMAL,
+                &mut NoProgress
+            )
+            .expect("jolt"),
+            98 + 345 + 92
+        );
+        assert!(parse_battery_line("x:123").is_err());
+    }
+
+    #[test]
+    fn test_total_jolt_errors() {
+        let short = parse_input("987\n3:12\n", BlankLines::Error).expect("lines");
+        assert!(calc_total_jolt(&short, Mode::Two).is_err());
+        assert!(calc_total_jolt_parallel(&short, Mode::Two, TieBreak::Earliest, &DECIMAL).is_err());
+
+        let nines = format!("19:{}\n", "9".repeat(19));
+        let huge = parse_input(&nines.repeat(2), BlankLines::Error).expect("lines");
+        assert_eq!(
+            calc_total_jolt(&huge[..1], Mode::Two).ok(),
+            Some(9_999_999_999_999_999_999)
+        );
+        assert!(matches!(
+            calc_total_jolt(&huge, Mode::Two),
+            Err(AocError::Overflow(_))
+        ));
+        assert!(calc_total_jolt_parallel(&huge, Mode::Two, TieBreak::Earliest, &DECIMAL).is_err());
+        assert!(
+            calc_total_jolt_with_tie_break(
+                &huge,
+                Mode::Two,
+                TieBreak::Latest,
+                &DECIMAL,
+                &mut NoProgress
+            )
+            .is_err()
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_checked_jolt_overflow() {
+        let line = parse_battery_line(&format!("20:{}", "9".repeat(20))).expect("line");
+        for (name, select) in SELECTORS {
+            assert!(
+                matches!(select(&line, 20), Err(AocError::Overflow(_))),
+                "{}",
+                name
+            );
+        }
+        for tie_break in [TieBreak::Earliest, TieBreak::Latest] {
+            assert!(matches!(
+                line_jolt(&line, Mode::Two, tie_break, &DECIMAL),
+                Err(AocError::Overflow(_))
+            ));
+        }
+        assert!(calc_total_jolt(&[line], Mode::Two).is_err());
+    }
+
+    #[test]
+    fn test_battery_line_index() {
+        let line = parse_battery_line("818181911112111").expect("line");
+        let index = BatteryLineIndex::new(&line).expect("index");
+        assert_eq!(index.len(), 15);
+        assert_eq!(index.next_digit(0, 9), Some(6));
+        assert_eq!(index.next_digit(7, 2), Some(11));
+        assert_eq!(index.next_digit(12, 8), None);
+        assert_eq!(index.next_digit(15, 1), None);
+        assert_eq!(index.largest_number(2), Some(92));
+        assert_eq!(index.largest_number(12), Some(888911112111));
+        assert_eq!(index.largest_positions(2), Some(vec![6, 11]));
+        assert_eq!(index.smallest_number(3), Some(111));
+        assert_eq!(index.largest_number(16), None);
+        assert_eq!(index.largest_number(0), Some(0));
+        assert!(BatteryLineIndex::new(&parse_battery_line("12a").expect("line")).is_err());
+    }
+
+    #[test]
+    fn test_index_matches_largest_number() {
+        let batteries = read_test_input().expect("read test input");
+        for line in &batteries {
+            let index = BatteryLineIndex::new(line).expect("index");
+            for digits in [1, 2, 5, 12] {
+                assert_eq!(
+                    index.largest_number(digits as usize),
+                    line.largest_number(digits).ok()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tie_break_changes_windows_not_value() {
+        let index =
+            BatteryLineIndex::new(&parse_battery_line("9919").expect("line")).expect("index");
+        let earliest = index
+            .largest_steps_with(2, TieBreak::Earliest)
+            .expect("steps");
+        let latest = index
+            .largest_steps_with(2, TieBreak::Latest)
+            .expect("steps");
+        assert_eq!(
+            earliest,
+            vec![
+                SelectionStep {
+                    window: 0..=2,
+                    position: 0
+                },
+                SelectionStep {
+                    window: 1..=3,
+                    position: 1
+                },
+            ]
+        );
+        assert_eq!(
+            latest,
+            vec![
+                SelectionStep {
+                    window: 0..=2,
+                    position: 1
+                },
+                SelectionStep {
+                    window: 2..=3,
+                    position: 3
+                },
+            ]
+        );
+        assert_eq!(index.largest_number_with(2, TieBreak::Earliest), Some(99));
+        assert_eq!(index.largest_number_with(2, TieBreak::Latest), Some(99));
+
+        let index = BatteryLineIndex::new(&parse_battery_line("811111111111119").expect("line"))
+            .expect("index");
+        for count in [2, 12] {
+            assert_eq!(
+                index.largest_number_with(count, TieBreak::Latest),
+                index.largest_number_with(count, TieBreak::Earliest)
+            );
+        }
+        assert_ne!(
+            index.largest_steps_with(12, TieBreak::Latest),
+            index.largest_steps_with(12, TieBreak::Earliest)
+        );
+    }
+
+    #[test]
+    fn test_latest_tie_break_can_lose() {
+        let index =
+            BatteryLineIndex::new(&parse_battery_line("991").expect("line")).expect("index");
+        assert_eq!(index.largest_number_with(2, TieBreak::Earliest), Some(99));
+        assert_eq!(index.largest_number_with(2, TieBreak::Latest), Some(91));
+        assert_eq!(index.prev_digit(2, 9), Some(1));
+        assert_eq!(index.prev_digit(2, 5), None);
+    }
+
+    #[test]
+    fn test_empty_and_blank_lines() {
+        assert!(matches!(
+            parse_input("", BlankLines::Error),
+            Err(AocError::EmptyInput(_))
+        ));
+        assert!(matches!(
+            parse_input("\n\n", BlankLines::Skip),
+            Err(AocError::EmptyInput(_))
+        ));
+        match parse_input("123\n\n456\n", BlankLines::Error) {
+            Err(AocError::Parse { line, source, .. }) => {
+                assert_eq!(line, 2);
+                assert_eq!(source.to_string(), "Parse error: blank line");
+            }
+            other => panic!("expected a blank line error, got {:?}", other),
+        }
+        match parse_input("123\n\n456\n\n", BlankLines::Error) {
+            Err(AocError::Multiple(errors)) => assert_eq!(errors.len(), 2),
+            other => panic!("expected both blank lines reported, got {:?}", other),
+        }
+        let lines = parse_input("123\n  \n456\n", BlankLines::Skip).expect("lines");
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].largest_number(4).is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let line: BatteryLine = "4:818181911112111".parse().expect("line");
+        assert_eq!(line.digits, Some(4));
+        assert_eq!(line.to_string().parse::<BatteryLine>().ok(), Some(line));
+        assert!("x:123".parse::<BatteryLine>().is_err());
+        assert_eq!("twelve".parse::<Mode>().ok(), Some(Mode::Twelve));
+        assert!("ten".parse::<Mode>().is_err());
+    }
+
+    #[test]
+    fn test_concatenated() {
+        let line = parse_concatenated("987654321111111\n\n811111111111119\n").expect("line");
+        assert_eq!(line.line.len(), 30);
+        assert_eq!(line.digits, None);
+        assert_eq!(calc_total_jolt(&[line], Mode::Two).ok(), Some(99));
+        assert!(matches!(
+            parse_concatenated("12\n3:45\n"),
+            Err(AocError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_concatenated("\n\n"),
+            Err(AocError::EmptyInput(_))
+        ));
+
+        let mut state = 12345u64;
+        let text: String = (0..200_000)
+            .map(|i| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let digit = char::from(b'1' + (state >> 33) as u8 % 9);
+                if i % 100 == 99 {
+                    format!("{}\n", digit)
+                } else {
+                    digit.to_string()
+                }
+            })
+            .collect();
+        let line = parse_concatenated(&text).expect("line");
+        assert_eq!(line.line.len(), 200_000);
+        assert_eq!(
+            line_jolt(&line, Mode::Twelve, TieBreak::Earliest, &DECIMAL).expect("jolt"),
+            select_by_stack(&line, 12).expect("jolt")
+        );
+    }
+
+    #[test]
+    fn test_nines_prefix_fast_path() {
+        let lines =
+            parse_input("999123\n99\n2:9981\n919999\n9\n", BlankLines::Error).expect("lines");
+        let fast: Vec<Option<u64>> = lines
+            .iter()
+            .map(|line| nines_prefix_jolt(line, line.digits_or(2)))
+            .collect();
+        assert_eq!(fast, vec![Some(99), Some(99), Some(99), None, None]);
+        assert_eq!(
+            FastPathStats::count(&lines, Mode::Two, TieBreak::Earliest, &DECIMAL),
+            FastPathStats { lines: 5, hits: 3 }
+        );
+        assert_eq!(
+            FastPathStats::count(&lines, Mode::Two, TieBreak::Latest, &DECIMAL).hits,
+            0
+        );
+        let line = parse_battery_line("991").expect("line");
+        assert_eq!(
+            line_jolt(&line, Mode::Two, TieBreak::Earliest, &DECIMAL).ok(),
+            Some(99)
+        );
+        assert_eq!(
+            line_jolt(&line, Mode::Two, TieBreak::Latest, &DECIMAL).ok(),
+            Some(91)
+        );
+        let bad = parse_battery_line("99x").expect("line");
+        assert!(line_jolt(&bad, Mode::Two, TieBreak::Earliest, &DECIMAL).is_err());
+        assert!(select_with_fast_path(&bad, 2).is_err());
+    }
+
+    #[test]
+    fn test_alphabets() {
+        let hex: Alphabet = "0123456789ABCDEF".parse().expect("hex");
+        let reversed: Alphabet = "9876543210".parse().expect("reversed");
+        let jolt = |line: &str, alphabet: &Alphabet, tie_break| {
+            let line = parse_battery_line(line).expect("line");
+            line_jolt(&line, Mode::Two, tie_break, alphabet).ok()
+        };
+        assert_eq!(jolt("1F2A3", &hex, TieBreak::Earliest), Some(0xFA));
+        assert_eq!(jolt("1F2A3", &hex, TieBreak::Latest), Some(0xFA));
+        assert_eq!(jolt("FF1", &hex, TieBreak::Earliest), Some(0xFF));
+        assert_eq!(
+            jolt("987654321111111", &hex, TieBreak::Earliest),
+            Some(0x98)
+        );
+        assert_eq!(jolt("1F2G3", &hex, TieBreak::Earliest), None);
+        // '0' is worth 9 and '9' nothing, so the line's 1s are the best.
+        assert_eq!(
+            jolt("987654321111111", &reversed, TieBreak::Earliest),
+            Some(88)
+        );
+        assert_eq!(jolt("5005", &reversed, TieBreak::Earliest), Some(99));
+
+        let lines = parse_input("FF1\n1F2A3\n", BlankLines::Error).expect("lines");
+        assert_eq!(
+            calc_total_jolt_with_tie_break(
+                &lines,
+                Mode::Two,
+                TieBreak::Earliest,
+                &hex,
+                &mut NoProgress
+            )
+            .ok(),
+            Some(0xFF + 0xFA)
+        );
+        assert_eq!(
+            calc_total_jolt_parallel(&lines, Mode::Two, TieBreak::Earliest, &hex).ok(),
+            Some(0xFF + 0xFA)
+        );
+        assert_eq!(
+            FastPathStats::count(&lines, Mode::Two, TieBreak::Earliest, &hex).hits,
+            1
+        );
+        let index = BatteryLineIndex::with_alphabet(&lines[1], &hex).expect("index");
+        assert_eq!(index.next_digit(0, 0xA), Some(3));
+        assert_eq!(index.next_digit(0, 0x10), None);
+        assert_eq!(index.smallest_number(2), Some(0x12));
+    }
+
+    #[test]
+    fn test_examples() {
+        for example in EXAMPLES {
+            let line = parse_battery_line(example.line).expect("line");
+            let digits = example.mode.digits();
+            for (name, selector) in SELECTORS {
+                assert_eq!(
+                    selector(&line, digits).ok(),
+                    Some(example.jolt),
+                    "{} selector on {} in mode {}",
+                    name,
+                    example.line,
+                    example.mode.name()
+                );
+            }
+            assert_eq!(
+                line_jolt(&line, example.mode, TieBreak::Earliest, &DECIMAL).ok(),
+                Some(example.jolt)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let fixtures = fixtures_for_day(3).expect("fixture manifest");
+        assert!(!fixtures.is_empty());
+        for fixture in fixtures {
+            let lines = read_input_file(&fixture.input).expect("fixture input");
+            let digits = Mode::from(fixture.mode.as_str()).digits();
+            for (name, selector) in SELECTORS {
+                let total: u64 = lines
+                    .iter()
+                    .map(|line| selector(line, digits).expect("selection"))
+                    .sum();
+                assert_eq!(
+                    total.to_string(),
+                    fixture.expected,
+                    "{} selector on {} in mode {}",
+                    name,
+                    fixture.input,
+                    fixture.mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_test_input() {
+        let batteries = read_test_input().expect("read test input");
+        let total_jolt = calc_total_jolt(&batteries, Mode::Two).expect("total jolt");
+        assert_eq!(total_jolt, 357);
+    }
+
+    #[test]
+    fn test_test_input2() {
+        let batteries = read_test_input2().expect("read test input 2");
+        let total_jolt = calc_total_jolt(&batteries, Mode::Two).expect("total jolt");
+        assert_eq!(total_jolt, 77 + 98 + 66 + 66);
+    }
+
+    #[test]
+    fn test_example_12() {
+        let batteries = read_test_input().expect("read test input");
+        let total_jolt = calc_total_jolt(&batteries, Mode::Twelve).expect("total jolt");
+        assert_eq!(total_jolt, 3121910778619);
+    }
+
+    #[test]
+    fn test_example_12_2() {
+        let batteries = read_test_input2().expect("read test input 2");
+        let total_jolt = calc_total_jolt(&batteries, Mode::Twelve).expect("total jolt");
+        assert_eq!(total_jolt, 3084441169181);
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_values() {
+        use clap::Parser;
+        let config = Config::parse_from(["day03", "--blank-lines", "skip"]);
+        assert_eq!(config.blank_lines, BlankLines::Skip);
+        assert!(Config::try_parse_from(["day03", "--blank-lines", "ignore"]).is_err());
+        let config = Config::parse_from(["day03", "--tie-break", "latest"]);
+        assert_eq!(config.tie_break, TieBreak::Latest);
+        assert!(Config::try_parse_from(["day03", "--tie-break", "last"]).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use super::BatteryLine;
+use crate::error::AocError;
+use crate::math;
+use crate::result::AocResult;
+
+/// The puzzle's batteries, `0` to `9`.
+pub const DECIMAL: Alphabet = Alphabet {
+    symbols: Cow::Borrowed(&['0', '1', '2', '3', '4', '5', '6', '7', '8', '9']),
+};
+
+/// Most symbols an alphabet may have, so a battery's value fits in a `u8`.
+pub const MAX_SYMBOLS: usize = 256;
+
+/// The symbols batteries are written in, from least to most valuable. A
+/// battery is worth its symbol's position, and the batteries turned on
+/// are read as a number in base `radix`. `0123456789ABCDEF` gives hex
+/// batteries and `9876543210` turns the puzzle's ordering around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: Cow<'static, [char]>,
+}
+
+impl Alphabet {
+    /// How many symbols there are, which is also the number base.
+    pub fn radix(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// The most valuable symbol.
+    pub fn top(&self) -> char {
+        self.symbols[self.symbols.len() - 1]
+    }
+
+    pub fn is_decimal(&self) -> bool {
+        *self == DECIMAL
+    }
+
+    /// The value of the battery `symbol`, if it is in the alphabet.
+    pub fn value(&self, symbol: char) -> Option<u8> {
+        self.symbols
+            .iter()
+            .position(|&s| s == symbol)
+            .map(|value| value as u8)
+    }
+
+    /// The value of every battery in `line`.
+    pub fn values(&self, line: &BatteryLine) -> AocResult<Vec<u8>> {
+        line.line
+            .chars()
+            .map(|c| {
+                self.value(c).ok_or_else(|| {
+                    AocError::ParseError(format!("invalid battery '{}' in {}", c, line))
+                })
+            })
+            .collect()
+    }
+
+    /// `num` with a battery worth `value` appended.
+    pub fn push(&self, num: u64, value: u8) -> AocResult<u64> {
+        math::push_digit_in(num, value as u64, self.radix() as u64, "jolt")
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = AocError;
+
+    /// At least two distinct symbols, none of them whitespace or the `:`
+    /// that starts a line's battery count.
+    fn from_str(s: &str) -> AocResult<Self> {
+        let symbols: Vec<char> = s.chars().collect();
+        let invalid = |why: &str| AocError::InvalidArgument(format!("alphabet '{}' {}", s, why));
+        if symbols.len() < 2 {
+            return Err(invalid("needs at least two symbols"));
+        }
+        if symbols.len() > MAX_SYMBOLS {
+            return Err(invalid(&format!("has more than {} symbols", MAX_SYMBOLS)));
+        }
+        if symbols.iter().any(|&c| c.is_whitespace() || c == ':') {
+            return Err(invalid("can't use whitespace or ':'"));
+        }
+        if symbols
+            .iter()
+            .enumerate()
+            .any(|(i, c)| symbols[..i].contains(c))
+        {
+            return Err(invalid("repeats a symbol"));
+        }
+        Ok(Alphabet {
+            symbols: Cow::Owned(symbols),
+        })
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.symbols.iter().try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alphabet() {
+        assert!(DECIMAL.is_decimal());
+        assert_eq!("0123456789".parse::<Alphabet>().expect("decimal"), DECIMAL);
+        let hex: Alphabet = "0123456789ABCDEF".parse().expect("hex");
+        assert_eq!((hex.radix(), hex.top()), (16, 'F'));
+        assert_eq!(hex.value('B'), Some(11));
+        assert_eq!(hex.value('b'), None);
+        assert_eq!(hex.push(0xA, 0xF).ok(), Some(0xAF));
+        assert_eq!(hex.to_string(), "0123456789ABCDEF");
+        let reversed: Alphabet = "9876543210".parse().expect("reversed");
+        assert_eq!(reversed.value('9'), Some(0));
+        assert!(!reversed.is_decimal());
+        for bad in ["", "1", "0120", "01 2", "01:"] {
+            assert!(bad.parse::<Alphabet>().is_err(), "{}", bad);
+        }
+    }
+}
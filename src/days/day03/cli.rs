@@ -0,0 +1,147 @@
+use super::columns::{self, CellJolt};
+use super::{
+    Config, FastPathStats, calc_total_jolt_parallel, calc_total_jolt_with_tie_break, html,
+    parse_concatenated, parse_input,
+};
+use crate::artifacts;
+use crate::crash;
+use crate::error::AocError;
+use crate::input;
+use crate::json;
+use crate::logging;
+use crate::result::AocResult;
+use crate::run::{self, OutputFormat, Part, Phases, RunReport};
+use crate::sniff;
+use tracing::{info, info_span};
+
+/// Runs the day 3 command line with `config`, printing to stdout. Shared by
+/// the `day03` binary and `aoc day03`. Returns false if `--bench`
+/// regressed against `--compare-baseline`.
+pub fn run(config: Config) -> AocResult<bool> {
+    let common = &config.common;
+    logging::init(common.verbosity, common.log_format);
+    let input = common.input_path(3);
+    crash::install(3, &input);
+    let _span = info_span!("day", day = 3).entered();
+    if let Some(part) = common.part {
+        run::run_part(3, &input, part, common)?;
+        return Ok(true);
+    }
+    if common.bench {
+        return run::run_bench(3, &input, Part::Both, common);
+    }
+    let mut run_dir = common.run_dir(3);
+    let mut phases = Phases::new();
+    let content = phases.time(
+        "read",
+        || input::read_to_string(&input),
+        |r| r.as_ref().map_or(0, |c| c.len()),
+    )?;
+    crash::set_input(&content);
+    if config.alphabet.is_decimal() {
+        sniff::check(3, &content).map_err(|e| e.in_file(&input))?;
+    }
+    let (lines, cells) = phases
+        .time(
+            "parse",
+            || {
+                if config.columns {
+                    let cells = columns::parse_cells(&content, config.blank_lines)?;
+                    Ok((cells.iter().map(|cell| cell.line.clone()).collect(), cells))
+                } else if config.concat {
+                    Ok((vec![parse_concatenated(&content)?], vec![]))
+                } else {
+                    Ok((parse_input(&content, config.blank_lines)?, vec![]))
+                }
+            },
+            |r: &AocResult<(Vec<_>, _)>| r.as_ref().map_or(0, |(l, _)| l.len()),
+        )
+        .map_err(|e| e.in_file(&input))?;
+    if let Some(path) = &common.dump_parsed {
+        let path = artifacts::place(&mut run_dir, "parsed", path)?;
+        json::dump(&path, &lines)?;
+    }
+    let mut cell_jolts: Vec<CellJolt> = vec![];
+    let total_jolt = phases
+        .time(
+            "solve",
+            || {
+                if config.columns {
+                    cell_jolts = if config.parallel {
+                        columns::cell_jolts_parallel(
+                            &cells,
+                            config.mode,
+                            config.tie_break,
+                            &config.alphabet,
+                        )
+                    } else {
+                        columns::cell_jolts(&cells, config.mode, config.tie_break, &config.alphabet)
+                    }?;
+                    return Ok(cell_jolts.iter().map(|cell| cell.jolt).sum());
+                }
+                if config.parallel {
+                    calc_total_jolt_parallel(
+                        &lines,
+                        config.mode,
+                        config.tie_break,
+                        &config.alphabet,
+                    )
+                } else {
+                    let mut progress = common.progress("lines");
+                    calc_total_jolt_with_tie_break(
+                        &lines,
+                        config.mode,
+                        config.tie_break,
+                        &config.alphabet,
+                        progress.as_mut(),
+                    )
+                }
+            },
+            |_| lines.len(),
+        )
+        .map_err(|e| e.in_file(&input))?;
+    if !config.columns {
+        info!(
+            "{}",
+            FastPathStats::count(&lines, config.mode, config.tie_break, &config.alphabet)
+        );
+    }
+    if let Some(path) = &config.html {
+        let path = artifacts::place(&mut run_dir, html::ARTIFACT, path)?;
+        let page = html::render(&lines, config.mode, &config.alphabet)?;
+        std::fs::write(&path, page).map_err(|e| AocError::io(&path, e))?;
+    }
+    let answer = phases.time("report", || total_jolt.to_string(), |_| 1);
+    let mut settings = vec![
+        ("tie_break", config.tie_break.name().to_string()),
+        ("columns", config.columns.to_string()),
+        ("concat", config.concat.to_string()),
+    ];
+    if !config.alphabet.is_decimal() {
+        settings.push(("alphabet", config.alphabet.to_string()));
+    }
+    let report = RunReport {
+        day: 3,
+        part: config.mode.part(),
+        mode: config.mode.name().to_string(),
+        settings,
+        metrics: vec![],
+        answer,
+        phases: phases.into_vec(),
+    };
+    match common.output {
+        OutputFormat::Text => {
+            for cell in &cell_jolts {
+                println!("{}", cell);
+            }
+            println!(
+                "Total jolt from all battery lines: {}",
+                common.group_digits.apply(&report.answer)
+            );
+            common.print_timings(&report.timings());
+        }
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
+    artifacts::finish(run_dir, &report)?;
+    Ok(true)
+}
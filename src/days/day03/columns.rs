@@ -0,0 +1,175 @@
+use std::fmt;
+
+use super::alphabet::Alphabet;
+use super::{BatteryLine, BlankLines, Mode, TieBreak, line_jolt, parse_battery_line};
+use crate::error::AocError;
+use crate::par;
+use crate::result::AocResult;
+
+/// One battery string of a row holding several, numbered from 1 as in the
+/// input file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub row: usize,
+    pub column: usize,
+    pub line: BatteryLine,
+}
+
+/// The jolt of one cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellJolt {
+    pub row: usize,
+    pub column: usize,
+    pub jolt: u64,
+}
+
+impl fmt::Display for CellJolt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Row {}, column {}: {}", self.row, self.column, self.jolt)
+    }
+}
+
+/// Parses rows of whitespace-separated battery strings, each with its own
+/// optional `K:` prefix, into cells in reading order. Blank lines are
+/// handled as in `parse_input`.
+pub fn parse_cells(input: &str, blank_lines: BlankLines) -> AocResult<Vec<Cell>> {
+    let rows = AocError::collect_all(
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| blank_lines == BlankLines::Error || !line.trim().is_empty())
+            .map(|(number, line)| {
+                let row = number + 1;
+                if line.trim().is_empty() {
+                    return Err(AocError::ParseError("blank line".to_string()).at_line(row, line));
+                }
+                line.split_whitespace()
+                    .enumerate()
+                    .map(|(column, batteries)| {
+                        let line = parse_battery_line(batteries).map_err(|e| match e {
+                            AocError::ParseError(message) => AocError::ParseError(format!(
+                                "row {} column {}: {}",
+                                row,
+                                column + 1,
+                                message
+                            )),
+                            e => e,
+                        })?;
+                        Ok(Cell {
+                            row,
+                            column: column + 1,
+                            line,
+                        })
+                    })
+                    .collect::<AocResult<Vec<Cell>>>()
+            }),
+    )?;
+    let cells: Vec<Cell> = rows.into_iter().flatten().collect();
+    if cells.is_empty() {
+        return Err(AocError::EmptyInput("no battery lines".to_string()));
+    }
+    Ok(cells)
+}
+
+/// Each cell solved on its own, in the order of `cells`.
+pub fn cell_jolts(
+    cells: &[Cell],
+    mode: Mode,
+    tie_break: TieBreak,
+    alphabet: &Alphabet,
+) -> AocResult<Vec<CellJolt>> {
+    cells
+        .iter()
+        .map(|cell| cell_jolt(cell, mode, tie_break, alphabet))
+        .collect()
+}
+
+/// `cell_jolts` with the cells solved on several threads.
+pub fn cell_jolts_parallel(
+    cells: &[Cell],
+    mode: Mode,
+    tie_break: TieBreak,
+    alphabet: &Alphabet,
+) -> AocResult<Vec<CellJolt>> {
+    par::map_ordered(cells, |cell| cell_jolt(cell, mode, tie_break, alphabet))
+        .into_iter()
+        .collect()
+}
+
+fn cell_jolt(
+    cell: &Cell,
+    mode: Mode,
+    tie_break: TieBreak,
+    alphabet: &Alphabet,
+) -> AocResult<CellJolt> {
+    Ok(CellJolt {
+        row: cell.row,
+        column: cell.column,
+        jolt: line_jolt(&cell.line, mode, tie_break, alphabet)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day03::alphabet::DECIMAL;
+    use crate::days::day03::{calc_total_jolt, parse_input};
+
+    const ROWS: &str = "987654321111111 811111111111119\n\
+                        234234234234278\t818181911112111  3:12345\n";
+
+    #[test]
+    fn test_cell_jolts() {
+        let cells = parse_cells(ROWS, BlankLines::Error).expect("cells");
+        assert_eq!(cells.len(), 5);
+        assert_eq!((cells[3].row, cells[3].column), (2, 2));
+        assert_eq!(cells[4].line.digits, Some(3));
+
+        let jolts = cell_jolts(&cells, Mode::Two, TieBreak::Earliest, &DECIMAL).expect("jolts");
+        let listed: Vec<String> = jolts.iter().map(CellJolt::to_string).collect();
+        assert_eq!(
+            listed,
+            [
+                "Row 1, column 1: 98",
+                "Row 1, column 2: 89",
+                "Row 2, column 1: 78",
+                "Row 2, column 2: 92",
+                "Row 2, column 3: 345",
+            ]
+        );
+        assert_eq!(
+            cell_jolts_parallel(&cells, Mode::Two, TieBreak::Earliest, &DECIMAL).expect("jolts"),
+            jolts
+        );
+
+        let one_per_row = ROWS
+            .split_whitespace()
+            .take(4)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = parse_input(&one_per_row, BlankLines::Error).expect("lines");
+        let total: u64 = jolts[..4].iter().map(|cell| cell.jolt).sum();
+        assert_eq!(
+            total,
+            calc_total_jolt(&lines, Mode::Two).expect("total jolt")
+        );
+    }
+
+    #[test]
+    fn test_parse_cells_errors() {
+        let error = parse_cells("12 x:34\n", BlankLines::Error).expect_err("bad count");
+        assert!(error.to_string().contains("row 1 column 2"));
+        assert!(parse_cells("12\n\n34\n", BlankLines::Error).is_err());
+        assert_eq!(
+            parse_cells("12\n\n34 56\n", BlankLines::Skip)
+                .expect("cells")
+                .last()
+                .map(|cell| (cell.row, cell.column)),
+            Some((3, 2))
+        );
+        assert!(matches!(
+            parse_cells("\n", BlankLines::Skip),
+            Err(AocError::EmptyInput(_))
+        ));
+    }
+}
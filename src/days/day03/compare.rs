@@ -0,0 +1,139 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::{BatteryLine, SELECTORS};
+use crate::fmt::Elapsed;
+use crate::result::AocResult;
+
+/// One selector's total jolt for one digit count, and how long it took.
+#[derive(Debug)]
+pub struct Cell {
+    pub answer: AocResult<u64>,
+    pub duration: Duration,
+}
+
+/// Every selector in `SELECTORS` run on the same lines for each digit
+/// count: a row per selector, a column per digit count.
+#[derive(Debug)]
+pub struct Comparison {
+    pub digits: Vec<u32>,
+    pub rows: Vec<(&'static str, Vec<Cell>)>,
+}
+
+impl Comparison {
+    pub fn run(lines: &[BatteryLine], digits: &[u32]) -> Self {
+        let rows = SELECTORS
+            .iter()
+            .map(|&(name, selector)| {
+                let cells = digits
+                    .iter()
+                    .map(|&digits| {
+                        let start = Instant::now();
+                        let answer = lines.iter().map(|line| selector(line, digits)).sum();
+                        Cell {
+                            answer,
+                            duration: start.elapsed(),
+                        }
+                    })
+                    .collect();
+                (name, cells)
+            })
+            .collect();
+        Comparison {
+            digits: digits.to_vec(),
+            rows,
+        }
+    }
+
+    /// A description of each column where the selectors failed or gave
+    /// different answers.
+    pub fn disagreements(&self) -> Vec<String> {
+        let mut found = Vec::new();
+        for (column, digits) in self.digits.iter().enumerate() {
+            let answers: Vec<(&str, Result<u64, String>)> = self
+                .rows
+                .iter()
+                .map(|(name, cells)| {
+                    let answer = cells[column].answer.as_ref();
+                    (*name, answer.copied().map_err(|e| e.to_string()))
+                })
+                .collect();
+            let Some((_, first)) = answers.first() else {
+                continue;
+            };
+            if answers
+                .iter()
+                .any(|(_, answer)| answer != first || answer.is_err())
+            {
+                let listed: Vec<String> = answers
+                    .iter()
+                    .map(|(name, answer)| match answer {
+                        Ok(answer) => format!("{} gave {}", name, answer),
+                        Err(e) => format!("{} failed: {}", name, e),
+                    })
+                    .collect();
+                found.push(format!("{} digits: {}", digits, listed.join(", ")));
+            }
+        }
+        found
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<16}", "selector")?;
+        for digits in &self.digits {
+            write!(f, " {:>30}", format!("{} digits", digits))?;
+        }
+        writeln!(f)?;
+        for (name, cells) in &self.rows {
+            write!(f, "{:<16}", name)?;
+            for cell in cells {
+                let answer = match &cell.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(_) => "error".to_string(),
+                };
+                write!(
+                    f,
+                    " {:>30}",
+                    format!("{} ({})", answer, Elapsed(cell.duration))
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day03::{BlankLines, parse_input};
+
+    #[test]
+    fn test_comparison() {
+        let lines = parse_input(
+            "987654321111111\n811111111111119\n234234234234278\n818181911112111\n",
+            BlankLines::Error,
+        )
+        .expect("lines");
+        let comparison = Comparison::run(&lines, &[2, 12]);
+        assert_eq!(comparison.rows.len(), SELECTORS.len());
+        for (name, cells) in &comparison.rows {
+            assert_eq!(cells[0].answer.as_ref().ok(), Some(&357), "{}", name);
+            assert_eq!(
+                cells[1].answer.as_ref().ok(),
+                Some(&3121910778619),
+                "{}",
+                name
+            );
+        }
+        assert!(comparison.disagreements().is_empty());
+        let text = comparison.to_string();
+        assert!(text.starts_with("selector"));
+        assert!(text.contains("\nmonotonic-stack "));
+
+        let short = Comparison::run(&lines, &[16]);
+        assert_eq!(short.disagreements().len(), 1);
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::alphabet::Alphabet;
+use super::{BatteryLine, BatteryLineIndex, Mode};
+use crate::artifacts;
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// Kind of the pages `--html` writes into a run directory.
+pub const ARTIFACT: &str = "html";
+
+/// Deletes the pages written into the run directories under `base`, or
+/// only lists them if `dry_run`, returning their paths.
+pub fn clean(base: &Path, dry_run: bool) -> io::Result<Vec<PathBuf>> {
+    artifacts::clean_artifacts(base, ARTIFACT, dry_run)
+}
+
+const STYLE: &str = "body { font-family: monospace; }\n\
+                     .line { white-space: nowrap; margin: 2px 0; }\n\
+                     .pick { background: #fd5; font-weight: bold; }\n\
+                     .jolt { color: #777; margin-left: 1em; }\n";
+
+/// Renders `lines` as a standalone HTML page with the batteries `mode`
+/// selects highlighted, valuing batteries by `alphabet`. Hovering over a selected battery shows the window
+/// of positions searched for it.
+pub fn render(lines: &[BatteryLine], mode: Mode, alphabet: &Alphabet) -> AocResult<String> {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Day 3 ({} batteries)</title>\n<style>\n{}</style>\n</head>\n<body>\n",
+        mode.name(),
+        STYLE
+    );
+    for (number, line) in lines.iter().enumerate() {
+        let digits = line.digits_or(mode.digits()) as usize;
+        let index = BatteryLineIndex::with_alphabet(line, alphabet)?;
+        let steps = index.largest_steps(digits).ok_or_else(|| {
+            AocError::ParseError(format!(
+                "line {} has fewer than {} batteries: {}",
+                number + 1,
+                digits,
+                line
+            ))
+        })?;
+        let mut steps = steps.iter().enumerate().peekable();
+        out.push_str("<div class=\"line\">");
+        for (position, battery) in line.line.chars().enumerate() {
+            match steps.next_if(|(_, step)| step.position == position) {
+                Some((i, step)) => {
+                    let _ = write!(
+                        out,
+                        "<span class=\"pick\" title=\"step {}: searched {}-{}, picked {} at {}\">{}</span>",
+                        i + 1,
+                        step.window.start(),
+                        step.window.end(),
+                        battery,
+                        position,
+                        battery
+                    );
+                }
+                None => out.push(battery),
+            }
+        }
+        let jolt = index.largest_number(digits).unwrap_or_default();
+        let _ = writeln!(out, "<span class=\"jolt\">{}</span></div>", jolt);
+    }
+    out.push_str("</body>\n</html>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day03::alphabet::DECIMAL;
+    use crate::days::day03::parse_battery_line;
+
+    #[test]
+    fn test_render() {
+        let lines = vec![parse_battery_line("818181911112111").expect("line")];
+        let html = render(&lines, Mode::Two, &DECIMAL).expect("html");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<div class=\"line\">818181\
+             <span class=\"pick\" title=\"step 1: searched 0-13, picked 9 at 6\">9</span>\
+             1111\
+             <span class=\"pick\" title=\"step 2: searched 7-14, picked 2 at 11\">2</span>\
+             111<span class=\"jolt\">92</span></div>"
+        ));
+        let short = vec![parse_battery_line("12345").expect("line")];
+        assert!(render(&short, Mode::Twelve, &DECIMAL).is_err());
+    }
+}
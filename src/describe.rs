@@ -0,0 +1,264 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::input;
+use crate::solver;
+use crate::submit::Stars;
+
+/// A small grammar vocabulary used by each day to describe its input format
+/// next to the nom parsers that implement it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grammar {
+    Literal(&'static str),
+    Digits,
+    Text,
+    Rule(&'static str),
+    Seq(Vec<Grammar>),
+    Alt(Vec<Grammar>),
+    Separated(Box<Grammar>, Box<Grammar>),
+    Lines(Box<Grammar>),
+}
+
+impl Grammar {
+    pub fn lit(s: &'static str) -> Self {
+        Grammar::Literal(s)
+    }
+
+    pub fn rule(name: &'static str) -> Self {
+        Grammar::Rule(name)
+    }
+
+    pub fn seq(items: impl IntoIterator<Item = Grammar>) -> Self {
+        Grammar::Seq(items.into_iter().collect())
+    }
+
+    pub fn alt(items: impl IntoIterator<Item = Grammar>) -> Self {
+        Grammar::Alt(items.into_iter().collect())
+    }
+
+    pub fn separated(item: Grammar, separator: Grammar) -> Self {
+        Grammar::Separated(Box::new(item), Box::new(separator))
+    }
+
+    pub fn lines(item: Grammar) -> Self {
+        Grammar::Lines(Box::new(item))
+    }
+}
+
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Grammar::Literal(s) => write!(f, "\"{}\"", s),
+            Grammar::Digits => write!(f, "digit+"),
+            Grammar::Text => write!(f, "char+"),
+            Grammar::Rule(name) => write!(f, "{}", name),
+            Grammar::Seq(items) => {
+                let parts: Vec<String> = items
+                    .iter()
+                    .map(|g| match g {
+                        Grammar::Alt(_) => format!("({})", g),
+                        _ => g.to_string(),
+                    })
+                    .collect();
+                write!(f, "{}", parts.join(" "))
+            }
+            Grammar::Alt(items) => {
+                let parts: Vec<String> = items.iter().map(|g| g.to_string()).collect();
+                write!(f, "{}", parts.join(" | "))
+            }
+            Grammar::Separated(item, separator) => {
+                write!(f, "{} ({} {})*", item, separator, item)
+            }
+            Grammar::Lines(item) => write!(f, "({} newline)*", item),
+        }
+    }
+}
+
+/// Named grammar productions, printed in order as `name ::= grammar`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules(pub Vec<(&'static str, Grammar)>);
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.0.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        for (name, grammar) in &self.0 {
+            writeln!(f, "    {:width$} ::= {}", name, grammar, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeDescription {
+    pub name: &'static str,
+    pub help: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+/// A small input and the answer it gives in `mode`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkedExample {
+    pub input: &'static str,
+    pub mode: &'static str,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Description {
+    pub day: u32,
+    pub title: &'static str,
+    pub command: clap::Command,
+    pub modes: Vec<ModeDescription>,
+    pub grammar: Rules,
+    pub examples: Vec<Example>,
+    pub worked: Vec<WorkedExample>,
+    /// The parts solved on the site, if the submission history was read.
+    pub stars: Option<Stars>,
+}
+
+impl Description {
+    fn binary(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Day {}: {}", self.day, self.title)?;
+        writeln!(f)?;
+        writeln!(f, "Puzzle: {}", input::puzzle_url(self.day))?;
+        if let Some(stars) = self.stars {
+            writeln!(f, "Stars: {} of 2 ({})", stars.count(), stars)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "## Modes")?;
+        writeln!(f)?;
+        for mode in &self.modes {
+            writeln!(f, "- `{}`: {}", mode.name, mode.help)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "## Flags")?;
+        writeln!(f)?;
+        for arg in self.command.get_arguments() {
+            let mut names = Vec::new();
+            if let Some(short) = arg.get_short() {
+                names.push(format!("-{}", short));
+            }
+            if let Some(long) = arg.get_long() {
+                names.push(format!("--{}", long));
+            }
+            write!(f, "- `{}`", names.join(", "))?;
+            if let Some(help) = arg.get_help() {
+                write!(f, ": {}", help)?;
+            }
+            let defaults: Vec<String> = arg
+                .get_default_values()
+                .iter()
+                .map(|v| v.to_string_lossy().into_owned())
+                .collect();
+            if !defaults.is_empty() {
+                write!(f, " (default: {})", defaults.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "## Input format")?;
+        writeln!(f)?;
+        write!(f, "{}", self.grammar)?;
+        writeln!(f)?;
+
+        writeln!(f, "## Examples")?;
+        writeln!(f)?;
+        for example in &self.examples {
+            writeln!(f, "    {} {}", self.binary(), example.args)?;
+            writeln!(f, "        {}", example.help)?;
+        }
+
+        if !self.worked.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "## Worked examples")?;
+            writeln!(f)?;
+            let width = self.worked.iter().map(|w| w.input.len()).max().unwrap_or(0);
+            for worked in &self.worked {
+                writeln!(
+                    f,
+                    "    {:width$}  {:6} -> {}",
+                    worked.input,
+                    worked.mode,
+                    worked.answer,
+                    width = width
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The description of `day`, with its stars from the submissions under
+/// `data/`.
+pub fn describe_day(day: u32) -> Option<Description> {
+    let mut description = (solver::command(day)?.describe)();
+    description.stars = Stars::load(Path::new("data"), day).ok();
+    Some(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grammar_display() {
+        let grammar = Grammar::seq([
+            Grammar::alt([Grammar::lit("L"), Grammar::lit("R")]),
+            Grammar::Digits,
+        ]);
+        assert_eq!(grammar.to_string(), "(\"L\" | \"R\") digit+");
+    }
+
+    #[test]
+    fn test_separated_display() {
+        let grammar = Grammar::separated(Grammar::rule("range"), Grammar::lit(","));
+        assert_eq!(grammar.to_string(), "range (\",\" range)*");
+    }
+
+    #[test]
+    fn test_rules_display() {
+        let rules = Rules(vec![
+            ("input", Grammar::lines(Grammar::rule("line"))),
+            ("line", Grammar::Digits),
+        ]);
+        assert_eq!(
+            rules.to_string(),
+            "    input ::= (line newline)*\n    line  ::= digit+\n"
+        );
+    }
+
+    #[test]
+    fn test_describe_known_days() {
+        for day in solver::days() {
+            let description = describe_day(day).expect("description");
+            assert_eq!(description.day, day);
+            assert_eq!(
+                Some(description.title),
+                solver::command(day).map(|command| command.title)
+            );
+            let text = description.to_string();
+            assert!(text.contains("## Flags"));
+            assert!(text.contains("--input"));
+            let url = format!("Puzzle: https://adventofcode.com/2025/day/{}\n", day);
+            assert!(text.contains(&url));
+        }
+        assert!(describe_day(26).is_none());
+        let day03 = describe_day(3).expect("day 3").to_string();
+        assert!(day03.contains("## Worked examples"));
+        assert!(day03.contains("    123456           two    -> 56\n"));
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,4 +12,336 @@ pub enum AocError {
 
     #[error("IO error: {0}")]
     IoError(String),
+
+    #[error("Empty input: {0}")]
+    EmptyInput(String),
+
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+
+    #[error("Overflow: {0}")]
+    Overflow(String),
+
+    #[error("IO error: {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A failure to parse `input`, which is line `line` of the text. Both
+    /// `line` and `column` count from 1; `column` is 1 when the whole line
+    /// is at fault.
+    #[error("Parse error at line {line}, column {column} of '{input}': {source}")]
+    Parse {
+        line: usize,
+        column: usize,
+        input: String,
+        #[source]
+        source: Box<AocError>,
+    },
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// `source`, raised while working on the contents of the file at `path`.
+    #[error("{}: {source}", path.display())]
+    InFile {
+        path: PathBuf,
+        #[source]
+        source: Box<AocError>,
+    },
+
+    #[error("{}", list_errors(.0))]
+    Multiple(Vec<AocError>),
+}
+
+/// Exit status for a run that failed to read a file.
+pub const EXIT_IO: i32 = 3;
+/// Exit status for a run whose input did not parse.
+pub const EXIT_PARSE: i32 = 4;
+/// Exit status for a run given arguments or input it cannot handle.
+pub const EXIT_INVALID: i32 = 5;
+/// Exit status for a run whose answer overflowed.
+pub const EXIT_OVERFLOW: i32 = 6;
+
+/// How many errors `AocError::Multiple` lists before summarising the rest.
+pub const MAX_LISTED_ERRORS: usize = 10;
+
+fn list_errors(errors: &[AocError]) -> String {
+    let mut out = format!("{} errors:", errors.len());
+    for error in errors.iter().take(MAX_LISTED_ERRORS) {
+        out.push_str(&format!("\n  {}", error));
+    }
+    if errors.len() > MAX_LISTED_ERRORS {
+        out.push_str(&format!(
+            "\n  ...and {} more",
+            errors.len() - MAX_LISTED_ERRORS
+        ));
+    }
+    out
+}
+
+impl AocError {
+    /// An IO failure on the file at `path`.
+    pub fn io(path: impl AsRef<Path>, source: std::io::Error) -> Self {
+        AocError::Io {
+            path: path.as_ref().to_path_buf(),
+            source,
+        }
+    }
+
+    /// `source` at `offset` bytes into `text`, placed by line and column.
+    pub fn parse_at(text: &str, offset: usize, source: AocError) -> Self {
+        let offset = offset.min(text.len());
+        let before = &text[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+        AocError::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[start..].chars().count() + 1,
+            input: text[start..end].to_string(),
+            source: Box::new(source),
+        }
+    }
+
+    /// This error, from parsing `input` on its own, as on line `line` of a
+    /// longer text. A column already found is kept.
+    pub fn at_line(self, line: usize, input: &str) -> Self {
+        let (column, source) = match self {
+            AocError::Parse { column, source, .. } => (column, source),
+            error => (1, Box::new(error)),
+        };
+        AocError::Parse {
+            line,
+            column,
+            input: input.to_string(),
+            source,
+        }
+    }
+
+    /// This error, raised while working on the contents of the file at
+    /// `path`. Errors that already name a file are kept as they are.
+    pub fn in_file(self, path: impl AsRef<Path>) -> Self {
+        match self {
+            AocError::Io { .. } | AocError::InFile { .. } => self,
+            error => AocError::InFile {
+                path: path.as_ref().to_path_buf(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// The process exit status for a run that failed with this error, one
+    /// per class of error. 1 is left for runs that fail a check and 2 for
+    /// clap's usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::Io { .. } | AocError::IoError(_) => EXIT_IO,
+            AocError::Parse { .. }
+            | AocError::ParseError(_)
+            | AocError::NomError(_)
+            | AocError::EmptyInput(_) => EXIT_PARSE,
+            AocError::InvalidArgument(_) | AocError::Unsupported(_) => EXIT_INVALID,
+            AocError::Overflow(_) => EXIT_OVERFLOW,
+            AocError::InFile { source, .. } => source.exit_code(),
+            AocError::Multiple(errors) => errors.first().map_or(1, AocError::exit_code),
+        }
+    }
+
+    /// This error as a compiler-style diagnostic. A parse error starts
+    /// `path:line:column:`, or `line:column:` if no file is known, and
+    /// quotes the offending line with a caret under the column:
+    ///
+    /// ```text
+    /// data/day02/input.txt:1:7: expected a digit, found '-'
+    ///   |
+    /// 1 | 11-22,-15
+    ///   |       ^
+    /// ```
+    ///
+    /// Other errors are their message, after the path if there is one.
+    pub fn diagnostic(&self) -> String {
+        self.diagnostic_in(None)
+    }
+
+    fn diagnostic_in(&self, path: Option<&Path>) -> String {
+        let place = path.map_or(String::new(), |path| format!("{}:", path.display()));
+        match self {
+            AocError::InFile { path, source } => source.diagnostic_in(Some(path)),
+            AocError::Parse {
+                line,
+                column,
+                input,
+                source,
+            } => {
+                let gutter = " ".repeat(line.to_string().len());
+                format!(
+                    "{}{}:{}: {}\n{} |\n{} | {}\n{} | {:>column$}",
+                    place,
+                    line,
+                    column,
+                    source.message(),
+                    gutter,
+                    line,
+                    input,
+                    gutter,
+                    "^"
+                )
+            }
+            AocError::Multiple(errors) => {
+                let mut out = errors
+                    .iter()
+                    .take(MAX_LISTED_ERRORS)
+                    .map(|error| error.diagnostic_in(path))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if errors.len() > MAX_LISTED_ERRORS {
+                    out.push_str(&format!(
+                        "\n...and {} more",
+                        errors.len() - MAX_LISTED_ERRORS
+                    ));
+                }
+                out
+            }
+            error if path.is_some() => format!("{} {}", place, error),
+            error => error.to_string(),
+        }
+    }
+
+    /// The message alone, without the kind of error in front.
+    fn message(&self) -> String {
+        match self {
+            AocError::ParseError(message)
+            | AocError::NomError(message)
+            | AocError::EmptyInput(message) => message.clone(),
+            error => error.to_string(),
+        }
+    }
+
+    /// Collects every value from `results`, or every error if any failed:
+    /// a lone error as itself, several as `Multiple`.
+    pub fn collect_all<T, I>(results: I) -> Result<Vec<T>, AocError>
+    where
+        I: IntoIterator<Item = Result<T, AocError>>,
+    {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for result in results {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(error),
+            }
+        }
+        match errors.len() {
+            0 => Ok(values),
+            1 => Err(errors.remove(0)),
+            _ => Err(AocError::Multiple(errors)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_all() {
+        let ok: Vec<Result<u32, AocError>> = vec![Ok(1), Ok(2)];
+        assert_eq!(AocError::collect_all(ok).expect("values"), vec![1, 2]);
+        let one = vec![Ok(1), Err(AocError::ParseError("bad".to_string()))];
+        assert!(matches!(
+            AocError::collect_all(one),
+            Err(AocError::ParseError(_))
+        ));
+        let many = (0..12).map(|i| Err::<u32, _>(AocError::ParseError(format!("line {}", i))));
+        let error = AocError::collect_all(many).expect_err("errors");
+        let message = error.to_string();
+        assert!(message.starts_with("12 errors:\n  Parse error: line 0\n"));
+        assert!(message.contains("Parse error: line 9\n"));
+        assert!(!message.contains("line 10"));
+        assert!(message.ends_with("...and 2 more"));
+    }
+
+    #[test]
+    fn test_structured_errors() {
+        let bad = || AocError::ParseError("bad".to_string());
+        let error = AocError::parse_at("L1\nRx2\nL3\n", 4, bad());
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 2 of 'Rx2': Parse error: bad"
+        );
+        assert!(matches!(
+            error.at_line(7, "Rx2"),
+            AocError::Parse {
+                line: 7,
+                column: 2,
+                ..
+            }
+        ));
+        assert!(matches!(
+            bad().at_line(3, "x"),
+            AocError::Parse {
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+        let io = AocError::io(
+            "data/missing.txt",
+            std::io::Error::from(std::io::ErrorKind::NotFound),
+        );
+        assert!(io.to_string().starts_with("IO error: data/missing.txt: "));
+        assert!(std::error::Error::source(&io).is_some());
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let parse = AocError::ParseError("bad".to_string()).at_line(3, "x");
+        assert_eq!(parse.exit_code(), EXIT_PARSE);
+        let in_file = parse.in_file("data/day01/input.txt");
+        assert_eq!(in_file.exit_code(), EXIT_PARSE);
+        assert!(
+            in_file
+                .to_string()
+                .starts_with("data/day01/input.txt: Parse error at line 3, column 1 of 'x'")
+        );
+        let io = AocError::io("x", std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert_eq!(io.exit_code(), EXIT_IO);
+        let io = io.in_file("y");
+        assert!(matches!(&io, AocError::Io { path, .. } if path.as_os_str() == "x"));
+        assert_eq!(
+            AocError::InvalidArgument("x".to_string()).exit_code(),
+            EXIT_INVALID
+        );
+        assert_eq!(
+            AocError::Overflow("x".to_string()).exit_code(),
+            EXIT_OVERFLOW
+        );
+        assert_eq!(AocError::Multiple(vec![io]).exit_code(), EXIT_IO);
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let error = AocError::parse_at(
+            "1-2\n11-22,-15\n",
+            10,
+            AocError::NomError("expected a digit, found '-'".to_string()),
+        );
+        assert_eq!(
+            error.in_file("data/day02/input.txt").diagnostic(),
+            "data/day02/input.txt:2:7: expected a digit, found '-'\n  |\n2 | 11-22,-15\n  |       ^"
+        );
+        let bad = |line| AocError::ParseError("bad".to_string()).at_line(line, "X1");
+        assert_eq!(
+            AocError::Multiple(vec![bad(2), bad(12)]).diagnostic(),
+            "2:1: bad\n  |\n2 | X1\n  | ^\n12:1: bad\n   |\n12 | X1\n   | ^"
+        );
+        let overflow = AocError::Overflow("sum".to_string());
+        assert_eq!(overflow.diagnostic(), "Overflow: sum");
+        assert_eq!(
+            overflow.in_file("in.txt").diagnostic(),
+            "in.txt: Overflow: sum"
+        );
+    }
 }
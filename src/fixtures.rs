@@ -0,0 +1,82 @@
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// Where the golden-fixture manifest lives, relative to the crate root.
+pub const MANIFEST_PATH: &str = "data/fixtures.txt";
+
+/// A recorded answer: solving `input` for `day` in `mode` must give
+/// `expected`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u32,
+    pub mode: String,
+    pub input: String,
+    pub expected: String,
+}
+
+/// Parses a manifest with one fixture per line as
+/// `day mode input expected`, ignoring blank lines and `#` comments.
+pub fn parse_manifest(text: &str) -> AocResult<Vec<Fixture>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(number, line)| {
+            let invalid = |reason: &str| {
+                AocError::ParseError(format!(
+                    "fixture manifest line {}: {}: '{}'",
+                    number + 1,
+                    reason,
+                    line
+                ))
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, mode, input, expected] = fields[..] else {
+                return Err(invalid("expected 'day mode input expected'"));
+            };
+            Ok(Fixture {
+                day: day.parse().map_err(|_| invalid("invalid day"))?,
+                mode: mode.to_string(),
+                input: input.to_string(),
+                expected: expected.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn read_manifest(path: &str) -> AocResult<Vec<Fixture>> {
+    let text = std::fs::read_to_string(path).map_err(|e| AocError::io(path, e))?;
+    parse_manifest(&text)
+}
+
+/// The fixtures recorded for `day`, in manifest order.
+pub fn fixtures_for_day(day: u32) -> AocResult<Vec<Fixture>> {
+    Ok(read_manifest(MANIFEST_PATH)?
+        .into_iter()
+        .filter(|fixture| fixture.day == day)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let text = "# day mode input expected\n\n3 two data/day03/test_input.txt 357\n";
+        let fixtures = parse_manifest(text).expect("manifest");
+        assert_eq!(
+            fixtures,
+            vec![Fixture {
+                day: 3,
+                mode: "two".to_string(),
+                input: "data/day03/test_input.txt".to_string(),
+                expected: "357".to_string(),
+            }]
+        );
+        assert!(parse_manifest("3 two data/day03/test_input.txt").is_err());
+        assert!(parse_manifest("x two data/day03/test_input.txt 357").is_err());
+    }
+}
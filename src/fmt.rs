@@ -0,0 +1,106 @@
+//! Human-readable durations, counts and rates for reports and logs.
+//!
+//! Each is a wrapper whose `Display` picks a unit to suit the value and
+//! honours width and alignment, so `{:>10}` lines up columns:
+//!
+//! ```text
+//! Elapsed(Duration::from_micros(1500))        1.5 ms
+//! Count(1_234_567)                            1.2M
+//! Rate { items: 3_000_000, duration: 2s }     1.5M/s
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+/// A duration in ns, µs, ms or s, whichever keeps the number below 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        let text = if nanos < 1_000 {
+            format!("{} ns", nanos)
+        } else if nanos < 1_000_000 {
+            format!("{:.1} µs", nanos as f64 / 1e3)
+        } else if nanos < 1_000_000_000 {
+            format!("{:.1} ms", nanos as f64 / 1e6)
+        } else {
+            format!("{:.2} s", self.0.as_secs_f64())
+        };
+        f.pad(&text)
+    }
+}
+
+/// A count with a k, M, G or T suffix once it reaches a thousand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Count(pub u64);
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&scaled(self.0 as f64))
+    }
+}
+
+/// Items handled per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    pub items: u64,
+    pub duration: Duration,
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.duration.as_secs_f64();
+        if seconds == 0.0 {
+            return f.pad("-/s");
+        }
+        f.pad(&format!("{}/s", scaled(self.items as f64 / seconds)))
+    }
+}
+
+fn scaled(value: f64) -> String {
+    const UNITS: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")];
+    match UNITS.iter().find(|&&(size, _)| value >= size) {
+        Some(&(size, suffix)) => format!("{:.1}{}", value / size, suffix),
+        None if value.fract() == 0.0 => format!("{}", value),
+        None => format!("{:.1}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elapsed() {
+        assert_eq!(Elapsed(Duration::ZERO).to_string(), "0 ns");
+        assert_eq!(Elapsed(Duration::from_nanos(999)).to_string(), "999 ns");
+        assert_eq!(Elapsed(Duration::from_nanos(1_500)).to_string(), "1.5 µs");
+        assert_eq!(Elapsed(Duration::from_micros(2_340)).to_string(), "2.3 ms");
+        assert_eq!(
+            Elapsed(Duration::from_millis(61_000)).to_string(),
+            "61.00 s"
+        );
+        assert_eq!(
+            format!("{:>8}|", Elapsed(Duration::from_millis(5))),
+            "  5.0 ms|"
+        );
+    }
+
+    #[test]
+    fn test_count_and_rate() {
+        assert_eq!(Count(0).to_string(), "0");
+        assert_eq!(Count(999).to_string(), "999");
+        assert_eq!(Count(1_234_567).to_string(), "1.2M");
+        assert_eq!(Count(5_000_000_000_000).to_string(), "5.0T");
+        assert_eq!(format!("{:<6}|", Count(12_000)), "12.0k |");
+        let rate = |items, millis| Rate {
+            items,
+            duration: Duration::from_millis(millis),
+        };
+        assert_eq!(rate(3_000_000, 2_000).to_string(), "1.5M/s");
+        assert_eq!(rate(3, 2_000).to_string(), "1.5/s");
+        assert_eq!(rate(10, 0).to_string(), "-/s");
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(&self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d.delta())
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        NEIGHBOURS_8.into_iter().map(move |d| self + d)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+pub const NEIGHBOURS_8: [Point; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(-1, 1),
+    Point::new(0, 1),
+    Point::new(1, 1),
+];
+
+/// Compass direction on a grid where `y` grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+/// All points on the straight line from `from` to `to` inclusive, using
+/// Bresenham's algorithm so diagonal and shallow lines are both covered.
+pub fn line(from: Point, to: Point) -> Vec<Point> {
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let sx = (to.x - from.x).signum();
+    let sy = (to.y - from.y).signum();
+    let mut err = dx + dy;
+    let mut p = from;
+    let mut points = vec![p];
+    while p != to {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            p.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            p.y += sy;
+        }
+        points.push(p);
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let p = Point::new(2, 3) + Point::new(1, -1);
+        assert_eq!(p, Point::new(3, 2));
+        assert_eq!(p - Point::new(3, 2), Point::ORIGIN);
+        assert_eq!(p * 2, Point::new(6, 4));
+        assert_eq!(Point::new(1, 1).manhattan(Point::new(-2, 5)), 7);
+    }
+
+    #[test]
+    fn test_turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.reverse().reverse(), d);
+            assert_eq!(d.delta() + d.reverse().delta(), Point::ORIGIN);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(
+            line(Point::new(0, 0), Point::new(3, 0)),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0)
+            ]
+        );
+        assert_eq!(
+            line(Point::new(2, 2), Point::new(0, 0)),
+            vec![Point::new(2, 2), Point::new(1, 1), Point::new(0, 0)]
+        );
+        assert_eq!(
+            line(Point::new(1, 1), Point::new(1, 1)),
+            vec![Point::new(1, 1)]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use crate::error::AocError;
+use crate::geometry::{self, Point};
+use crate::result::AocResult;
+
+/// Dense rectangular grid addressed by `Point`, with `(0, 0)` at the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> AocResult<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AocError::ParseError(format!(
+                    "grid row {} has {} cells, expected {}",
+                    y,
+                    row.len(),
+                    width
+                )));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn parse<F>(input: &str, f: F) -> AocResult<Self>
+    where
+        F: Fn(char) -> AocResult<T>,
+    {
+        let rows = input
+            .lines()
+            .map(|line| line.chars().map(&f).collect::<AocResult<Vec<T>>>())
+            .collect::<AocResult<Vec<Vec<T>>>>()?;
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn set(&mut self, p: Point, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours4().filter(|n| self.contains(*n))
+    }
+
+    /// Points reachable from `start` through orthogonal steps onto cells
+    /// accepted by `passable`. The start cell must itself be passable.
+    pub fn flood_fill<F>(&self, start: Point, passable: F) -> HashSet<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut seen = HashSet::new();
+        if !self.get(start).is_some_and(&passable) {
+            return seen;
+        }
+        let mut queue = VecDeque::from([start]);
+        seen.insert(start);
+        while let Some(p) = queue.pop_front() {
+            for n in self.neighbours4(p) {
+                if !seen.contains(&n) && passable(&self.cells[self.index(n).unwrap()]) {
+                    seen.insert(n);
+                    queue.push_back(n);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Labels orthogonally connected regions whose neighbouring cells satisfy
+    /// `same`. Returns a grid of labels in `0..count` plus the region count.
+    pub fn connected_components<F>(&self, same: F) -> (Grid<usize>, usize)
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut labels = Grid::new(self.width, self.height, usize::MAX);
+        let mut count = 0;
+        for start in self.points() {
+            if labels.get(start) != Some(&usize::MAX) {
+                continue;
+            }
+            labels.set(start, count);
+            let mut queue = VecDeque::from([start]);
+            while let Some(p) = queue.pop_front() {
+                let here = self.get(p).unwrap();
+                for n in self.neighbours4(p) {
+                    if labels.get(n) == Some(&usize::MAX) && same(here, self.get(n).unwrap()) {
+                        labels.set(n, count);
+                        queue.push_back(n);
+                    }
+                }
+            }
+            count += 1;
+        }
+        (labels, count)
+    }
+
+    /// Points visited stepping from `from` by `step` (exclusive of `from`)
+    /// until the ray leaves the grid.
+    pub fn ray(&self, from: Point, step: Point) -> impl Iterator<Item = Point> + '_ {
+        let mut p = from;
+        std::iter::from_fn(move || {
+            if step == Point::ORIGIN {
+                return None;
+            }
+            p += step;
+            if self.contains(p) { Some(p) } else { None }
+        })
+    }
+
+    /// First point along a ray whose cell is accepted by `blocking`.
+    pub fn first_hit<F>(&self, from: Point, step: Point, blocking: F) -> Option<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.ray(from, step)
+            .find(|p| self.get(*p).is_some_and(&blocking))
+    }
+
+    /// True when no cell strictly between `from` and `to` is blocking.
+    pub fn line_of_sight<F>(&self, from: Point, to: Point, blocking: F) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        let points = geometry::line(from, to);
+        let inner = &points[1..points.len().saturating_sub(1).max(1)];
+        inner.iter().all(|p| !self.get(*p).is_some_and(&blocking))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok).expect("grid")
+    }
+
+    const MAZE: &str = "\
+..#..
+..#..
+###..
+...#.
+...#.";
+
+    #[test]
+    fn test_parse_and_get() {
+        let grid = parse_chars(MAZE);
+        assert_eq!(grid.width(), 5);
+        assert_eq!(grid.height(), 5);
+        assert_eq!(grid.get(Point::new(2, 0)), Some(&'#'));
+        assert_eq!(grid.get(Point::new(5, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), format!("{}\n", MAZE));
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = parse_chars(MAZE);
+        let region = grid.flood_fill(Point::new(0, 0), |c| *c == '.');
+        assert_eq!(region.len(), 4);
+        let region = grid.flood_fill(Point::new(4, 0), |c| *c == '.');
+        assert_eq!(region.len(), 8);
+        assert!(region.contains(&Point::new(4, 4)));
+        assert!(grid.flood_fill(Point::new(2, 0), |c| *c == '.').is_empty());
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let grid = parse_chars(MAZE);
+        let (labels, count) = grid.connected_components(|a, b| a == b);
+        // top-left dots, walls, right-hand dots, bottom-left dots, lower wall
+        assert_eq!(count, 5);
+        assert_eq!(labels.get(Point::new(0, 0)), labels.get(Point::new(1, 1)));
+        assert_ne!(labels.get(Point::new(0, 0)), labels.get(Point::new(0, 4)));
+        assert_eq!(labels.get(Point::new(2, 0)), labels.get(Point::new(0, 2)));
+    }
+
+    #[test]
+    fn test_ray_and_first_hit() {
+        let grid = parse_chars(MAZE);
+        let ray: Vec<Point> = grid.ray(Point::new(0, 0), Point::new(1, 0)).collect();
+        assert_eq!(ray.len(), 4);
+        assert_eq!(
+            grid.first_hit(Point::new(0, 0), Point::new(1, 0), |c| *c == '#'),
+            Some(Point::new(2, 0))
+        );
+        assert_eq!(
+            grid.first_hit(Point::new(4, 0), Point::new(0, 1), |c| *c == '#'),
+            None
+        );
+        assert_eq!(grid.ray(Point::new(0, 0), Point::ORIGIN).count(), 0);
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let grid = parse_chars(MAZE);
+        assert!(!grid.line_of_sight(Point::new(0, 0), Point::new(4, 0), |c| *c == '#'));
+        assert!(grid.line_of_sight(Point::new(4, 0), Point::new(4, 4), |c| *c == '#'));
+        assert!(grid.line_of_sight(Point::new(0, 0), Point::new(1, 0), |c| *c == '#'));
+        assert!(grid.line_of_sight(Point::new(2, 0), Point::new(2, 0), |c| *c == '#'));
+    }
+}
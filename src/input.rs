@@ -0,0 +1,523 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+use nom::IResult;
+
+use crate::error::AocError;
+use crate::interner::Interner;
+use crate::net::{self, Failure, RetryPolicy, SystemClock};
+use crate::parse::{self, parse_complete};
+use crate::result::AocResult;
+
+/// Puzzle input text together with the symbol table its parsers intern
+/// labels into.
+#[derive(Debug, Default)]
+pub struct Input {
+    text: String,
+    symbols: RefCell<Interner>,
+}
+
+impl Input {
+    pub fn new(text: impl Into<String>) -> Self {
+        Input {
+            text: text.into(),
+            symbols: RefCell::new(Interner::new()),
+        }
+    }
+
+    pub fn read(path: &str) -> AocResult<Self> {
+        Ok(Self::new(read_to_string(path)?))
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// The interner to hand to `parse::symbol`.
+    pub fn symbols(&self) -> &RefCell<Interner> {
+        &self.symbols
+    }
+
+    pub fn into_symbols(self) -> Interner {
+        self.symbols.into_inner()
+    }
+
+    /// Lines grouped into blocks separated by one or more blank lines.
+    pub fn blocks(&self) -> Vec<Vec<&str>> {
+        let mut blocks = vec![];
+        let mut current = vec![];
+        for line in self.lines() {
+            if line.trim().is_empty() {
+                if !current.is_empty() {
+                    blocks.push(std::mem::take(&mut current));
+                }
+            } else {
+                current.push(line);
+            }
+        }
+        if !current.is_empty() {
+            blocks.push(current);
+        }
+        blocks
+    }
+
+    /// Parse every line in full with `parser`.
+    pub fn parse_lines<T, F>(&self, mut parser: F) -> AocResult<Vec<T>>
+    where
+        F: FnMut(&str) -> IResult<&str, T>,
+    {
+        self.lines()
+            .map(|line| parse_complete(line, &mut parser))
+            .collect()
+    }
+}
+
+/// The `--input` path that stands for standard input.
+pub const STDIN: &str = "-";
+
+/// The text of the input file at `path`, or of standard input if `path`
+/// is `-`.
+pub fn read_to_string(path: &str) -> AocResult<String> {
+    read_from(path, &mut std::io::stdin())
+}
+
+fn read_from(path: &str, stdin: &mut dyn Read) -> AocResult<String> {
+    let result = if path == STDIN {
+        let mut text = String::new();
+        stdin.read_to_string(&mut text).map(|_| text)
+    } else {
+        std::fs::read_to_string(path)
+    };
+    result.map_err(|e| read_error(path, e))
+}
+
+fn read_error(path: &str, error: std::io::Error) -> AocError {
+    AocError::io(path, error)
+}
+
+fn line_error(number: usize, line: &str, error: AocError) -> AocError {
+    error.at_line(number + 1, line)
+}
+
+/// The lines of the input at `path`, read as by `read_to_string`.
+pub fn read_lines(path: &str) -> AocResult<Vec<String>> {
+    Ok(read_to_string(path)?.lines().map(str::to_string).collect())
+}
+
+/// Each line of the input at `path` parsed with `parser`, reporting every
+/// bad line at once, numbered from 1.
+pub fn parse_lines<T>(path: &str, parser: impl FnMut(&str) -> AocResult<T>) -> AocResult<Vec<T>> {
+    parse::parse_lines(&read_to_string(path)?, parser)
+}
+
+/// Each line of the input at `path` parsed with `parser` as it is read,
+/// so a large file is never held in memory whole. Errors name the line,
+/// numbered from 1. Reads standard input if `path` is `-`.
+pub fn lines_iter<T, F>(
+    path: &str,
+    parser: F,
+) -> AocResult<impl Iterator<Item = AocResult<T>> + use<T, F>>
+where
+    F: FnMut(&str) -> AocResult<T>,
+{
+    let reader: Box<dyn BufRead> = if path == STDIN {
+        Box::new(BufReader::new(std::io::stdin()))
+    } else {
+        Box::new(BufReader::new(
+            File::open(path).map_err(|e| read_error(path, e))?,
+        ))
+    };
+    Ok(parse_reader(reader, path.to_string(), parser))
+}
+
+fn parse_reader<T>(
+    reader: impl BufRead,
+    path: String,
+    mut parser: impl FnMut(&str) -> AocResult<T>,
+) -> impl Iterator<Item = AocResult<T>> {
+    reader.lines().enumerate().map(move |(number, line)| {
+        let line = line.map_err(|e| read_error(&path, e))?;
+        parser(&line).map_err(|e| line_error(number, &line, e))
+    })
+}
+
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Shortest time between two requests to adventofcode.com, across runs.
+pub const REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where the real input for `day` lives.
+pub fn input_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("data/day{:02}/input.txt", day))
+}
+
+/// Downloads the real input for `day` to `input_path(day)`, unless it is
+/// already there. Returns the path and whether it was downloaded.
+///
+/// The session cookie comes from `AOC_SESSION`, or else the first line of
+/// `session_file()`. Requests go through `curl` and are spaced at least
+/// `REQUEST_INTERVAL` apart.
+pub fn download(day: u32) -> AocResult<(PathBuf, bool)> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::InvalidArgument(format!(
+            "day {} is not 1 to 25",
+            day
+        )));
+    }
+    let path = input_path(day);
+    let fetched = fetch_into(&path, || request(&format!("day/{}/input", day), &[]))?;
+    Ok((path, fetched))
+}
+
+/// Writes the text `fetch` returns to `path` if `path` is missing or
+/// empty. Returns whether `fetch` was called.
+fn fetch_into(path: &Path, fetch: impl FnOnce() -> AocResult<String>) -> AocResult<bool> {
+    let cached = std::fs::metadata(path).is_ok_and(|meta| meta.len() > 0);
+    if cached {
+        return Ok(false);
+    }
+    let text = fetch()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+    }
+    std::fs::write(path, text).map_err(|e| io_error(path, e))?;
+    Ok(true)
+}
+
+/// Records when the last request was made, so separate runs share the
+/// rate limit.
+const LAST_REQUEST_FILE: &str = "data/.last_request";
+
+/// Deletes the downloaded puzzle inputs and the time of the last request,
+/// or only lists them if `dry_run`, returning their paths.
+pub fn clean_cache(dry_run: bool) -> std::io::Result<Vec<PathBuf>> {
+    let found: Vec<PathBuf> = (1..=25)
+        .map(input_path)
+        .chain([PathBuf::from(LAST_REQUEST_FILE)])
+        .filter(|path| path.is_file())
+        .collect();
+    if !dry_run {
+        for path in &found {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(found)
+}
+
+/// `$XDG_CONFIG_HOME/aoc25/session`, falling back to `~/.config`.
+pub fn session_file() -> Option<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config.join("aoc25").join("session"))
+}
+
+fn session_token() -> AocResult<String> {
+    if let Ok(token) = std::env::var(SESSION_VAR) {
+        return parse_session(&token);
+    }
+    let missing = || {
+        AocError::Unsupported(format!(
+            "no session cookie: set {} or write it to {}",
+            SESSION_VAR,
+            session_file().map_or("the session file".to_string(), |p| p.display().to_string())
+        ))
+    };
+    let path = session_file().ok_or_else(missing)?;
+    let text = std::fs::read_to_string(&path).map_err(|_| missing())?;
+    parse_session(&text)
+}
+
+/// The token from a line such as `53616c74...` or `session=53616c74...`.
+fn parse_session(text: &str) -> AocResult<String> {
+    let token = text.lines().next().unwrap_or("").trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AocError::ParseError(
+            "session cookie should be a string of letters and digits".to_string(),
+        ));
+    }
+    Ok(token.to_string())
+}
+
+/// Sleeps until `REQUEST_INTERVAL` has passed since the time recorded in
+/// `stamp`, then records now.
+fn wait_for_rate_limit(stamp: &Path) -> AocResult<()> {
+    let last = std::fs::metadata(stamp)
+        .and_then(|meta| meta.modified())
+        .ok();
+    let wait = rate_limit_wait(last, SystemTime::now());
+    if !wait.is_zero() {
+        tracing::info!("Waiting {:.1?} before the next request", wait);
+        std::thread::sleep(wait);
+    }
+    if let Some(dir) = stamp.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+    }
+    std::fs::write(stamp, "").map_err(|e| io_error(stamp, e))
+}
+
+/// How long to wait at `now` if the last request was at `last`.
+fn rate_limit_wait(last: Option<SystemTime>, now: SystemTime) -> Duration {
+    last.and_then(|last| now.duration_since(last).ok())
+        .map_or(Duration::ZERO, |since| {
+            REQUEST_INTERVAL.saturating_sub(since)
+        })
+}
+
+/// This year's Advent of Code, which every puzzle page hangs off.
+pub const SITE: &str = "https://adventofcode.com/2025";
+
+/// The puzzle page for `day`.
+pub fn puzzle_url(day: u32) -> String {
+    format!("{}/day/{}", SITE, day)
+}
+
+/// The body of `https://adventofcode.com/2025/<page>`, fetched with `curl`
+/// and the session cookie once the rate limit allows. Non-empty `form`
+/// fields are URL-encoded and posted. Network errors, 429s and 5xxs are
+/// retried with `net::retry`.
+pub(crate) fn request(page: &str, form: &[(&str, &str)]) -> AocResult<String> {
+    let session = session_token()?;
+    let url = format!("{}/{}", SITE, page);
+    net::retry(&RetryPolicy::default(), &mut SystemClock::new(), || {
+        wait_for_rate_limit(Path::new(LAST_REQUEST_FILE)).map_err(Failure::permanent)?;
+        curl(&url, &session, form)
+    })
+}
+
+/// Follows the body `curl` prints with the status and `Retry-After`.
+const WRITE_OUT: &str = "\n%{http_code} %header{retry-after}";
+
+/// One request with `curl`. The cookie is passed on stdin so it doesn't
+/// show up in the process list.
+fn curl(url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, Failure> {
+    tracing::info!("Requesting {}", url);
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--header", "@-"])
+        .args(["--user-agent", "github.com/cmsd2/aoc25 via curl"])
+        .args(["--write-out", WRITE_OUT]);
+    for (name, value) in form {
+        command
+            .arg("--data-urlencode")
+            .arg(format!("{}={}", name, value));
+    }
+    let curl_error = |e: std::io::Error| {
+        Failure::permanent(AocError::IoError(format!("Failed to run curl: {}", e)))
+    };
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(curl_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={}", session).map_err(curl_error)?;
+    }
+    let output = child.wait_with_output().map_err(curl_error)?;
+    if !output.status.success() {
+        // curl only fails like this when the request didn't complete.
+        return Err(Failure::transient(AocError::IoError(format!(
+            "Failed to request {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))));
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| Failure::permanent(AocError::ParseError(format!("{} is not UTF-8", url))))?;
+    response(url, &stdout)
+}
+
+/// The body of a response printed with `WRITE_OUT`, or the failure its
+/// status means.
+fn response(url: &str, stdout: &str) -> Result<String, Failure> {
+    let (body, trailer) = stdout.rsplit_once('\n').unwrap_or(("", stdout));
+    let (status, retry_after) = trailer.split_once(' ').unwrap_or((trailer, ""));
+    let status: u16 = status.parse().map_err(|_| {
+        Failure::permanent(AocError::ParseError(format!(
+            "no status from curl for {}",
+            url
+        )))
+    })?;
+    if (200..300).contains(&status) {
+        return Ok(body.to_string());
+    }
+    let error = AocError::IoError(format!("{} returned HTTP {}", url, status));
+    if status == 429 || status >= 500 {
+        Err(Failure {
+            error,
+            retryable: true,
+            retry_after: net::parse_retry_after(retry_after),
+        })
+    } else {
+        Err(Failure::permanent(error))
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> AocError {
+    AocError::io(path, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interner::Symbol;
+    use crate::parse::{label, symbol};
+    use nom::{Parser, character::complete::space1, multi::separated_list1, sequence::preceded};
+
+    #[test]
+    fn test_parse_lines_with_symbols() {
+        let input = Input::new("aaa: you hhh\nyou: bbb ccc\nbbb: ddd eee\n");
+        let edges: Vec<(Symbol, Vec<Symbol>)> = input
+            .parse_lines(|line| {
+                (
+                    label(input.symbols()),
+                    preceded(space1, separated_list1(space1, symbol(input.symbols()))),
+                )
+                    .parse(line)
+            })
+            .expect("parse");
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[1].0, edges[0].1[0]);
+        let symbols = input.into_symbols();
+        assert_eq!(symbols.len(), 7);
+        assert_eq!(symbols.resolve(edges[2].0), Some("bbb"));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = Input::new("\na\nb\n\n  \n\nc\n");
+        assert_eq!(input.blocks(), vec![vec!["a", "b"], vec!["c"]]);
+        assert!(Input::new("\n\n").blocks().is_empty());
+    }
+
+    #[test]
+    fn test_parse_lines_rejects_trailing_input() {
+        let input = Input::new("aaa bbb");
+        assert!(
+            input
+                .parse_lines(|line| symbol(input.symbols())(line))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_read_from_stdin() {
+        let mut stdin = "L68\nR48\n".as_bytes();
+        assert_eq!(read_from(STDIN, &mut stdin).expect("stdin"), "L68\nR48\n");
+        let text = read_from("data/day01/test_input.txt", &mut "".as_bytes()).expect("file");
+        assert!(text.starts_with("L68\n"));
+        assert!(matches!(
+            read_from("data/day00/missing.txt", &mut "".as_bytes()),
+            Err(AocError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parse = |line: &str| {
+            line.parse::<u32>()
+                .map_err(|e| AocError::ParseError(e.to_string()))
+        };
+        assert_eq!(
+            parse::parse_lines("1\n2\n3", parse).expect("numbers"),
+            [1, 2, 3]
+        );
+        let error = parse::parse_lines("1\nx\n3\ny", parse).expect_err("bad lines");
+        assert!(error.to_string().contains("line 2"));
+        assert!(error.to_string().contains("line 4"));
+        assert_eq!(
+            read_lines("data/day03/test_input.txt")
+                .expect("lines")
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_lines_iter() {
+        let parse = |line: &str| {
+            line.parse::<u32>()
+                .map_err(|e| AocError::ParseError(e.to_string()))
+        };
+        let mut items = parse_reader("1\r\n2\nx\n4".as_bytes(), "-".to_string(), parse);
+        assert_eq!(items.next().map(Result::ok), Some(Some(1)));
+        assert_eq!(items.next().map(Result::ok), Some(Some(2)));
+        let error = items.next().and_then(Result::err).expect("bad line");
+        assert!(error.to_string().contains("line 3"));
+        assert_eq!(items.next().map(Result::ok), Some(Some(4)));
+        assert!(items.next().is_none());
+
+        let lines: Vec<String> =
+            lines_iter("data/day01/test_input.txt", |line| Ok(line.to_string()))
+                .expect("file")
+                .collect::<AocResult<_>>()
+                .expect("lines");
+        assert_eq!(lines.first().map(String::as_str), Some("L68"));
+        assert!(lines_iter("data/day00/missing.txt", parse).is_err());
+    }
+
+    #[test]
+    fn test_parse_session() {
+        assert_eq!(parse_session("abc123\n").expect("token"), "abc123");
+        assert_eq!(parse_session("session=abc123").expect("token"), "abc123");
+        assert!(parse_session("").is_err());
+        assert!(parse_session("abc; rm").is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let now = SystemTime::now();
+        assert_eq!(rate_limit_wait(None, now), Duration::ZERO);
+        assert_eq!(
+            rate_limit_wait(Some(now - Duration::from_secs(2)), now),
+            REQUEST_INTERVAL - Duration::from_secs(2)
+        );
+        assert_eq!(
+            rate_limit_wait(Some(now - REQUEST_INTERVAL * 2), now),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_fetch_into_skips_existing_input() {
+        let dir = std::env::temp_dir().join(format!("aoc25-fetch-{}", std::process::id()));
+        let path = dir.join("day05/input.txt");
+        assert!(fetch_into(&path, || Ok("1\n".to_string())).expect("fetched"));
+        assert!(!fetch_into(&path, || panic!("input is cached")).expect("cached"));
+        assert_eq!(std::fs::read_to_string(&path).expect("input"), "1\n");
+        std::fs::remove_dir_all(&dir).expect("clean up");
+    }
+
+    #[test]
+    fn test_response() {
+        let url = "https://adventofcode.com/2025/day/1/input";
+        assert_eq!(
+            response(url, "L68\nL30\n\n200 ").expect("body"),
+            "L68\nL30\n"
+        );
+        let failure = response(url, "slow down\n429 30").expect_err("rate limited");
+        assert!(failure.retryable);
+        assert_eq!(failure.retry_after, Some(Duration::from_secs(30)));
+        assert!(response(url, "\n503 ").expect_err("unavailable").retryable);
+        let failure = response(url, "Please log in\n400 ").expect_err("logged out");
+        assert!(!failure.retryable);
+        assert_eq!(
+            failure.error.to_string(),
+            "IO error: https://adventofcode.com/2025/day/1/input returned HTTP 400"
+        );
+        assert!(response(url, "").is_err());
+    }
+}
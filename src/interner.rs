@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Compact handle for a string stored in an `Interner`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Maps repeated labels to dense `Symbol`s numbered from zero in order of
+/// first appearance, so they can index a `Vec` directly.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    symbols: HashMap<Box<str>, Symbol>,
+    names: Vec<Box<str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return symbol;
+        }
+        let symbol =
+            Symbol(u32::try_from(self.names.len()).expect("more than u32::MAX interned symbols"));
+        self.names.push(name.into());
+        self.symbols.insert(name.into(), symbol);
+        symbol
+    }
+
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.names.get(symbol.index()).map(|s| s.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (Symbol(i as u32), name.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_dedupes() {
+        let mut interner = Interner::new();
+        let a = interner.intern("aaa");
+        let b = interner.intern("bbb");
+        assert_eq!(interner.intern("aaa"), a);
+        assert_ne!(a, b);
+        assert_eq!(a.index(), 0);
+        assert_eq!(b.index(), 1);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut interner = Interner::new();
+        let you = interner.intern("you");
+        assert_eq!(interner.resolve(you), Some("you"));
+        assert_eq!(interner.get("you"), Some(you));
+        assert_eq!(interner.get("out"), None);
+        assert_eq!(interner.resolve(Symbol(7)), None);
+        let all: Vec<(Symbol, &str)> = interner.iter().collect();
+        assert_eq!(all, vec![(you, "you")]);
+    }
+}
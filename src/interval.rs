@@ -0,0 +1,199 @@
+use std::fmt;
+
+/// Inclusive range of integers `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// Panics if `start > end`.
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(start <= end, "interval start {} after end {}", start, end);
+        Interval { start, end }
+    }
+
+    pub fn point(value: i64) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.start.abs_diff(self.end) + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the two intervals overlap or sit next to each other, so
+    /// their union is a single interval.
+    pub fn touches(&self, other: &Interval) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Set of integers stored as sorted, disjoint, non-adjacent intervals, so
+/// dense runs of values cost one entry each.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        // Intervals before `first` end too early to touch the new one.
+        let first = self
+            .intervals
+            .partition_point(|iv| iv.end.saturating_add(1) < interval.start);
+        let mut merged = interval;
+        let mut last = first;
+        while let Some(iv) = self.intervals.get(last) {
+            if !iv.touches(&merged) {
+                break;
+            }
+            merged.start = merged.start.min(iv.start);
+            merged.end = merged.end.max(iv.end);
+            last += 1;
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn insert_value(&mut self, value: i64) {
+        // Appending in order is the common case, so check the end first.
+        match self.intervals.last_mut() {
+            Some(last) if last.end.checked_add(1) == Some(value) => last.end = value,
+            Some(last) if last.end < value => self.intervals.push(Interval::point(value)),
+            None => self.intervals.push(Interval::point(value)),
+            _ => self.insert(Interval::point(value)),
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.intervals.partition_point(|iv| iv.end < value);
+        self.intervals.get(i).is_some_and(|iv| iv.contains(value))
+    }
+
+    /// Number of intervals.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values covered by all intervals.
+    pub fn total_len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = i64> + '_ {
+        self.intervals.iter().flat_map(|iv| iv.start..=iv.end)
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl FromIterator<i64> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for value in iter {
+            set.insert_value(value);
+        }
+        set
+    }
+}
+
+/// Comma-separated `start-end` list, e.g. `1-3,7-7`.
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(3, 7);
+        assert_eq!(a.len(), 5);
+        assert!(a.contains(7));
+        assert!(!a.contains(8));
+        assert!(a.overlaps(&Interval::new(7, 9)));
+        assert!(!a.overlaps(&Interval::new(8, 9)));
+        assert!(a.touches(&Interval::new(8, 9)));
+        assert!(!a.touches(&Interval::new(9, 9)));
+        assert_eq!(Interval::new(-2, 2).len(), 5);
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(Interval::new(10, 12));
+        set.insert(Interval::new(1, 2));
+        set.insert(Interval::new(20, 25));
+        assert_eq!(set.to_string(), "1-2,10-12,20-25");
+        set.insert(Interval::new(3, 9));
+        assert_eq!(set.to_string(), "1-12,20-25");
+        set.insert(Interval::new(0, 30));
+        assert_eq!(set.to_string(), "0-30");
+        assert_eq!(set.total_len(), 31);
+    }
+
+    #[test]
+    fn test_from_values() {
+        let set: IntervalSet = [5, 1, 2, 3, 9, 4, 11, 10].into_iter().collect();
+        assert_eq!(set.to_string(), "1-5,9-11");
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(10));
+        assert!(!set.contains(7));
+        assert_eq!(
+            set.values().collect::<Vec<i64>>(),
+            vec![1, 2, 3, 4, 5, 9, 10, 11]
+        );
+        let edge: IntervalSet = [i64::MAX, i64::MAX - 1].into_iter().collect();
+        assert_eq!(edge.len(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt::Write;
+
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// Serialises a value as compact JSON.
+pub trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> String {
+        string(self)
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> String {
+        string(self)
+    }
+}
+
+impl ToJson for u32 {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToJson for u64 {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToJson for i64 {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> String {
+        let items: Vec<String> = self.iter().map(ToJson::to_json).collect();
+        format!("[{}]", items.join(","))
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> String {
+        self.as_slice().to_json()
+    }
+}
+
+/// `s` as a quoted JSON string.
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Reads the quoted JSON string `input` starts with, as written by
+/// `string`, returning its value and the rest of `input`.
+pub fn read_string(input: &str) -> Option<(String, &str)> {
+    let mut chars = input.strip_prefix('"')?.char_indices();
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &input[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                'n' => out.push('\n'),
+                't' => out.push('\t'),
+                'r' => out.push('\r'),
+                'u' => {
+                    let hex: String = (0..4)
+                        .map(|_| chars.next().map(|(_, c)| c))
+                        .collect::<Option<_>>()?;
+                    out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                c => out.push(c),
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// `s` as a bare JSON number if it is a whole number, otherwise as a
+/// string, so numeric answers can be used without unquoting.
+pub fn number_or_string(s: &str) -> String {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let canonical = digits == "0" || !digits.starts_with('0');
+    if !digits.is_empty() && canonical && digits.bytes().all(|b| b.is_ascii_digit()) {
+        s.to_string()
+    } else {
+        string(s)
+    }
+}
+
+/// A JSON object from `(key, json)` pairs whose values are already
+/// serialised.
+pub fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", string(key), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Writes `value` to `path` as pretty-printed JSON through serde, for
+/// `--dump-parsed`.
+#[cfg(feature = "serde")]
+pub fn dump<T: serde::Serialize + ?Sized>(path: &str, value: &T) -> AocResult<()> {
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|e| AocError::io(path, std::io::Error::other(e)))?;
+    std::fs::write(path, text + "\n").map_err(|e| AocError::io(path, e))
+}
+
+/// Without the `serde` feature there is nothing to serialise with.
+#[cfg(not(feature = "serde"))]
+pub fn dump<T: ?Sized>(_path: &str, _value: &T) -> AocResult<()> {
+    Err(AocError::Unsupported(
+        "--dump-parsed needs aoc25 built with the 'serde' feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(string("a\"b\\c\u{1}"), "\"a\\\"b\\\\c\\u0001\"");
+        assert_eq!(vec![1u32, 2, 3].to_json(), "[1,2,3]");
+        assert_eq!(Vec::<u64>::new().to_json(), "[]");
+        assert_eq!(
+            object(&[("op", "L".to_json()), ("n", 68u32.to_json())]),
+            "{\"op\":\"L\",\"n\":68}"
+        );
+        assert_eq!(number_or_string("1227775554"), "1227775554");
+        assert_eq!(number_or_string("-7"), "-7");
+        assert_eq!(number_or_string("0"), "0");
+        assert_eq!(number_or_string("007"), "\"007\"");
+        assert_eq!(number_or_string("1/2"), "\"1/2\"");
+        assert_eq!(number_or_string(""), "\"\"");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_dump() {
+        use crate::days::day02::{RangeSpec, parse_range_specs};
+
+        let path = std::env::temp_dir().join(format!("aoc25-dump-{}.json", std::process::id()));
+        let path = path.to_str().expect("utf-8 path");
+        let specs = parse_range_specs("11-22,95-\n").expect("specs");
+        dump(path, &specs).expect("dump");
+        let text = std::fs::read_to_string(path).expect("read back");
+        let parsed: Vec<RangeSpec> = serde_json::from_str(&text).expect("parse back");
+        assert_eq!(parsed, specs);
+        std::fs::remove_file(path).expect("clean up");
+    }
+
+    #[test]
+    #[cfg(not(feature = "serde"))]
+    fn test_dump_needs_serde() {
+        assert!(matches!(
+            dump("unused.json", &[1u32]),
+            Err(AocError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_string() {
+        let quoted = string("a\"b\\c\u{1}\nd");
+        assert_eq!(
+            read_string(&(quoted + ",rest")),
+            Some(("a\"b\\c\u{1}\nd".to_string(), ",rest"))
+        );
+        assert_eq!(
+            read_string("\"caf\\u00e9\""),
+            Some(("café".to_string(), ""))
+        );
+        assert_eq!(read_string("\"open"), None);
+        assert_eq!(read_string("bare"), None);
+    }
+}
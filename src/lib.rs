@@ -1,2 +1,38 @@
+pub mod answers;
+pub mod artifacts;
+pub mod bench;
+pub mod bitset;
+pub mod cli;
+pub mod compress;
+pub mod console;
+pub mod crash;
+pub mod dashboard;
+pub mod days;
+pub mod describe;
 pub mod error;
+pub mod fixtures;
+pub mod fmt;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interner;
+pub mod interval;
+pub mod json;
+pub mod logging;
+pub mod math;
+pub mod net;
+pub mod par;
+pub mod params;
+pub mod parse;
+pub mod pq;
+pub mod progress;
 pub mod result;
+pub mod run;
+pub mod scaffold;
+pub mod sim;
+pub mod sniff;
+pub mod solution;
+pub mod solver;
+pub mod submit;
+pub mod timing;
+pub mod turtle;
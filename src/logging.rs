@@ -0,0 +1,224 @@
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::fmt::Count;
+use clap_verbosity_flag::Verbosity;
+use tracing::info;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry;
+
+/// How log lines are written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `tracing-subscriber`'s usual human-readable lines.
+    #[default]
+    Text,
+    /// A JSON object per line, for feeding to analysis tools.
+    Json,
+}
+
+impl From<&str> for LogFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "json" => LogFormat::Json,
+            _ => LogFormat::Text,
+        }
+    }
+}
+
+/// Installs a `tracing` subscriber at the level picked by the `-v`/`-q`
+/// flags, so every binary reads them the same way, writing lines in
+/// `format`. In JSON each line carries the `day` and `phase` spans the
+/// event was in:
+///
+/// ```text
+/// {"timestamp":"...","level":"WARN","fields":{"message":"..."},"target":"aoc25::days::day02","spans":[{"day":2,"name":"day"},{"phase":"solve","name":"phase"}]}
+/// ```
+pub fn init(verbosity: Verbosity, format: LogFormat) {
+    let level = verbosity.tracing_level_filter();
+    // Spans pass at every level, so a warning still says which day and
+    // phase it came from.
+    let filter = filter_fn(move |metadata| metadata.is_span() || *metadata.level() <= level);
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal());
+    match format {
+        LogFormat::Text => registry().with(layer.with_filter(filter)).init(),
+        LogFormat::Json => registry()
+            .with(
+                layer
+                    .json()
+                    .with_current_span(false)
+                    .with_span_list(true)
+                    .with_filter(filter),
+            )
+            .init(),
+    }
+}
+
+/// Whether step-by-step narrative output is wanted. Uses the same threshold
+/// as `info!` events, which is `-vv`.
+pub fn narrative_enabled() -> bool {
+    tracing::enabled!(tracing::Level::INFO)
+}
+
+static LOG_EVERY: AtomicUsize = AtomicUsize::new(1);
+
+/// Makes every `ThrottledLog` created afterwards log a summary each
+/// `every` items instead of a line per item. 0 and 1 both mean every item.
+pub fn set_log_every(every: usize) {
+    LOG_EVERY.store(every.max(1), Ordering::Relaxed);
+}
+
+/// How many of the heaviest items a throttled log repeats when it
+/// finishes.
+pub const TOP_ITEMS: usize = 5;
+
+/// `info!` lines for a loop over many items, such as the ranges of a scan.
+/// Until `set_log_every` says otherwise each item gets its own line; after
+/// that the log has a summary every so many items and, at the end, the
+/// `TOP_ITEMS` items with the largest weight.
+#[derive(Debug, Clone)]
+pub struct ThrottledLog {
+    label: &'static str,
+    weight_name: &'static str,
+    every: usize,
+    enabled: bool,
+    seen: usize,
+    window: u64,
+    /// The heaviest items so far, heaviest first.
+    top: Vec<(u64, String)>,
+}
+
+impl ThrottledLog {
+    /// A log of `label` items whose weights count `weight_name`.
+    pub fn new(label: &'static str, weight_name: &'static str) -> Self {
+        ThrottledLog {
+            enabled: narrative_enabled(),
+            ..ThrottledLog::with_every(label, weight_name, LOG_EVERY.load(Ordering::Relaxed))
+        }
+    }
+
+    fn with_every(label: &'static str, weight_name: &'static str, every: usize) -> Self {
+        ThrottledLog {
+            label,
+            weight_name,
+            every: every.max(1),
+            enabled: true,
+            seen: 0,
+            window: 0,
+            top: Vec::new(),
+        }
+    }
+
+    /// Records an item of `weight`, formatting `message` only if it gets
+    /// logged now or may be among the heaviest at the end.
+    pub fn item(&mut self, weight: u64, message: impl FnOnce() -> String) {
+        if !self.enabled {
+            return;
+        }
+        self.seen += 1;
+        if self.every == 1 {
+            info!("{}", message());
+            return;
+        }
+        self.window += weight;
+        if self.top.len() < TOP_ITEMS || self.top.last().is_some_and(|&(w, _)| weight > w) {
+            let at = self.top.partition_point(|&(w, _)| w >= weight);
+            self.top.insert(at, (weight, message()));
+            self.top.truncate(TOP_ITEMS);
+        }
+        if self.seen.is_multiple_of(self.every) {
+            info!(
+                "- {} {} so far, {} {} in the last {}",
+                Count(self.seen as u64),
+                self.label,
+                Count(self.window),
+                self.weight_name,
+                self.every
+            );
+            self.window = 0;
+        }
+    }
+
+    /// Logs the heaviest items, if they weren't all logged already.
+    pub fn finish(&mut self) {
+        if !self.enabled || self.every == 1 || self.seen == 0 {
+            return;
+        }
+        info!(
+            "{} {} in all, most {} in:",
+            Count(self.seen as u64),
+            self.label,
+            self.weight_name
+        );
+        for (_, message) in &self.top {
+            info!("{}", message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_json_lines_carry_spans() {
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+        impl io::Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .json()
+            .with_current_span(false)
+            .with_span_list(true)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let _day = tracing::info_span!("day", day = 2).entered();
+            let _phase = tracing::info_span!("phase", phase = "solve").entered();
+            tracing::warn!("range \"{}\" is empty", "5-4");
+        });
+        let line = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(line.contains("\"level\":\"WARN\""));
+        assert!(line.contains("\"message\":\"range \\\"5-4\\\" is empty\""));
+        assert!(line.contains("\"target\":\"aoc25::logging::tests\""));
+        assert!(line.contains(
+            "\"spans\":[{\"day\":2,\"name\":\"day\"},{\"phase\":\"solve\",\"name\":\"phase\"}]"
+        ));
+        assert!(line.ends_with("}\n"));
+        assert_eq!(LogFormat::from("json"), LogFormat::Json);
+    }
+
+    #[test]
+    fn test_throttled_log_keeps_heaviest() {
+        let mut log = ThrottledLog::with_every("ranges", "invalid IDs", 4);
+        for weight in [3, 9, 1, 4, 1, 5, 9, 2, 6, 5] {
+            log.item(weight, || format!("weight {}", weight));
+        }
+        log.finish();
+        assert_eq!(log.seen, 10);
+        assert_eq!(log.window, 6 + 5);
+        let weights: Vec<u64> = log.top.iter().map(|&(w, _)| w).collect();
+        assert_eq!(weights, [9, 9, 6, 5, 5]);
+        assert_eq!(log.top[0].1, "weight 9");
+
+        let mut every = ThrottledLog::with_every("ranges", "invalid IDs", 1);
+        for weight in 0..3 {
+            every.item(weight, || format!("weight {}", weight));
+        }
+        assert_eq!(every.seen, 3);
+        assert!(every.top.is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc25::answers;
+use aoc25::artifacts::{self, RUNS_DIR};
+use aoc25::bench;
+use aoc25::crash;
+use aoc25::days::day01::trace::{self, Trace};
+use aoc25::days::day01::verify;
+use aoc25::days::day02::density;
+use aoc25::days::day03::compare::Comparison;
+use aoc25::days::day03::html;
+use aoc25::days::day03::read_input_file;
+use aoc25::describe::describe_day;
+use aoc25::error::AocError;
+use aoc25::input;
+use aoc25::run::{RunAllConfig, run_all};
+
+#[derive(clap::Parser, Debug)]
+#[command(name = "aoc25", about = "Advent of Code 2025 helpers")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Print a day's modes, flags, input grammar and example invocations
+    Describe {
+        #[clap(short, long, help = "Day number")]
+        day: u32,
+    },
+    /// Solve every known day from `<dir>/dayNN/input.txt`
+    RunAll(RunAllConfig),
+    /// Remove files left behind by earlier runs
+    Clean {
+        #[clap(long, help = "Remove crash reports from the temp directory")]
+        crash_reports: bool,
+        #[clap(long, help = "Remove run directories written under runs/ by --out-dir")]
+        runs: bool,
+        #[clap(long, help = "Remove benchmark baselines saved under target/baselines")]
+        baselines: bool,
+        #[clap(
+            long,
+            help = "Remove puzzle inputs downloaded to data/dayNN/input.txt and the request time stamp"
+        )]
+        inputs: bool,
+        #[clap(long, help = "Remove the verified answers run-all --record saved")]
+        answers: bool,
+        #[clap(long, help = "Remove day 1 traces from run directories under runs/")]
+        traces: bool,
+        #[clap(
+            long,
+            help = "Remove run reports, HTML pages and density curves from run directories under runs/"
+        )]
+        reports: bool,
+        #[clap(long, help = "Remove everything clean knows about")]
+        all: bool,
+        #[clap(long, help = "List what would be removed without removing it")]
+        dry_run: bool,
+    },
+    /// Day 1 tools that work on saved runs
+    Day01 {
+        #[command(subcommand)]
+        command: Day01Command,
+    },
+    /// Day 3 tools for checking the selection algorithms
+    Day03 {
+        #[command(subcommand)]
+        command: Day03Command,
+    },
+    /// Check a day's fast solver against a slow reference model
+    Verify {
+        #[command(subcommand)]
+        command: VerifyCommand,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum VerifyCommand {
+    /// Compare every rotation from every dial position with turning the
+    /// dial one notch at a time
+    Day01 {
+        #[clap(
+            long,
+            default_value = "1000",
+            help = "Largest rotation argument to check"
+        )]
+        max_argument: u32,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Day01Command {
+    /// Re-render the narrative of a trace written with `day01 --trace`
+    Replay {
+        #[clap(help = "Path to a JSON Lines trace")]
+        trace: String,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Day03Command {
+    /// Run every selection algorithm on one input and print a matrix of
+    /// answers and timings, failing if any disagree
+    Compare {
+        #[clap(
+            short,
+            long,
+            default_value = "data/day03/input.txt",
+            help = "Path to input file"
+        )]
+        input: String,
+        #[clap(
+            short,
+            long,
+            value_delimiter = ',',
+            default_value = "2,12",
+            help = "Digit counts to compare"
+        )]
+        digits: Vec<u32>,
+    },
+}
+
+/// Removes one kind of generated file, or with `true` only lists them.
+type Cleaner = fn(bool) -> io::Result<Vec<PathBuf>>;
+
+fn main() {
+    use clap::Parser;
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Describe { day } => match describe_day(day) {
+            Some(description) => print!("{}", description),
+            None => {
+                eprintln!("No description available for day {}", day);
+                std::process::exit(1);
+            }
+        },
+        Command::RunAll(config) => {
+            if !run_all(&config) {
+                std::process::exit(1);
+            }
+        }
+        Command::Clean {
+            crash_reports,
+            runs,
+            baselines,
+            inputs,
+            answers,
+            traces,
+            reports,
+            all,
+            dry_run,
+        } => {
+            // Run directories go last, so the kinds inside them are counted
+            // before they are removed.
+            let kinds: [(bool, &str, Cleaner); 9] = [
+                (crash_reports, "crash reports", crash::clean),
+                (baselines, "benchmark baselines", bench::clean_baselines),
+                (inputs, "cached inputs", input::clean_cache),
+                (answers, "verified answers", answers::clean),
+                (traces, "traces", |dry_run| {
+                    trace::clean(Path::new(RUNS_DIR), dry_run)
+                }),
+                (reports, "run reports", |dry_run| {
+                    artifacts::clean_reports(Path::new(RUNS_DIR), dry_run)
+                }),
+                (reports, "HTML pages", |dry_run| {
+                    html::clean(Path::new(RUNS_DIR), dry_run)
+                }),
+                (reports, "density curves", |dry_run| {
+                    density::clean(Path::new(RUNS_DIR), dry_run)
+                }),
+                (runs, "run directories", |dry_run| {
+                    artifacts::clean(Path::new(RUNS_DIR), dry_run)
+                }),
+            ];
+            if !kinds.iter().any(|(selected, _, _)| *selected) && !all {
+                eprintln!("Nothing selected to clean, see --help");
+                std::process::exit(1);
+            }
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            for (_, what, clean) in kinds.iter().filter(|(selected, _, _)| *selected || all) {
+                match clean(dry_run) {
+                    Ok(paths) => {
+                        for path in &paths {
+                            println!("{} {}", verb, path.display());
+                        }
+                        println!("{} {} {}", verb, paths.len(), what);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to clean {}: {}", what, e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        Command::Day01 {
+            command: Day01Command::Replay { trace },
+        } => {
+            let replay = std::fs::read_to_string(&trace)
+                .map_err(|e| AocError::io(&trace, e))
+                .and_then(|text| Trace::parse_jsonl(&text).map_err(|e| e.in_file(&trace)));
+            match replay {
+                Ok(replay) => print!("{}", replay.narrate()),
+                Err(e) => {
+                    eprintln!("Failed to replay trace: {}", e);
+                    std::process::exit(e.exit_code());
+                }
+            }
+        }
+        Command::Verify {
+            command: VerifyCommand::Day01 { max_argument },
+        } => {
+            let verification = verify::exhaustive(max_argument);
+            for mismatch in &verification.examples {
+                println!("{}", mismatch);
+            }
+            println!(
+                "{} of {} rotations disagree with the notch model",
+                verification.failed, verification.checked
+            );
+            if !verification.passed() {
+                std::process::exit(1);
+            }
+        }
+        Command::Day03 {
+            command: Day03Command::Compare { input, digits },
+        } => {
+            let lines = match read_input_file(&input).map_err(|e| e.in_file(&input)) {
+                Ok(lines) => lines,
+                Err(e) => {
+                    eprintln!("Failed to read input: {}", e);
+                    std::process::exit(e.exit_code());
+                }
+            };
+            let comparison = Comparison::run(&lines, &digits);
+            print!("{}", comparison);
+            let disagreements = comparison.disagreements();
+            if !disagreements.is_empty() {
+                for disagreement in disagreements {
+                    eprintln!("Selectors disagree at {}", disagreement);
+                }
+                std::process::exit(1);
+            }
+        }
+    }
+}
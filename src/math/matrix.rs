@@ -0,0 +1,193 @@
+use std::fmt;
+
+/// Dense integer matrix for linear recurrences taken modulo some `m`.
+///
+/// A state vector `v` updated by `v' = A v (mod m)` reaches step `n` as
+/// `A^n v`, which `pow_mod` computes in `O(log n)` multiplications. Affine
+/// updates such as day01's "rotate the dial by the net offset of one pass
+/// of the instructions" fit by appending a constant `1` to the state:
+///
+/// ```
+/// use aoc25::math::Matrix;
+///
+/// // position' = position + 37 (mod 100), as [position, 1]
+/// let pass = Matrix::from_rows(vec![vec![1, 37], vec![0, 1]]);
+/// let repeated = pass.pow_mod(1_000_000_000_000, 100);
+/// assert_eq!(repeated.apply_mod(&[50, 1], 100), vec![50, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<u64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![0; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, 1);
+        }
+        m
+    }
+
+    /// Panics if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<u64>>) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        assert!(
+            rows.iter().all(|r| r.len() == cols),
+            "matrix rows must have equal length"
+        );
+        Matrix {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> u64 {
+        self.data[row * self.cols + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: u64) {
+        self.data[row * self.cols + col] = value;
+    }
+
+    pub fn mul_mod(&self, other: &Matrix, modulus: u64) -> Matrix {
+        assert_eq!(self.cols, other.rows, "matrix dimensions do not match");
+        assert!(modulus > 0, "modulus must be positive");
+        let m = modulus as u128;
+        let mut out = Matrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self.get(i, k) as u128;
+                if a == 0 {
+                    continue;
+                }
+                for j in 0..other.cols {
+                    let sum = (out.get(i, j) as u128 + a * other.get(k, j) as u128) % m;
+                    out.set(i, j, sum as u64);
+                }
+            }
+        }
+        out
+    }
+
+    /// `self^exp (mod modulus)` by repeated squaring. Panics unless square.
+    pub fn pow_mod(&self, mut exp: u64, modulus: u64) -> Matrix {
+        assert_eq!(self.rows, self.cols, "only square matrices have powers");
+        let mut result = Matrix::identity(self.rows).reduce(modulus);
+        let mut base = self.reduce(modulus);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul_mod(&base, modulus);
+            }
+            base = base.mul_mod(&base, modulus);
+            exp >>= 1;
+        }
+        result
+    }
+
+    fn reduce(&self, modulus: u64) -> Matrix {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|v| v % modulus).collect(),
+        }
+    }
+
+    pub fn apply_mod(&self, vector: &[u64], modulus: u64) -> Vec<u64> {
+        let column = Matrix {
+            rows: vector.len(),
+            cols: 1,
+            data: vector.to_vec(),
+        };
+        self.mul_mod(&column, modulus).data
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.data.chunks(self.cols.max(1)) {
+            let cells: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(f, "[{}]", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_mod() {
+        let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let b = Matrix::from_rows(vec![vec![5, 6], vec![7, 8]]);
+        assert_eq!(
+            a.mul_mod(&b, 1000),
+            Matrix::from_rows(vec![vec![19, 22], vec![43, 50]])
+        );
+        assert_eq!(
+            a.mul_mod(&b, 10),
+            Matrix::from_rows(vec![vec![9, 2], vec![3, 0]])
+        );
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        let fib = Matrix::from_rows(vec![vec![1, 1], vec![1, 0]]);
+        assert_eq!(fib.pow_mod(10, u64::MAX).get(0, 1), 55);
+        assert_eq!(
+            fib.pow_mod(90, u64::MAX).get(0, 1),
+            2_880_067_194_370_816_120
+        );
+        // Pisano period for 10 is 60.
+        assert_eq!(fib.pow_mod(60, 10), Matrix::identity(2));
+    }
+
+    #[test]
+    fn test_pow_zero_and_modulus_one() {
+        let m = Matrix::from_rows(vec![vec![2, 3], vec![5, 7]]);
+        assert_eq!(m.pow_mod(0, 100), Matrix::identity(2));
+        assert_eq!(m.pow_mod(5, 1), Matrix::zeros(2, 2));
+    }
+
+    #[test]
+    fn test_affine_repeat_matches_iteration() {
+        let step = Matrix::from_rows(vec![vec![3, 7], vec![0, 1]]);
+        let mut value = 5u64;
+        for _ in 0..1000 {
+            value = (3 * value + 7) % 1009;
+        }
+        assert_eq!(
+            step.pow_mod(1000, 1009).apply_mod(&[5, 1], 1009),
+            vec![value, 1]
+        );
+    }
+
+    #[test]
+    fn test_large_entries_do_not_overflow() {
+        let big = u64::MAX - 1;
+        let m = Matrix::from_rows(vec![vec![big, big], vec![big, big]]);
+        let squared = m.mul_mod(&m, u64::MAX);
+        // (-1)^2 * 2 = 2 mod (u64::MAX)
+        assert_eq!(squared.get(0, 0), 2);
+    }
+}
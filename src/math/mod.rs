@@ -0,0 +1,327 @@
+use std::ops::RangeInclusive;
+
+use crate::error::AocError;
+use crate::result::AocResult;
+
+mod matrix;
+mod rational;
+mod ring;
+
+pub use matrix::Matrix;
+pub use rational::Rational;
+pub use ring::ModRing;
+
+fn checked_sum_u128(start: u128, end: u128) -> Option<u128> {
+    if start > end {
+        return Some(0);
+    }
+    let count = end - start + 1;
+    let ends = start.checked_add(end)?;
+    // One of count and start + end is always even, so halve that one first.
+    if count.is_multiple_of(2) {
+        (count / 2).checked_mul(ends)
+    } else {
+        count.checked_mul(ends / 2)
+    }
+}
+
+fn widen(range: &RangeInclusive<u64>) -> RangeInclusive<u128> {
+    *range.start() as u128..=*range.end() as u128
+}
+
+/// Sum of every integer in `range`; an empty range sums to 0.
+///
+/// Panics if the sum does not fit in a `u64`; see `checked_sum_range` and
+/// `sum_range_u128`.
+pub fn sum_range(range: RangeInclusive<u64>) -> u64 {
+    checked_sum_range(range).expect("sum_range overflowed u64")
+}
+
+pub fn checked_sum_range(range: RangeInclusive<u64>) -> Option<u64> {
+    let sum = checked_sum_u128(*range.start() as u128, *range.end() as u128)?;
+    u64::try_from(sum).ok()
+}
+
+pub fn sum_range_u128(range: RangeInclusive<u128>) -> u128 {
+    checked_sum_u128(*range.start(), *range.end()).expect("sum_range_u128 overflowed")
+}
+
+/// `a + b` for a solver's running total of `what`. Built with the `checked`
+/// feature an overflow is an `AocError::Overflow`; without it this is plain
+/// `+`, which panics in debug builds and wraps in release.
+#[inline]
+pub fn add(a: u64, b: u64, what: &str) -> AocResult<u64> {
+    if cfg!(feature = "checked") {
+        a.checked_add(b)
+            .ok_or_else(|| overflow(what, format!("{} + {}", a, b)))
+    } else {
+        Ok(a + b)
+    }
+}
+
+/// `num` with the decimal `digit` appended, checked like `add`.
+#[inline]
+pub fn push_digit(num: u64, digit: u64, what: &str) -> AocResult<u64> {
+    push_digit_in(num, digit, 10, what)
+}
+
+/// `num` with `digit` appended in base `radix`, checked like `add`.
+#[inline]
+pub fn push_digit_in(num: u64, digit: u64, radix: u64, what: &str) -> AocResult<u64> {
+    if cfg!(feature = "checked") {
+        num.checked_mul(radix)
+            .and_then(|n| n.checked_add(digit))
+            .ok_or_else(|| overflow(what, format!("{} * {} + {}", num, radix, digit)))
+    } else {
+        Ok(num * radix + digit)
+    }
+}
+
+/// The error for `what` no longer fitting in a `u64` at `step`.
+pub fn overflow(what: &str, step: String) -> AocError {
+    AocError::Overflow(format!("{} does not fit in a u64 at {}", what, step))
+}
+
+/// The multiples of `k` inside `range`, as the range of their quotients.
+fn multiple_quotients(k: u128, range: RangeInclusive<u128>) -> RangeInclusive<u128> {
+    assert!(k > 0, "multiples of zero");
+    let first = range.start().div_ceil(k);
+    let last = range.end() / k;
+    first..=last
+}
+
+pub fn count_multiples_in_range(k: u64, range: RangeInclusive<u64>) -> u64 {
+    let quotients = multiple_quotients(k as u128, widen(&range));
+    if quotients.is_empty() {
+        0
+    } else {
+        (quotients.end() - quotients.start() + 1) as u64
+    }
+}
+
+/// Sum of all multiples of `k` inside `range`. Panics if `k` is zero.
+pub fn sum_multiples_in_range(k: u64, range: RangeInclusive<u64>) -> u64 {
+    checked_sum_multiples_in_range(k, range).expect("sum_multiples_in_range overflowed u64")
+}
+
+pub fn checked_sum_multiples_in_range(k: u64, range: RangeInclusive<u64>) -> Option<u64> {
+    let sum = checked_sum_multiples_u128(k as u128, widen(&range))?;
+    u64::try_from(sum).ok()
+}
+
+pub fn sum_multiples_in_range_u128(k: u128, range: RangeInclusive<u128>) -> u128 {
+    checked_sum_multiples_u128(k, range).expect("sum_multiples_in_range_u128 overflowed")
+}
+
+fn checked_sum_multiples_u128(k: u128, range: RangeInclusive<u128>) -> Option<u128> {
+    let quotients = multiple_quotients(k, range);
+    checked_sum_u128(*quotients.start(), *quotients.end())?.checked_mul(k)
+}
+
+/// The multiplier that repeats a `period`-digit block `reps` times, e.g.
+/// `repeat_multiplier(3, 2) == 1001` because `123 * 1001 == 123123`.
+pub fn repeat_multiplier(period: u32, reps: u32) -> Option<u128> {
+    let shift = 10u128.checked_pow(period)?;
+    let mut multiplier: u128 = 0;
+    for _ in 0..reps {
+        multiplier = multiplier.checked_mul(shift)?.checked_add(1)?;
+    }
+    Some(multiplier)
+}
+
+/// The blocks of exactly `period` digits whose `reps`-fold repetition falls
+/// inside `range`, along with the repeat multiplier.
+fn repeated_blocks(
+    period: u32,
+    reps: u32,
+    range: RangeInclusive<u128>,
+) -> Option<(u128, RangeInclusive<u128>)> {
+    assert!(period > 0, "blocks must have at least one digit");
+    let multiplier = repeat_multiplier(period, reps)?;
+    let smallest = 10u128.checked_pow(period - 1)?;
+    let largest = 10u128.checked_pow(period)? - 1;
+    let blocks = multiple_quotients(multiplier, range);
+    let start = (*blocks.start()).max(smallest);
+    let end = (*blocks.end()).min(largest);
+    Some((multiplier, start..=end))
+}
+
+/// Number of values in `range` made of one `period`-digit block (without a
+/// leading zero) repeated `reps` times.
+pub fn count_repeated_blocks(period: u32, reps: u32, range: RangeInclusive<u64>) -> u64 {
+    match repeated_blocks(period, reps, widen(&range)) {
+        Some((_, blocks)) if !blocks.is_empty() => (blocks.end() - blocks.start() + 1) as u64,
+        _ => 0,
+    }
+}
+
+/// Sum of the values counted by `count_repeated_blocks`.
+pub fn sum_repeated_blocks(period: u32, reps: u32, range: RangeInclusive<u64>) -> u64 {
+    checked_sum_repeated_blocks(period, reps, range).expect("sum_repeated_blocks overflowed u64")
+}
+
+pub fn checked_sum_repeated_blocks(
+    period: u32,
+    reps: u32,
+    range: RangeInclusive<u64>,
+) -> Option<u64> {
+    let sum = checked_sum_repeated_blocks_u128(period, reps, widen(&range))?;
+    u64::try_from(sum).ok()
+}
+
+pub fn sum_repeated_blocks_u128(period: u32, reps: u32, range: RangeInclusive<u128>) -> u128 {
+    checked_sum_repeated_blocks_u128(period, reps, range)
+        .expect("sum_repeated_blocks_u128 overflowed")
+}
+
+fn checked_sum_repeated_blocks_u128(
+    period: u32,
+    reps: u32,
+    range: RangeInclusive<u128>,
+) -> Option<u128> {
+    match repeated_blocks(period, reps, range) {
+        // A multiplier too large for u128 means no repetition fits in range.
+        None => Some(0),
+        Some((multiplier, blocks)) => {
+            checked_sum_u128(*blocks.start(), *blocks.end())?.checked_mul(multiplier)
+        }
+    }
+}
+
+/// Greatest common divisor, always non-negative; `gcd(0, 0) == 0`.
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).expect("gcd of i128::MIN and 0 overflows i128")
+}
+
+/// Least common multiple, or `None` if it overflows.
+pub fn checked_lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(i128::abs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_repeated(period: u32, reps: u32, range: RangeInclusive<u64>) -> (u64, u64) {
+        range
+            .filter(|&n| {
+                let s = n.to_string();
+                s.len() == (period * reps) as usize
+                    && s[..period as usize].repeat(reps as usize) == s
+            })
+            .fold((0, 0), |(count, sum), n| (count + 1, sum + n))
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(checked_lcm(4, 6), Some(12));
+        assert_eq!(checked_lcm(-4, 6), Some(12));
+        assert_eq!(checked_lcm(i128::MAX, i128::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_sum_range() {
+        assert_eq!(sum_range(1..=100), 5050);
+        assert_eq!(sum_range(7..=7), 7);
+        assert_eq!(sum_range(RangeInclusive::new(10, 3)), 0);
+        assert_eq!(sum_range(0..=0), 0);
+        assert_eq!(sum_range(3..=6), 18);
+    }
+
+    #[test]
+    fn test_sum_range_overflow() {
+        assert_eq!(checked_sum_range(0..=u64::MAX), None);
+        assert_eq!(checked_sum_range(u64::MAX..=u64::MAX), Some(u64::MAX));
+        let big = sum_range_u128(0..=u64::MAX as u128);
+        assert_eq!(big, (u64::MAX as u128) * (u64::MAX as u128 + 1) / 2);
+    }
+
+    #[test]
+    fn test_add_and_push_digit() {
+        assert_eq!(add(2, 3, "sum").ok(), Some(5));
+        assert_eq!(push_digit(98, 7, "number").ok(), Some(987));
+        assert_eq!(push_digit(u64::MAX / 10, 5, "number").ok(), Some(u64::MAX));
+        assert_eq!(push_digit_in(0xab, 0xc, 16, "number").ok(), Some(0xabc));
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_checked_overflow() {
+        assert!(matches!(
+            add(u64::MAX, 1, "sum"),
+            Err(AocError::Overflow(_))
+        ));
+        assert!(matches!(
+            push_digit(u64::MAX / 10, 6, "number"),
+            Err(AocError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_multiples() {
+        assert_eq!(count_multiples_in_range(3, 1..=10), 3);
+        assert_eq!(sum_multiples_in_range(3, 1..=10), 3 + 6 + 9);
+        assert_eq!(sum_multiples_in_range(5, 11..=14), 0);
+        assert_eq!(count_multiples_in_range(5, 11..=14), 0);
+        assert_eq!(sum_multiples_in_range(7, 0..=7), 7);
+        assert_eq!(checked_sum_multiples_in_range(1, 0..=u64::MAX), None);
+        assert_eq!(sum_multiples_in_range_u128(10, 1..=100), 550);
+    }
+
+    #[test]
+    fn test_repeat_multiplier() {
+        assert_eq!(repeat_multiplier(1, 2), Some(11));
+        assert_eq!(repeat_multiplier(3, 2), Some(1001));
+        assert_eq!(repeat_multiplier(2, 3), Some(10101));
+        assert_eq!(repeat_multiplier(20, 3), None);
+    }
+
+    #[test]
+    fn test_repeated_blocks_examples() {
+        // 11-22 contains 11 and 22; 95-115 contains 99.
+        assert_eq!(count_repeated_blocks(1, 2, 11..=22), 2);
+        assert_eq!(sum_repeated_blocks(1, 2, 11..=22), 33);
+        assert_eq!(sum_repeated_blocks(1, 2, 95..=115), 99);
+        assert_eq!(sum_repeated_blocks(2, 2, 1188511880..=1188511890), 0);
+        assert_eq!(
+            sum_repeated_blocks(5, 2, 1188511880..=1188511890),
+            1188511885
+        );
+    }
+
+    #[test]
+    fn test_repeated_blocks_match_brute_force() {
+        for (period, reps) in [(1, 2), (1, 3), (2, 2), (1, 4), (2, 3), (3, 2)] {
+            for range in [0..=100_000, 1000..=1010, 9..=12, 123..=98765] {
+                let expected = brute_repeated(period, reps, range.clone());
+                let actual = (
+                    count_repeated_blocks(period, reps, range.clone()),
+                    sum_repeated_blocks(period, reps, range.clone()),
+                );
+                assert_eq!(
+                    actual, expected,
+                    "period {} reps {} {:?}",
+                    period, reps, range
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_repeated_blocks_overflow() {
+        assert_eq!(checked_sum_repeated_blocks(10, 2, 0..=u64::MAX), None);
+        assert_eq!(sum_repeated_blocks(20, 2, 0..=u64::MAX), 0);
+        assert!(sum_repeated_blocks_u128(10, 2, 0..=u64::MAX as u128) > u64::MAX as u128);
+    }
+}
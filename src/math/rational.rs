@@ -0,0 +1,362 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use super::{checked_lcm, gcd};
+use crate::error::AocError;
+
+/// Exact fraction kept in lowest terms with a positive denominator, so the
+/// derived equality and hashing agree with numeric equality.
+///
+/// The checked operations return `None` on `i128` overflow; the operator
+/// impls panic instead, like the primitive integer types do in debug builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Panics if `den` is zero or the normalised value overflows.
+    pub fn new(num: i128, den: i128) -> Self {
+        Self::checked_new(num, den).expect("invalid or overflowing rational")
+    }
+
+    pub fn checked_new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd(num, den);
+        let (mut num, mut den) = (num / g, den / g);
+        if den < 0 {
+            num = num.checked_neg()?;
+            den = den.checked_neg()?;
+        }
+        Some(Rational { num, den })
+    }
+
+    pub const fn from_integer(n: i128) -> Self {
+        Rational { num: n, den: 1 }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    pub fn floor(&self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    pub fn ceil(&self) -> i128 {
+        -(-self.num).div_euclid(self.den)
+    }
+
+    pub fn abs(&self) -> Self {
+        Rational {
+            num: self.num.abs(),
+            den: self.den,
+        }
+    }
+
+    pub fn signum(&self) -> i128 {
+        self.num.signum()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Value scaled by `10^decimals` and rounded down, i.e. a fixed-point
+    /// representation with that many decimal places.
+    pub fn to_fixed(&self, decimals: u32) -> Option<i128> {
+        let scaled = self.checked_mul(Rational::from_integer(10i128.checked_pow(decimals)?))?;
+        Some(scaled.floor())
+    }
+
+    /// Inverse of `to_fixed`: `from_fixed(1250, 3) == 5/4`.
+    pub fn from_fixed(value: i128, decimals: u32) -> Option<Self> {
+        Self::checked_new(value, 10i128.checked_pow(decimals)?)
+    }
+
+    pub fn recip(&self) -> Option<Self> {
+        Self::checked_new(self.den, self.num)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let den = checked_lcm(self.den, rhs.den)?;
+        let a = self.num.checked_mul(den / self.den)?;
+        let b = rhs.num.checked_mul(den / rhs.den)?;
+        Self::checked_new(a.checked_add(b)?, den)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // Cancel across the diagonals first to keep intermediates small.
+        let g1 = gcd(self.num, rhs.den).max(1);
+        let g2 = gcd(rhs.num, self.den).max(1);
+        let num = (self.num / g1).checked_mul(rhs.num / g2)?;
+        let den = (self.den / g2).checked_mul(rhs.den / g1)?;
+        Self::checked_new(num, den)
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs.recip()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Rational {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational::from_integer(n)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational::from_integer(n as i128)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare continued-fraction terms so no product can overflow.
+        let (mut a_num, mut a_den) = (self.num, self.den);
+        let (mut b_num, mut b_den) = (other.num, other.den);
+        let mut flipped = false;
+        loop {
+            let (qa, ra) = (a_num.div_euclid(a_den), a_num.rem_euclid(a_den));
+            let (qb, rb) = (b_num.div_euclid(b_den), b_num.rem_euclid(b_den));
+            let ordering = match (qa.cmp(&qb), ra, rb) {
+                (Ordering::Equal, 0, 0) => Ordering::Equal,
+                (Ordering::Equal, 0, _) => Ordering::Less,
+                (Ordering::Equal, _, 0) => Ordering::Greater,
+                (Ordering::Equal, _, _) => {
+                    // ra/a_den vs rb/b_den is the reverse of a_den/ra vs b_den/rb.
+                    (a_num, a_den, b_num, b_den) = (a_den, ra, b_den, rb);
+                    flipped = !flipped;
+                    continue;
+                }
+                (ordering, _, _) => ordering,
+            };
+            return if flipped {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        self.checked_add(rhs).expect("rational addition overflowed")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self.checked_sub(rhs)
+            .expect("rational subtraction overflowed")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        self.checked_mul(rhs)
+            .expect("rational multiplication overflowed")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Rational {
+        self.checked_div(rhs)
+            .expect("rational division by zero or overflow")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        self.checked_neg().expect("rational negation overflowed")
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Parses `n`, `n/d` or a decimal such as `-1.25`.
+impl FromStr for Rational {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || AocError::ParseError(format!("invalid rational '{}'", s));
+        let int = |t: &str| t.parse::<i128>().map_err(|_| invalid());
+        if let Some((num, den)) = s.split_once('/') {
+            return Rational::checked_new(int(num)?, int(den)?).ok_or_else(invalid);
+        }
+        match s.split_once('.') {
+            Some((whole, frac)) => {
+                if frac.is_empty() || !frac.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                let digits = format!("{}{}", whole, frac);
+                Rational::from_fixed(int(&digits)?, frac.len() as u32).ok_or_else(invalid)
+            }
+            None => Ok(Rational::from_integer(int(s)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn test_normalisation() {
+        assert_eq!(r(2, 4), r(1, 2));
+        assert_eq!(r(1, -2), r(-1, 2));
+        assert_eq!(r(-3, -6), r(1, 2));
+        assert_eq!(r(0, -5), Rational::ZERO);
+        assert_eq!(r(-1, 2).denom(), 2);
+        assert_eq!(Rational::checked_new(1, 0), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(1, 3), r(1, 6));
+        assert_eq!(r(2, 3) * r(3, 4), r(1, 2));
+        assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+        assert_eq!(-r(2, 3), r(-2, 3));
+        assert_eq!(r(7, 1).to_integer(), Some(7));
+        assert_eq!(r(7, 2).to_integer(), None);
+        assert_eq!(Rational::ZERO.recip(), None);
+        assert_eq!(r(1, 3).checked_div(Rational::ZERO), None);
+    }
+
+    #[test]
+    fn test_floor_ceil() {
+        assert_eq!(r(7, 2).floor(), 3);
+        assert_eq!(r(7, 2).ceil(), 4);
+        assert_eq!(r(-7, 2).floor(), -4);
+        assert_eq!(r(-7, 2).ceil(), -3);
+        assert_eq!(r(4, 2).ceil(), 2);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(5, 3) > r(3, 2));
+        assert_eq!(r(2, 4).cmp(&r(1, 2)), Ordering::Equal);
+        let mut values = vec![r(3, 4), r(-1, 1), r(1, 3), r(2, 3)];
+        values.sort();
+        assert_eq!(values, vec![r(-1, 1), r(1, 3), r(2, 3), r(3, 4)]);
+    }
+
+    #[test]
+    fn test_ordering_without_overflow() {
+        let big = i128::MAX;
+        let a = r(big - 1, big);
+        let b = r(big - 2, big - 1);
+        assert!(a > b);
+        assert!(r(big, big - 1) > Rational::ONE);
+        assert!(r(i128::MIN + 1, big) < r(-1, 2));
+    }
+
+    #[test]
+    fn test_overflow_is_checked() {
+        let big = Rational::from_integer(i128::MAX);
+        assert_eq!(big.checked_add(Rational::ONE), None);
+        assert_eq!(big.checked_mul(r(2, 1)), None);
+        assert_eq!(big.checked_mul(r(1, 2)), Some(r(i128::MAX, 2)));
+        assert_eq!(Rational::from_integer(i128::MIN).checked_neg(), None);
+        assert_eq!(r(1, i128::MAX).checked_add(r(1, i128::MAX - 1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn test_operator_overflow_panics() {
+        let _ = Rational::from_integer(i128::MAX) + Rational::ONE;
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        assert_eq!(r(5, 4).to_fixed(3), Some(1250));
+        assert_eq!(r(1, 3).to_fixed(4), Some(3333));
+        assert_eq!(r(-1, 3).to_fixed(2), Some(-34));
+        assert_eq!(Rational::from_fixed(1250, 3), Some(r(5, 4)));
+        assert_eq!(r(1, 3).to_fixed(40), None);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!("3/6".parse::<Rational>().expect("rational"), r(1, 2));
+        assert_eq!("-1.25".parse::<Rational>().expect("rational"), r(-5, 4));
+        assert_eq!("42".parse::<Rational>().expect("rational"), r(42, 1));
+        assert!("1/0".parse::<Rational>().is_err());
+        assert!("1.".parse::<Rational>().is_err());
+        assert!("x".parse::<Rational>().is_err());
+        assert_eq!(r(-5, 4).to_string(), "-5/4");
+        assert_eq!(r(8, 4).to_string(), "2");
+        assert!((r(1, 4).to_f64() - 0.25).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,79 @@
+/// `value + delta` on a ring of `modulus` values, with how many times the
+/// result wrapped from `modulus - 1` round to 0.
+fn wrapping_add(value: u32, delta: u32, modulus: u32) -> (u32, u32) {
+    let total = value as u64 + delta as u64;
+    let modulus = modulus as u64;
+    ((total % modulus) as u32, (total / modulus) as u32)
+}
+
+/// `value - delta` on a ring of `modulus` values, with how many times the
+/// result wrapped from 0 round to `modulus - 1`.
+fn wrapping_sub(value: u32, delta: u32, modulus: u32) -> (u32, u32) {
+    let (value, delta, modulus) = (value as u64, delta as u64, modulus as u64);
+    if delta <= value {
+        return ((value - delta) as u32, 0);
+    }
+    let wraps = (delta - value).div_ceil(modulus);
+    ((value + wraps * modulus - delta) as u32, wraps as u32)
+}
+
+/// A value in `0..modulus` whose additions and subtractions wrap around
+/// and say how often they did. The modulus is a run-time value so one type
+/// covers the puzzle's dial and any other `--dial-size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModRing {
+    value: u32,
+    modulus: u32,
+}
+
+impl ModRing {
+    /// `value` reduced into `0..modulus`. `modulus` must not be 0.
+    pub fn new(value: u32, modulus: u32) -> Self {
+        ModRing {
+            value: value % modulus,
+            modulus,
+        }
+    }
+
+    pub fn value(self) -> u32 {
+        self.value
+    }
+
+    pub fn modulus(self) -> u32 {
+        self.modulus
+    }
+
+    /// `self + delta`, with how many times it wrapped past `modulus - 1`.
+    pub fn wrapping_add(self, delta: u32) -> (Self, u32) {
+        let (value, wraps) = wrapping_add(self.value, delta, self.modulus);
+        (ModRing { value, ..self }, wraps)
+    }
+
+    /// `self - delta`, with how many times it wrapped past 0.
+    pub fn wrapping_sub(self, delta: u32) -> (Self, u32) {
+        let (value, wraps) = wrapping_sub(self.value, delta, self.modulus);
+        (ModRing { value, ..self }, wraps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mod_ring() {
+        let dial = ModRing::new(250, 100);
+        assert_eq!((dial.value(), dial.modulus()), (50, 100));
+        assert_eq!(dial.wrapping_add(49), (ModRing::new(99, 100), 0));
+        assert_eq!(dial.wrapping_add(50), (ModRing::new(0, 100), 1));
+        assert_eq!(dial.wrapping_add(1050), (ModRing::new(0, 100), 11));
+        assert_eq!(dial.wrapping_sub(50), (ModRing::new(0, 100), 0));
+        assert_eq!(dial.wrapping_sub(51), (ModRing::new(99, 100), 1));
+        assert_eq!(dial.wrapping_sub(250), (ModRing::new(0, 100), 2));
+        assert_eq!(
+            ModRing::new(0, 7).wrapping_sub(u32::MAX),
+            (ModRing::new(4, 7), 613_566_757)
+        );
+        assert_eq!(wrapping_add(u32::MAX, u32::MAX, 2), (0, u32::MAX));
+    }
+}
@@ -0,0 +1,268 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// How often and how patiently `retry` tries again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts in all, including the first.
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for each one after.
+    pub base_delay: Duration,
+    /// Longest wait between attempts. A server's `Retry-After` asking for
+    /// longer is cut down to this, so a bad value can't hang a client.
+    pub max_delay: Duration,
+    /// Up to this fraction of each wait is taken off at random, so
+    /// clients that failed together don't retry together.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The wait after the `retry`th failure, counting from 0, given a
+    /// random `r` in `0..1`.
+    pub fn delay(&self, retry: u32, r: f64) -> Duration {
+        let factor = 2u32.saturating_pow(retry);
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        delay.mul_f64(1.0 - self.jitter * r)
+    }
+}
+
+/// A failed attempt, and whether trying again could help.
+#[derive(Debug)]
+pub struct Failure {
+    pub error: AocError,
+    pub retryable: bool,
+    /// How long the server asked to wait, from `Retry-After`.
+    pub retry_after: Option<Duration>,
+}
+
+impl Failure {
+    /// A failure that trying again won't fix.
+    pub fn permanent(error: AocError) -> Self {
+        Failure {
+            error,
+            retryable: false,
+            retry_after: None,
+        }
+    }
+
+    /// A failure that may pass, such as a timeout or a 503.
+    pub fn transient(error: AocError) -> Self {
+        Failure {
+            error,
+            retryable: true,
+            retry_after: None,
+        }
+    }
+}
+
+/// Where `retry` waits and gets its randomness, so tests can stand in a
+/// clock that doesn't sleep.
+pub trait Clock {
+    fn sleep(&mut self, duration: Duration);
+
+    /// A number in `0..1`.
+    fn random(&mut self) -> f64;
+}
+
+/// Sleeps for real, with jitter from a xorshift generator seeded from the
+/// time.
+pub struct SystemClock {
+    state: u64,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        SystemClock { state: nanos | 1 }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    fn random(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails permanently or has been tried
+/// `policy.max_attempts` times, waiting with exponential backoff between
+/// tries, or as long as the server's `Retry-After` asked up to
+/// `policy.max_delay`. Returns the last error if every attempt failed.
+pub fn retry<T>(
+    policy: &RetryPolicy,
+    clock: &mut dyn Clock,
+    mut attempt: impl FnMut() -> Result<T, Failure>,
+) -> AocResult<T> {
+    let mut tried = 0;
+    loop {
+        let failure = match attempt() {
+            Ok(value) => return Ok(value),
+            Err(failure) => failure,
+        };
+        tried += 1;
+        if !failure.retryable || tried >= policy.max_attempts {
+            return Err(failure.error);
+        }
+        let wait = match failure.retry_after {
+            Some(wait) => wait.min(policy.max_delay),
+            None => policy.delay(tried - 1, clock.random()),
+        };
+        tracing::warn!(
+            "Attempt {} of {} failed, retrying in {:.1?}: {}",
+            tried,
+            policy.max_attempts,
+            wait,
+            failure.error
+        );
+        clock.sleep(wait);
+    }
+}
+
+/// The wait a `Retry-After` header value asks for, if given in seconds.
+/// HTTP dates are not supported.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records sleeps instead of sleeping and always draws `r`.
+    struct MockClock {
+        slept: Vec<Duration>,
+        r: f64,
+    }
+
+    impl Clock for MockClock {
+        fn sleep(&mut self, duration: Duration) {
+            self.slept.push(duration);
+        }
+
+        fn random(&mut self) -> f64 {
+            self.r
+        }
+    }
+
+    fn failing(retry_after: Option<u64>) -> Failure {
+        Failure {
+            error: AocError::IoError("503".to_string()),
+            retryable: true,
+            retry_after: retry_after.map(Duration::from_secs),
+        }
+    }
+
+    #[test]
+    fn test_backoff_until_success() {
+        let mut clock = MockClock {
+            slept: vec![],
+            r: 0.0,
+        };
+        let mut calls = 0;
+        let result = retry(&RetryPolicy::default(), &mut clock, || {
+            calls += 1;
+            if calls < 4 {
+                Err(failing(None))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.expect("fourth attempt succeeds"), 4);
+        let secs: Vec<u64> = clock.slept.iter().map(Duration::as_secs).collect();
+        assert_eq!(secs, [1, 2, 4]);
+    }
+
+    #[test]
+    fn test_gives_up() {
+        let mut clock = MockClock {
+            slept: vec![],
+            r: 1.0,
+        };
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            ..RetryPolicy::default()
+        };
+        let result: AocResult<()> = retry(&policy, &mut clock, || Err(failing(None)));
+        assert!(matches!(result, Err(AocError::IoError(_))));
+        assert_eq!(
+            clock.slept,
+            [Duration::from_millis(500), Duration::from_secs(1)]
+        );
+
+        let mut calls = 0;
+        let result: AocResult<()> = retry(&policy, &mut clock, || {
+            calls += 1;
+            Err(Failure::permanent(AocError::IoError("404".to_string())))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_honours_retry_after() {
+        let mut clock = MockClock {
+            slept: vec![],
+            r: 0.0,
+        };
+        let mut calls = 0;
+        let result = retry(&RetryPolicy::default(), &mut clock, || {
+            calls += 1;
+            if calls == 1 {
+                Err(failing(Some(45)))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(clock.slept, [Duration::from_secs(45)]);
+        assert_eq!(parse_retry_after(" 120 "), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_retry_after_is_capped() {
+        let mut clock = MockClock {
+            slept: vec![],
+            r: 0.0,
+        };
+        let policy = RetryPolicy::default();
+        let result: AocResult<()> = retry(&policy, &mut clock, || Err(failing(Some(u64::MAX))));
+        assert!(result.is_err());
+        assert_eq!(clock.slept, [policy.max_delay; 3]);
+    }
+
+    #[test]
+    fn test_delay_is_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(10, 0.0), policy.max_delay);
+        assert_eq!(policy.delay(40, 0.0), policy.max_delay);
+        assert!(SystemClock::new().random() < 1.0);
+    }
+}
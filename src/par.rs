@@ -0,0 +1,33 @@
+use rayon::prelude::*;
+
+/// Number of worker threads to use, one per available core.
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Applies `f` to every item on rayon's thread pool.
+///
+/// Results come back in the order of `items` however the threads finish,
+/// so anything rendered from them afterwards is identical to a sequential
+/// run. Callers collect first and print second; workers never print.
+pub fn map_ordered<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_ordered() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = map_ordered(&items, |&n| n * n);
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert!(map_ordered(&[] as &[u64], |&n| n).is_empty());
+    }
+}
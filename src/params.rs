@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// Extra day-specific knobs as `key=value` pairs, e.g. `digits=4`, so
+/// front ends can pass them through without knowing each day's options.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a comma-separated `key=value` list. Blank input gives no
+    /// params.
+    pub fn parse(text: &str) -> AocResult<Self> {
+        let mut params = Params::new();
+        for pair in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or_else(|| {
+                AocError::InvalidArgument(format!("param '{}' is not key=value", pair))
+            })?;
+            params.set(key.trim(), value.trim());
+        }
+        Ok(params)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// The value of `key` parsed as `T`, or `None` if it is not set.
+    pub fn get<T: FromStr>(&self, key: &str) -> AocResult<Option<T>> {
+        self.get_str(key)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    AocError::InvalidArgument(format!(
+                        "invalid value '{}' for param {}",
+                        value, key
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> AocResult<T> {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+
+    /// Fails on any key not in `known`, so a misspelt knob is not silently
+    /// ignored.
+    pub fn check_known(&self, known: &[&str]) -> AocResult<()> {
+        match self
+            .values
+            .keys()
+            .find(|key| !known.contains(&key.as_str()))
+        {
+            Some(key) if known.is_empty() => Err(AocError::InvalidArgument(format!(
+                "unknown param {}, this day takes none",
+                key
+            ))),
+            Some(key) => Err(AocError::InvalidArgument(format!(
+                "unknown param {}, expected one of {}",
+                key,
+                known.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Params {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> AocResult<Self> {
+        Params::parse(s)
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let params = Params::parse("digits=4, start = 20").expect("params");
+        assert_eq!(params.get::<u32>("digits").expect("digits"), Some(4));
+        assert_eq!(params.get_or("start", 50u32).expect("start"), 20);
+        assert_eq!(params.get_or("missing", 7u32).expect("default"), 7);
+        assert!(params.get::<u32>("missing").expect("missing").is_none());
+        assert_eq!(params.to_string(), "digits=4,start=20");
+        assert!(Params::parse("").expect("empty").is_empty());
+        assert!(Params::parse("digits").is_err());
+        assert!(
+            Params::parse("digits=x")
+                .expect("params")
+                .get::<u32>("digits")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_known() {
+        let params = Params::parse("digits=4").expect("params");
+        assert!(params.check_known(&["digits"]).is_ok());
+        assert_eq!(
+            params
+                .check_known(&["start"])
+                .expect_err("unknown")
+                .to_string(),
+            "Invalid argument: unknown param digits, expected one of start"
+        );
+        assert!(params.check_known(&[]).is_err());
+        assert!(Params::new().check_known(&[]).is_ok());
+    }
+}
@@ -0,0 +1,463 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag_no_case, take_till1, take_while1},
+    character::complete::{
+        char, digit1, hex_digit1, line_ending, multispace0, not_line_ending, one_of,
+    },
+    combinator::{all_consuming, eof, map_res, opt, recognize, verify},
+    error::ErrorKind,
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+};
+
+use crate::error::AocError;
+use crate::interner::{Interner, Symbol};
+use crate::result::AocResult;
+
+/// Run `parser` over the whole of `input`, failing if anything is left over.
+pub fn parse_complete<'a, T, F>(input: &'a str, parser: F) -> AocResult<T>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    let (_, item) = all_consuming(parser)
+        .parse(input)
+        .map_err(|e| nom_error(input, e))?;
+    Ok(item)
+}
+
+/// `error` from a nom parser run over `input`, placed at the line and
+/// column where it gave up and saying what it wanted there, as in
+/// "expected a digit, found 'x'".
+pub fn nom_error(input: &str, error: nom::Err<nom::error::Error<&str>>) -> AocError {
+    let (offset, message) = match &error {
+        nom::Err::Error(inner) | nom::Err::Failure(inner) => (
+            input.len() - inner.input.len(),
+            format!(
+                "expected {}, found {}",
+                expected(inner.code),
+                found(inner.input)
+            ),
+        ),
+        nom::Err::Incomplete(_) => (input.len(), "unexpected end of input".to_string()),
+    };
+    AocError::parse_at(input, offset, AocError::NomError(message))
+}
+
+/// What a nom parser that failed with `kind` was looking for.
+fn expected(kind: ErrorKind) -> String {
+    let what = match kind {
+        ErrorKind::Digit => "a digit",
+        ErrorKind::HexDigit => "a hex digit",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Char | ErrorKind::OneOf => "a separator",
+        ErrorKind::Tag => "a keyword",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::MapRes | ErrorKind::MapOpt => "a number that fits",
+        kind => return kind.description().to_lowercase(),
+    };
+    what.to_string()
+}
+
+/// The text a nom parser gave up at, as the character it stopped on.
+fn found(rest: &str) -> String {
+    rest.chars()
+        .next()
+        .map_or("end of input".to_string(), |c| format!("{:?}", c))
+}
+
+/// Each line of `text` parsed with `parser`, reporting every bad line at
+/// once with its line number, counted from 1.
+pub fn parse_lines<T>(
+    text: &str,
+    mut parser: impl FnMut(&str) -> AocResult<T>,
+) -> AocResult<Vec<T>> {
+    AocError::collect_all(
+        text.lines()
+            .enumerate()
+            .map(|(number, line)| parser(line).map_err(|e| e.at_line(number + 1, line))),
+    )
+}
+
+/// An unsigned decimal number, failing if it doesn't fit in `T`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse).parse(input)
+}
+
+/// A decimal number with an optional `+` or `-` sign, failing if it
+/// doesn't fit in `T`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse).parse(input)
+}
+
+/// `separator` with any whitespace, newlines included, on either side.
+pub fn separator<'a>(
+    separator: char,
+) -> impl Parser<&'a str, Output = char, Error = nom::error::Error<&'a str>> {
+    delimited(multispace0, char(separator), multispace0)
+}
+
+/// One or more `item`s split by `separator`, as in `1, 2,3`.
+pub fn list<'a, T, F>(
+    separator_char: char,
+    item: F,
+) -> impl Parser<&'a str, Output = Vec<T>, Error = nom::error::Error<&'a str>>
+where
+    F: Parser<&'a str, Output = T, Error = nom::error::Error<&'a str>>,
+{
+    separated_list1(separator(separator_char), item)
+}
+
+/// An inclusive range written `a-b`, as two `unsigned` numbers.
+pub fn range<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(unsigned, char('-'), unsigned).parse(input)
+}
+
+/// A label made of ASCII letters, digits and underscores.
+pub fn identifier(input: &str) -> IResult<&str, &str> {
+    recognize(take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_')).parse(input)
+}
+
+/// Parses an `identifier` and interns it, yielding a compact `Symbol`.
+pub fn symbol<'i>(
+    interner: &'i RefCell<Interner>,
+) -> impl FnMut(&str) -> IResult<&str, Symbol> + 'i {
+    move |input: &str| {
+        let (rest, name) = identifier(input)?;
+        Ok((rest, interner.borrow_mut().intern(name)))
+    }
+}
+
+/// Parses a `symbol` followed by `:`, as used for node labels like `aaa: bbb ccc`.
+pub fn label<'i>(
+    interner: &'i RefCell<Interner>,
+) -> impl FnMut(&str) -> IResult<&str, Symbol> + 'i {
+    let mut symbol = symbol(interner);
+    move |input: &str| {
+        let (rest, label) = symbol(input)?;
+        let (rest, _) = char(':').parse(rest)?;
+        Ok((rest, label))
+    }
+}
+
+/// An unsigned number written in decimal or, after `0x`, in hex, with
+/// single underscores allowed between digits: `42`, `1_000`, `0x1F`,
+/// `0xdead_beef`. Fails on values that don't fit in `T`.
+pub fn relaxed_number<T: TryFrom<u64>>(input: &str) -> IResult<&str, T> {
+    let hex = map_res(preceded(tag_no_case("0x"), hex_digits), |s: &str| {
+        u64::from_str_radix(&s.replace('_', ""), 16)
+    });
+    let decimal = map_res(decimal_digits, |s: &str| s.replace('_', "").parse::<u64>());
+    map_res(alt((hex, decimal)), T::try_from).parse(input)
+}
+
+fn decimal_digits(input: &str) -> IResult<&str, &str> {
+    recognize(pair(digit1, many0(preceded(char('_'), digit1)))).parse(input)
+}
+
+fn hex_digits(input: &str) -> IResult<&str, &str> {
+    recognize(pair(hex_digit1, many0(preceded(char('_'), hex_digit1)))).parse(input)
+}
+
+/// A titled block of input such as
+///
+/// ```text
+/// seed-to-soil map:
+/// 50 98 2
+/// 52 50 48
+/// ```
+///
+/// or a single line like `seeds: 79 14 55 13`, where the text after the
+/// colon is kept as `inline`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section<'a> {
+    pub title: &'a str,
+    pub inline: &'a str,
+    pub body: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    pub fn parse_inline<T, F>(&self, parser: F) -> AocResult<T>
+    where
+        F: FnMut(&str) -> IResult<&str, T>,
+    {
+        parse_complete(self.inline, parser)
+    }
+
+    pub fn parse_body<T, F>(&self, mut parser: F) -> AocResult<Vec<T>>
+    where
+        F: FnMut(&str) -> IResult<&str, T>,
+    {
+        self.body
+            .iter()
+            .map(|line| parse_complete(line, &mut parser))
+            .collect()
+    }
+}
+
+fn blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// `title:` followed by any inline text up to the end of the line.
+pub fn section_header(input: &str) -> IResult<&str, (&str, &str)> {
+    let (input, title) =
+        terminated(take_till1(|c| c == ':' || c == '\n'), char(':')).parse(input)?;
+    let (input, inline) = terminated(not_line_ending, opt(line_ending)).parse(input)?;
+    Ok((input, (title.trim(), inline.trim())))
+}
+
+fn body_line(input: &str) -> IResult<&str, &str> {
+    terminated(
+        verify(not_line_ending, |line: &str| !blank(line)),
+        opt(line_ending),
+    )
+    .parse(input)
+}
+
+pub fn section(input: &str) -> IResult<&str, Section<'_>> {
+    let (input, (title, inline)) = section_header(input)?;
+    let (input, body) = many0(body_line).parse(input)?;
+    Ok((
+        input,
+        Section {
+            title,
+            inline,
+            body,
+        },
+    ))
+}
+
+/// One or more sections separated by blank lines.
+pub fn sections(input: &str) -> IResult<&str, Vec<Section<'_>>> {
+    delimited(
+        multispace0,
+        separated_list1(many1(line_ending), section),
+        (multispace0, eof),
+    )
+    .parse(input)
+}
+
+/// Sections keyed by title, remembering the order they appeared in.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SectionMap<'a> {
+    order: Vec<&'a str>,
+    sections: HashMap<&'a str, Section<'a>>,
+}
+
+impl<'a> SectionMap<'a> {
+    pub fn parse(input: &'a str) -> AocResult<Self> {
+        let parsed = parse_complete(input, sections)?;
+        let mut map = SectionMap::default();
+        for section in parsed {
+            if map.sections.contains_key(section.title) {
+                return Err(AocError::ParseError(format!(
+                    "duplicate section '{}'",
+                    section.title
+                )));
+            }
+            map.order.push(section.title);
+            map.sections.insert(section.title, section);
+        }
+        Ok(map)
+    }
+
+    pub fn get(&self, title: &str) -> Option<&Section<'a>> {
+        self.sections.get(title)
+    }
+
+    pub fn require(&self, title: &str) -> AocResult<&Section<'a>> {
+        self.get(title)
+            .ok_or_else(|| AocError::ParseError(format!("missing section '{}'", title)))
+    }
+
+    pub fn titles(&self) -> &[&'a str] {
+        &self.order
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Section<'a>> {
+        self.order.iter().map(|title| &self.sections[title])
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{character::complete::space1, multi::separated_list1, sequence::preceded};
+
+    #[test]
+    fn test_numbers_lists_and_ranges() {
+        assert_eq!(parse_complete("42", unsigned::<u8>).expect("u8"), 42);
+        assert!(parse_complete("256", unsigned::<u8>).is_err());
+        assert_eq!(
+            parse_complete("18446744073709551615", unsigned::<u64>).expect("u64"),
+            u64::MAX
+        );
+        assert_eq!(parse_complete("-17", signed::<i32>).expect("negative"), -17);
+        assert_eq!(parse_complete("+5", signed::<i64>).expect("plus"), 5);
+        assert!(parse_complete("-", signed::<i64>).is_err());
+        assert_eq!(
+            parse_complete("1,2 ,\n 3", |s| list(',', unsigned::<u32>).parse(s)).expect("list"),
+            vec![1, 2, 3]
+        );
+        assert!(parse_complete("1,", |s| list(',', unsigned::<u32>).parse(s)).is_err());
+        assert_eq!(
+            parse_complete("11-22", range::<u64>).expect("range"),
+            (11, 22)
+        );
+        assert_eq!(
+            parse_complete("1-2, 3-4", |s| list(',', range::<u16>).parse(s)).expect("ranges"),
+            vec![(1, 2), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn test_nom_error_messages() {
+        let error = parse_complete("1\n2x\n", |s| separated_list1(line_ending, digit1).parse(s))
+            .expect_err("trailing letter");
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 2 of '2x': Nom error: expected end of input, found 'x'"
+        );
+        let error = parse_complete("", digit1).expect_err("empty");
+        assert!(
+            error
+                .to_string()
+                .ends_with("expected a digit, found end of input")
+        );
+    }
+
+    #[test]
+    fn test_identifier() {
+        let (rest, name) = identifier("abc_1 rest").expect("parser");
+        assert_eq!(name, "abc_1");
+        assert_eq!(rest, " rest");
+        assert!(identifier(" abc").is_err());
+    }
+
+    #[test]
+    fn test_relaxed_number() {
+        let number = |s| parse_complete(s, relaxed_number::<u32>);
+        assert_eq!(number("42").expect("decimal"), 42);
+        assert_eq!(number("1_000_000").expect("grouped"), 1_000_000);
+        assert_eq!(number("0x1F").expect("hex"), 31);
+        assert_eq!(number("0Xdead_beef").expect("grouped hex"), 0xdead_beef);
+        assert_eq!(number("007").expect("leading zeros"), 7);
+        for bad in ["", "_1", "1_", "1__0", "0x", "0x_1", "x1F", "4294967296"] {
+            assert!(number(bad).is_err(), "{}", bad);
+        }
+        let (rest, n) = relaxed_number::<u8>("255 rest").expect("parser");
+        assert_eq!((rest, n), (" rest", 255));
+    }
+
+    #[test]
+    fn test_symbol() {
+        let interner = RefCell::new(Interner::new());
+        let (_, a) = symbol(&interner)("aaa").expect("parser");
+        let (_, b) = symbol(&interner)("bbb").expect("parser");
+        let (_, again) = symbol(&interner)("aaa").expect("parser");
+        assert_eq!(a, again);
+        assert_ne!(a, b);
+        assert_eq!(interner.borrow().resolve(b), Some("bbb"));
+    }
+
+    #[test]
+    fn test_label_with_edges() {
+        let interner = RefCell::new(Interner::new());
+        let (rest, (from, to)) = (
+            label(&interner),
+            preceded(space1, separated_list1(space1, symbol(&interner))),
+        )
+            .parse("you: bbb ccc")
+            .expect("parser");
+        assert_eq!(rest, "");
+        assert_eq!(interner.borrow().resolve(from), Some("you"));
+        assert_eq!(to.len(), 2);
+        assert_eq!(interner.borrow().len(), 3);
+    }
+
+    const ALMANAC: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+";
+
+    fn numbers(input: &str) -> IResult<&str, Vec<u64>> {
+        separated_list1(
+            space1,
+            nom::combinator::map_res(nom::character::complete::digit1, str::parse),
+        )
+        .parse(input)
+    }
+
+    #[test]
+    fn test_section_header() {
+        let (rest, (title, inline)) = section_header("seeds: 79 14\nnext").expect("parser");
+        assert_eq!(title, "seeds");
+        assert_eq!(inline, "79 14");
+        assert_eq!(rest, "next");
+    }
+
+    #[test]
+    fn test_sections() {
+        let (_, parsed) = sections(ALMANAC).expect("parser");
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].title, "seeds");
+        assert!(parsed[0].body.is_empty());
+        assert_eq!(parsed[1].title, "seed-to-soil map");
+        assert_eq!(parsed[1].inline, "");
+        assert_eq!(parsed[1].body, vec!["50 98 2", "52 50 48"]);
+        assert_eq!(parsed[2].body, vec!["0 15 37"]);
+    }
+
+    #[test]
+    fn test_section_map() {
+        let map = SectionMap::parse(ALMANAC).expect("sections");
+        assert_eq!(
+            map.titles(),
+            &["seeds", "seed-to-soil map", "soil-to-fertilizer map"]
+        );
+        let seeds = map.require("seeds").expect("seeds");
+        assert_eq!(
+            seeds.parse_inline(numbers).expect("numbers"),
+            vec![79, 14, 55, 13]
+        );
+        let rows = map
+            .require("seed-to-soil map")
+            .expect("map")
+            .parse_body(numbers)
+            .expect("rows");
+        assert_eq!(rows, vec![vec![50, 98, 2], vec![52, 50, 48]]);
+        assert!(map.require("humidity").is_err());
+    }
+
+    #[test]
+    fn test_section_map_rejects_duplicates() {
+        assert!(SectionMap::parse("a: 1\n\na: 2\n").is_err());
+    }
+
+    #[test]
+    fn test_section_map_rejects_garbage() {
+        assert!(SectionMap::parse("no header here\n").is_err());
+    }
+}
@@ -0,0 +1,368 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// `BinaryHeap` that pops its smallest element first.
+#[derive(Debug, Clone)]
+pub struct MinHeap<T: Ord> {
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> Default for MinHeap<T> {
+    fn default() -> Self {
+        MinHeap {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<T: Ord> MinHeap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.heap.push(Reverse(item));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|Reverse(item)| item)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|Reverse(item)| item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        MinHeap {
+            heap: iter.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+/// How to order entries that share a priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Earliest pushed first.
+    #[default]
+    Fifo,
+    /// Latest pushed first.
+    Lifo,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<P, V> {
+    priority: P,
+    seq: u64,
+    value: V,
+}
+
+impl<P: Ord, V> PartialEq for Entry<P, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<P: Ord, V> Eq for Entry<P, V> {}
+
+impl<P: Ord, V> PartialOrd for Entry<P, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Ord, V> Ord for Entry<P, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so the max-heap pops the lowest (priority, seq) first.
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// Min-priority queue whose values need not be `Ord`; entries with equal
+/// priority come out in the order given by its `TieBreak`. Wrap the
+/// priority in `Reverse` for a max-priority queue.
+#[derive(Debug, Clone)]
+pub struct PriorityQueue<P: Ord, V> {
+    heap: BinaryHeap<Entry<P, V>>,
+    tie_break: TieBreak,
+    pushed: u64,
+}
+
+impl<P: Ord, V> Default for PriorityQueue<P, V> {
+    fn default() -> Self {
+        Self::with_tie_break(TieBreak::default())
+    }
+}
+
+impl<P: Ord, V> PriorityQueue<P, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tie_break(tie_break: TieBreak) -> Self {
+        PriorityQueue {
+            heap: BinaryHeap::new(),
+            tie_break,
+            pushed: 0,
+        }
+    }
+
+    pub fn push(&mut self, priority: P, value: V) {
+        let seq = match self.tie_break {
+            TieBreak::Fifo => self.pushed,
+            TieBreak::Lifo => u64::MAX - self.pushed,
+        };
+        self.pushed += 1;
+        self.heap.push(Entry {
+            priority,
+            seq,
+            value,
+        });
+    }
+
+    pub fn pop(&mut self) -> Option<(P, V)> {
+        self.heap.pop().map(|e| (e.priority, e.value))
+    }
+
+    pub fn peek(&self) -> Option<(&P, &V)> {
+        self.heap.peek().map(|e| (&e.priority, &e.value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+/// Min-priority queue over keys `0..capacity` that holds each key at most
+/// once and supports changing a queued key's priority in `O(log n)`. Equal
+/// priorities pop in ascending key order.
+#[derive(Debug, Clone)]
+pub struct IndexedPriorityQueue<P> {
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+    priorities: Vec<Option<P>>,
+}
+
+impl<P: Ord + Copy> IndexedPriorityQueue<P> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::with_capacity(capacity),
+            positions: vec![None; capacity],
+            priorities: vec![None; capacity],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        self.positions.get(key).is_some_and(|p| p.is_some())
+    }
+
+    pub fn priority(&self, key: usize) -> Option<P> {
+        if self.contains(key) {
+            self.priorities[key]
+        } else {
+            None
+        }
+    }
+
+    /// Insert `key`, or move it to `priority` if it is already queued.
+    pub fn push(&mut self, key: usize, priority: P) {
+        assert!(key < self.capacity(), "key {} out of range", key);
+        match self.positions[key] {
+            Some(position) => {
+                let old = self.priorities[key].replace(priority);
+                if old.is_some_and(|old| priority < old) {
+                    self.sift_up(position);
+                } else {
+                    self.sift_down(position);
+                }
+            }
+            None => {
+                self.priorities[key] = Some(priority);
+                self.positions[key] = Some(self.heap.len());
+                self.heap.push(key);
+                self.sift_up(self.heap.len() - 1);
+            }
+        }
+    }
+
+    /// Lower the priority of `key`, inserting it if absent. Returns `false`
+    /// and leaves the queue unchanged if `priority` is not an improvement.
+    pub fn decrease_key(&mut self, key: usize, priority: P) -> bool {
+        if self
+            .priority(key)
+            .is_some_and(|current| current <= priority)
+        {
+            return false;
+        }
+        self.push(key, priority);
+        true
+    }
+
+    pub fn peek(&self) -> Option<(usize, P)> {
+        self.heap
+            .first()
+            .map(|&key| (key, self.priorities[key].expect("queued priority")))
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        let top = self.peek()?;
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        self.heap.pop();
+        self.positions[top.0] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some(top)
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        let (ka, kb) = (self.heap[a], self.heap[b]);
+        (self.priorities[ka], ka) < (self.priorities[kb], kb)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a]] = Some(a);
+        self.positions[self.heap[b]] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.less(child, smallest) {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap: MinHeap<u32> = [5, 1, 4, 2].into_iter().collect();
+        heap.push(3);
+        assert_eq!(heap.peek(), Some(&1));
+        let drained: Vec<u32> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(drained, vec![1, 2, 3, 4, 5]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_priority_queue_fifo_ties() {
+        let mut pq = PriorityQueue::new();
+        pq.push(2, "c");
+        pq.push(1, "a");
+        pq.push(2, "d");
+        pq.push(1, "b");
+        let drained: Vec<&str> = std::iter::from_fn(|| pq.pop().map(|(_, v)| v)).collect();
+        assert_eq!(drained, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_priority_queue_lifo_and_max() {
+        let mut pq = PriorityQueue::with_tie_break(TieBreak::Lifo);
+        pq.push(Reverse(1), "a");
+        pq.push(Reverse(2), "b");
+        pq.push(Reverse(2), "c");
+        assert_eq!(pq.len(), 3);
+        let drained: Vec<&str> = std::iter::from_fn(|| pq.pop().map(|(_, v)| v)).collect();
+        assert_eq!(drained, vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_indexed_decrease_key() {
+        let mut pq = IndexedPriorityQueue::with_capacity(5);
+        pq.push(0, 10);
+        pq.push(1, 20);
+        pq.push(2, 30);
+        assert!(pq.decrease_key(2, 5));
+        assert!(!pq.decrease_key(1, 25));
+        assert!(pq.decrease_key(4, 15));
+        assert_eq!(pq.priority(1), Some(20));
+        assert_eq!(pq.len(), 4);
+        let drained: Vec<(usize, u32)> = std::iter::from_fn(|| pq.pop()).collect();
+        assert_eq!(drained, vec![(2, 5), (0, 10), (4, 15), (1, 20)]);
+        assert!(!pq.contains(2));
+    }
+
+    #[test]
+    fn test_indexed_increase_and_ties() {
+        let mut pq = IndexedPriorityQueue::with_capacity(4);
+        for key in [3, 1, 2, 0] {
+            pq.push(key, 7);
+        }
+        pq.push(1, 9);
+        let drained: Vec<usize> = std::iter::from_fn(|| pq.pop().map(|(k, _)| k)).collect();
+        assert_eq!(drained, vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_indexed_dijkstra() {
+        // 0 -1-> 1 -1-> 2, 0 -5-> 2, 2 -1-> 3
+        let edges: Vec<Vec<(usize, u32)>> =
+            vec![vec![(1, 1), (2, 5)], vec![(2, 1)], vec![(3, 1)], vec![]];
+        let mut dist = vec![u32::MAX; edges.len()];
+        let mut pq = IndexedPriorityQueue::with_capacity(edges.len());
+        dist[0] = 0;
+        pq.push(0, 0);
+        while let Some((node, d)) = pq.pop() {
+            for &(next, w) in &edges[node] {
+                if d + w < dist[next] {
+                    dist[next] = d + w;
+                    pq.decrease_key(next, d + w);
+                }
+            }
+        }
+        assert_eq!(dist, vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Instant;
+
+use crate::fmt::{Count, Rate};
+use tracing::info;
+
+/// Receives progress from a long-running solver loop. Solvers take a
+/// `&mut dyn Progress` and stay unaware of how, or whether, it is shown.
+pub trait Progress {
+    /// The amount of work expected in total.
+    fn set_total(&mut self, total: u64);
+
+    /// Records `delta` more units of work done.
+    fn inc(&mut self, delta: u64);
+
+    fn finish(&mut self);
+}
+
+/// Ignores all progress.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn set_total(&mut self, _total: u64) {}
+
+    fn inc(&mut self, _delta: u64) {}
+
+    fn finish(&mut self) {}
+}
+
+/// Logs an `info!` line each time another tenth of the total is done,
+/// with the rate since `set_total`.
+#[derive(Debug, Clone, Default)]
+pub struct LogProgress {
+    label: String,
+    total: u64,
+    done: u64,
+    logged_tenths: u64,
+    started: Option<Instant>,
+}
+
+impl LogProgress {
+    pub fn new(label: &str) -> Self {
+        LogProgress {
+            label: label.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Progress for LogProgress {
+    fn set_total(&mut self, total: u64) {
+        self.total = total;
+        self.started = Some(Instant::now());
+    }
+
+    fn inc(&mut self, delta: u64) {
+        self.done = self.done.saturating_add(delta);
+        if self.total == 0 {
+            return;
+        }
+        let tenths = (self.done.min(self.total) as u128 * 10 / self.total as u128) as u64;
+        if tenths > self.logged_tenths {
+            self.logged_tenths = tenths;
+            let rate = Rate {
+                items: self.done,
+                duration: self.started.map(|s| s.elapsed()).unwrap_or_default(),
+            };
+            info!(
+                "{}: {}/{} ({}%, {})",
+                self.label,
+                Count(self.done),
+                Count(self.total),
+                tenths * 10,
+                rate
+            );
+        }
+    }
+
+    fn finish(&mut self) {
+        info!("{}: done", self.label);
+    }
+}
+
+#[cfg(feature = "progress")]
+impl Progress for indicatif::ProgressBar {
+    fn set_total(&mut self, total: u64) {
+        self.set_length(total);
+    }
+
+    fn inc(&mut self, delta: u64) {
+        indicatif::ProgressBar::inc(self, delta);
+    }
+
+    fn finish(&mut self) {
+        indicatif::ProgressBar::finish(self);
+    }
+}
+
+/// The bar's layout: the label, then `done/total` in `label` units.
+#[cfg(feature = "progress")]
+const BAR_TEMPLATE: &str = "{prefix} {wide_bar} {human_pos}/{human_len} {per_sec} ({eta})";
+
+/// Progress for a command-line run: a progress bar on stderr when built
+/// with the `progress` feature, `bar` is allowed and stderr is a terminal,
+/// otherwise log lines.
+pub fn for_terminal(label: &str, bar: bool) -> Box<dyn Progress> {
+    #[cfg(feature = "progress")]
+    {
+        use std::io::IsTerminal;
+        if bar && std::io::stderr().is_terminal() {
+            let style = indicatif::ProgressStyle::with_template(BAR_TEMPLATE)
+                .expect("BAR_TEMPLATE is valid");
+            return Box::new(
+                indicatif::ProgressBar::new(0)
+                    .with_style(style)
+                    .with_prefix(label.to_string()),
+            );
+        }
+    }
+    #[cfg(not(feature = "progress"))]
+    let _ = bar;
+    Box::new(LogProgress::new(label))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_progress_counts() {
+        let mut progress = LogProgress::new("scan");
+        progress.set_total(20);
+        progress.inc(1);
+        assert_eq!(progress.logged_tenths, 0);
+        progress.inc(4);
+        assert_eq!(progress.logged_tenths, 2);
+        progress.inc(100);
+        assert_eq!(progress.logged_tenths, 10);
+        progress.finish();
+    }
+}
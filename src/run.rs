@@ -0,0 +1,894 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::answers::{self, Answers, Check};
+use crate::bench::Bench;
+use crate::cli::CommonConfig;
+use crate::crash;
+use crate::error::AocError;
+use crate::fmt::Elapsed;
+use crate::input;
+use crate::json;
+use crate::par;
+use crate::params::Params;
+use crate::result::AocResult;
+use crate::sniff;
+use crate::solution::{Answer, Solution};
+use crate::solver;
+use crate::submit::Stars;
+use crate::timing::{Timer, Timings};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use tracing::info_span;
+
+/// How a binary prints its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl From<&str> for OutputFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "json" => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        }
+    }
+}
+
+/// How digits of a numeric answer are grouped in human-readable output.
+/// JSON output always uses the raw digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitGrouping {
+    #[default]
+    None,
+    /// `1_227_775_554`
+    Underscore,
+    /// `1,227,775,554`
+    Comma,
+}
+
+impl From<&str> for DigitGrouping {
+    fn from(s: &str) -> Self {
+        match s {
+            "underscore" => DigitGrouping::Underscore,
+            "comma" => DigitGrouping::Comma,
+            _ => DigitGrouping::None,
+        }
+    }
+}
+
+impl DigitGrouping {
+    /// Groups the digits of `answer` in threes from the right. Answers that
+    /// are not plain integers are returned unchanged.
+    pub fn apply(self, answer: &str) -> String {
+        let separator = match self {
+            DigitGrouping::None => return answer.to_string(),
+            DigitGrouping::Underscore => '_',
+            DigitGrouping::Comma => ',',
+        };
+        let (sign, digits) = match answer.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", answer),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return answer.to_string();
+        }
+        let mut out = String::with_capacity(answer.len() + digits.len() / 3);
+        out.push_str(sign);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push(separator);
+            }
+            out.push(digit);
+        }
+        out
+    }
+}
+
+/// Which parts of a puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Part {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Part {
+    /// The part numbers this selects, in order.
+    pub fn numbers(self) -> &'static [u32] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+}
+
+impl From<&str> for Part {
+    fn from(s: &str) -> Self {
+        match s {
+            "1" => Part::One,
+            "2" => Part::Two,
+            _ => Part::Both,
+        }
+    }
+}
+
+/// Parses `input` and solves the requested parts, in part order. Both parts
+/// go through `Solution::solve_both` so days can share work between them.
+pub fn solve_parts<S: Solution>(solution: &S, input: &str, part: Part) -> AocResult<Vec<Answer>> {
+    solve_parts_with(solution, input, part, &Params::new())
+}
+
+/// `solve_parts` with day-specific `params`, rejecting any the day does
+/// not know before parsing.
+pub fn solve_parts_with<S: Solution>(
+    solution: &S,
+    input: &str,
+    part: Part,
+    params: &Params,
+) -> AocResult<Vec<Answer>> {
+    Ok(solve_parts_timed(solution, input, part, params)?.0)
+}
+
+/// `solve_parts_with`, also returning how long parsing and solving took.
+pub fn solve_parts_timed<S: Solution>(
+    solution: &S,
+    input: &str,
+    part: Part,
+    params: &Params,
+) -> AocResult<(Vec<Answer>, Timings)> {
+    params.check_known(solution.param_names())?;
+    let mut timings = Timings::default();
+    let parsed = {
+        let _span = info_span!("phase", phase = "parse").entered();
+        let _timer = Timer::start(&mut timings.parse);
+        solution.parse(input)?
+    };
+    let answers = {
+        let _span = info_span!("phase", phase = "solve").entered();
+        let _timer = Timer::start(&mut timings.solve);
+        match part {
+            Part::One => vec![solution.part1_with(&parsed, params)?],
+            Part::Two => vec![solution.part2_with(&parsed, params)?],
+            Part::Both => {
+                let (one, two) = solution.solve_both_with(&parsed, params)?;
+                vec![one, two]
+            }
+        }
+    };
+    timings.total = timings.parse + timings.solve;
+    Ok((answers, timings))
+}
+
+/// Solves one part of `day` for in-memory `input`, without any file I/O.
+/// `part` must be `Part::One` or `Part::Two`. Input that is recognisably
+/// for another day is rejected before parsing.
+pub fn solve(day: u32, part: Part, input: &str) -> AocResult<Answer> {
+    solve_with(day, part, input, &Params::new())
+}
+
+/// `solve` with day-specific `params`.
+pub fn solve_with(day: u32, part: Part, input: &str, params: &Params) -> AocResult<Answer> {
+    if part == Part::Both {
+        return Err(AocError::Unsupported(
+            "solve answers a single part, use solve_parts for both".to_string(),
+        ));
+    }
+    Ok(solve_day(day, part, input, params)?.0.remove(0))
+}
+
+fn solve_day(
+    day: u32,
+    part: Part,
+    input: &str,
+    params: &Params,
+) -> AocResult<(Vec<Answer>, Timings)> {
+    sniff::check(day, input)?;
+    let solver = solver::find(day)
+        .ok_or_else(|| AocError::Unsupported(format!("day {} is not implemented", day)))?;
+    solver.solve(input, part, params)
+}
+
+/// The answers, or the error, from solving one day in `solve_all`.
+#[derive(Debug)]
+pub struct DayAnswers {
+    pub day: u32,
+    pub part: Part,
+    pub answers: AocResult<Vec<Answer>>,
+    /// Wall-clock time to parse and solve.
+    pub duration: Duration,
+    /// Parse and solve times, if it got that far.
+    pub timings: Option<Timings>,
+}
+
+impl DayAnswers {
+    /// The answer to `part`, if it was asked for and solved.
+    pub fn answer(&self, part: u32) -> Option<&Answer> {
+        let answers = self.answers.as_ref().ok()?;
+        let index = self.part.numbers().iter().position(|&n| n == part)?;
+        answers.get(index)
+    }
+}
+
+/// Solves `part` of every `(day, input)` pair, on several threads if
+/// `parallel`. Results are in the order of `inputs` either way.
+pub fn solve_all(inputs: &[(u32, String)], part: Part, parallel: bool) -> Vec<DayAnswers> {
+    let solve_one = |(day, input): &(u32, String)| {
+        let _span = info_span!("day", day = *day).entered();
+        let start = Instant::now();
+        let solved = solve_day(*day, part, input, &Params::new());
+        let duration = start.elapsed();
+        let (answers, timings) = match solved {
+            Ok((answers, timings)) => (Ok(answers), Some(timings)),
+            Err(e) => (Err(e), None),
+        };
+        DayAnswers {
+            day: *day,
+            part,
+            answers,
+            duration,
+            timings,
+        }
+    };
+    if parallel {
+        par::map_ordered(inputs, solve_one)
+    } else {
+        inputs.iter().map(solve_one).collect()
+    }
+}
+
+/// Flags for solving every day at once.
+#[derive(clap::Args, Debug, Clone)]
+pub struct RunAllConfig {
+    #[clap(
+        long,
+        default_value = "data",
+        help = "Directory holding the dayNN inputs"
+    )]
+    pub dir: String,
+
+    #[clap(
+        short,
+        long,
+        default_value = "both",
+        value_parser = PossibleValuesParser::new(["1", "2", "both"]).map(|s| Part::from(s.as_str())),
+        help = "Part: '1', '2' or 'both'"
+    )]
+    pub part: Part,
+
+    #[clap(long, help = "Solve the days on several threads")]
+    pub parallel: bool,
+
+    #[clap(long, help = "Leave out the time column, e.g. to diff two runs")]
+    pub no_timings: bool,
+
+    #[clap(
+        long,
+        default_value = answers::ANSWERS_PATH,
+        help = "File of verified answers to check against"
+    )]
+    pub answers: String,
+
+    #[clap(long, help = "Store this run's answers as verified")]
+    pub record: bool,
+}
+
+/// Solves every registered day from `<dir>/dayNN/input.txt` and prints the
+/// answers, skipping days without an input file. Answers that differ from
+/// the verified ones are reported as regressions. With `record` every
+/// answer is stored as verified instead. Returns false if there were
+/// regressions that weren't recorded over.
+pub fn run_all(config: &RunAllConfig) -> bool {
+    let mut inputs = Vec::new();
+    for day in solver::days() {
+        let path = format!("{}/day{:02}/input.txt", config.dir, day);
+        match std::fs::read_to_string(&path) {
+            Ok(input) => inputs.push((day, input)),
+            Err(e) => eprintln!("Skipping day {}: {}: {}", day, path, e),
+        }
+    }
+    let results = solve_all(&inputs, config.part, config.parallel);
+    print!("{}", render_all(&results, !config.no_timings));
+    let stars: Vec<(u32, Stars)> = solver::days()
+        .map(|day| {
+            let stars = Stars::load(Path::new(&config.dir), day).unwrap_or_else(|e| {
+                eprintln!("No stars for day {}: {}", day, e);
+                Stars::default()
+            });
+            (day, stars)
+        })
+        .collect();
+    print!("{}", render_progress(&stars));
+
+    let path = Path::new(&config.answers);
+    let mut answers = match Answers::load(path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not checking answers: {}", e);
+            return true;
+        }
+    };
+    let checks = answers.check_all(&results);
+    let mut regressions = 0;
+    for (day, part, answer, check) in &checks {
+        if let Check::Regression { expected } = check {
+            println!(
+                "Day {} part {} regressed: expected {}, got {}",
+                day, part, expected, answer
+            );
+            regressions += 1;
+        }
+    }
+    if config.record {
+        for (day, part, answer, _) in &checks {
+            answers.record(*day, *part, answer);
+        }
+        match answers.save(path) {
+            Ok(()) => println!("Recorded {} answers in {}", checks.len(), path.display()),
+            Err(e) => eprintln!("Failed to record answers: {}", e),
+        }
+    } else if regressions == 0 {
+        let verified = checks
+            .iter()
+            .filter(|(.., check)| *check == Check::Matches)
+            .count();
+        println!("{} of {} answers verified", verified, checks.len());
+    }
+    regressions == 0 || config.record
+}
+
+/// Handles a day binary's `--part` flag: solves `part` of `day` from the
+/// file at `input_path` through the shared `Solver` and prints each answer.
+pub fn run_part(day: u32, input_path: &str, part: Part, common: &CommonConfig) -> AocResult<()> {
+    let input = input::read_to_string(input_path)?;
+    crash::set_input(&input);
+    match common.output {
+        OutputFormat::Text => {
+            let (answers, timings) =
+                solve_day(day, part, &input, &Params::new()).map_err(|e| e.in_file(input_path))?;
+            for (number, answer) in part.numbers().iter().zip(&answers) {
+                println!("Part {}: {}", number, common.group_digits.apply(&answer.0));
+            }
+            common.print_timings(&timings);
+        }
+        OutputFormat::Json => {
+            for &number in part.numbers() {
+                let result = PartResult::solve(day, number, &input, &Params::new())
+                    .map_err(|e| e.in_file(input_path))?;
+                println!("{}", result.to_json());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handles `--bench` for a day without a benchmark of its own: times
+/// parsing the file at `input_path` and solving each of `part` on it.
+/// Returns false if it regressed against `--compare-baseline`.
+pub fn run_bench(day: u32, input_path: &str, part: Part, common: &CommonConfig) -> AocResult<bool> {
+    let input = input::read_to_string(input_path)?;
+    crash::set_input(&input);
+    let solver = solver::find(day)
+        .ok_or_else(|| AocError::Unsupported(format!("day {} is not implemented", day)))?;
+    let report = solver
+        .bench(&input, part, &common.bench_options())
+        .map_err(|e| e.in_file(input_path))?;
+    common.finish_bench(&report)
+}
+
+/// The summary table `run-all` prints: a row per day with both answers
+/// and, if `timings`, how long the day took to parse, to solve and in
+/// all. Errors are listed below the table.
+pub fn render_all(results: &[DayAnswers], timings: bool) -> String {
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+    if timings {
+        rows[0].extend(["Parse", "Solve", "Time"].map(String::from));
+    }
+    for result in results {
+        let cell = |part| match (&result.answers, result.answer(part)) {
+            (_, Some(answer)) => answer.to_string(),
+            (Err(_), None) => "failed".to_string(),
+            (Ok(_), None) => "-".to_string(),
+        };
+        let mut row = vec![result.day.to_string(), cell(1), cell(2)];
+        if timings {
+            let split = |time: fn(&Timings) -> Duration| {
+                result
+                    .timings
+                    .as_ref()
+                    .map_or("-".to_string(), |t| Elapsed(time(t)).to_string())
+            };
+            row.push(split(|t| t.parse));
+            row.push(split(|t| t.solve));
+            row.push(Elapsed(result.duration).to_string());
+        }
+        rows.push(row);
+    }
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+    let mut out = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        let _ = writeln!(out, "{}", cells.join("  ").trim_end());
+    }
+    for result in results {
+        if let Err(e) = &result.answers {
+            let _ = writeln!(out, "Day {} failed: {}", result.day, e);
+        }
+    }
+    out
+}
+
+/// A line per day with its stars, puzzle title and page, then the stars
+/// earned out of those available, as `run-all` prints below the answers.
+pub fn render_progress(stars: &[(u32, Stars)]) -> String {
+    let title = |day| solver::command(day).map_or("?", |command| command.title);
+    let width = stars
+        .iter()
+        .map(|&(day, _)| title(day).len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for &(day, stars) in stars {
+        let _ = writeln!(
+            out,
+            "Day {:<2}  {}  {:width$}  {}",
+            day,
+            stars,
+            title(day),
+            input::puzzle_url(day),
+            width = width
+        );
+    }
+    let earned: u32 = stars.iter().map(|(_, stars)| stars.count()).sum();
+    let _ = writeln!(out, "Stars: {} of {}", earned, stars.len() * 2);
+    out
+}
+
+/// Time spent in one stage of a run and how many items it handled, e.g.
+/// bytes read or records parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub duration: Duration,
+    pub items: usize,
+}
+
+/// Records `Phase`s in the order they run.
+#[derive(Debug, Clone, Default)]
+pub struct Phases {
+    phases: Vec<Phase>,
+}
+
+impl Phases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` as the phase `name`, counting its items with `items`.
+    pub fn time<T, F, C>(&mut self, name: &'static str, f: F, items: C) -> T
+    where
+        F: FnOnce() -> T,
+        C: FnOnce(&T) -> usize,
+    {
+        crash::set_phase(name);
+        let _span = info_span!("phase", phase = name).entered();
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        crash::end_phase();
+        self.phases.push(Phase {
+            name,
+            duration,
+            items: items(&result),
+        });
+        result
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Phase> {
+        self.phases.iter()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|p| p.duration).sum()
+    }
+
+    pub fn into_vec(self) -> Vec<Phase> {
+        self.phases
+    }
+}
+
+/// One part's answer and how long parsing and solving it took, as
+/// `--part` prints it with `--output json`.
+///
+/// ```text
+/// {"day":2,"part":1,"answer":1227775554,"elapsed_ms":12,"timings":{"parse_us":210,...}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub timings: Timings,
+}
+
+impl PartResult {
+    /// Solves part `part` of `day` on its own, so the time is for that
+    /// part alone.
+    pub fn solve(day: u32, part: u32, input: &str, params: &Params) -> AocResult<Self> {
+        let which = if part == 1 { Part::One } else { Part::Two };
+        let start = Instant::now();
+        let (mut answers, timings) = solve_day(day, which, input, params)?;
+        Ok(PartResult {
+            day,
+            part,
+            answer: answers.remove(0),
+            elapsed: start.elapsed(),
+            timings,
+        })
+    }
+
+    /// Numeric answers are JSON numbers, anything else a string.
+    pub fn to_json(&self) -> String {
+        json::object(&[
+            ("day", self.day.to_string()),
+            ("part", self.part.to_string()),
+            ("answer", json::number_or_string(&self.answer.0)),
+            ("elapsed_ms", self.elapsed.as_millis().to_string()),
+            ("timings", self.timings.to_json()),
+        ])
+    }
+}
+
+/// The JSON object for a run of `part` of `day` that prints something
+/// other than the answer, such as a listing or statistics, under `fields`.
+pub fn part_json(day: u32, part: u32, fields: &[(&str, String)]) -> String {
+    let mut all = vec![("day", day.to_string()), ("part", part.to_string())];
+    all.extend(fields.iter().cloned());
+    json::object(&all)
+}
+
+/// The machine-readable result of a run. Leads with the same fields as a
+/// `PartResult`, then the details of how the answer was found:
+///
+/// ```text
+/// {"day":1,"part":1,"answer":3,"elapsed_ms":0,"mode":"after","timings":{"parse_us":52,"solve_us":8,"total_us":103},"phases":[{"name":"read","duration_us":41,"items":61},...]}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    pub day: u32,
+    /// The puzzle part `mode` answers.
+    pub part: u32,
+    pub mode: String,
+    /// Options that shaped the answer beyond `mode`, e.g. a tie-break
+    /// strategy. Omitted from the JSON when empty.
+    pub settings: Vec<(&'static str, String)>,
+    /// Numbers measured along the way, such as how far the dial turned.
+    /// Omitted from the JSON when empty.
+    pub metrics: Vec<(&'static str, u64)>,
+    pub answer: String,
+    pub phases: Vec<Phase>,
+}
+
+impl RunReport {
+    /// The parse and solve phases, and the run as a whole.
+    pub fn timings(&self) -> Timings {
+        Timings::from_phases(&self.phases)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out = format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{},\"mode\":{},",
+            self.day,
+            self.part,
+            json::number_or_string(&self.answer),
+            self.timings().total.as_millis(),
+            json::string(&self.mode)
+        );
+        if !self.settings.is_empty() {
+            let settings: Vec<(&str, String)> = self
+                .settings
+                .iter()
+                .map(|(key, value)| (*key, json::string(value)))
+                .collect();
+            let _ = write!(out, "\"settings\":{},", json::object(&settings));
+        }
+        if !self.metrics.is_empty() {
+            let metrics: Vec<(&str, String)> = self
+                .metrics
+                .iter()
+                .map(|(key, value)| (*key, value.to_string()))
+                .collect();
+            let _ = write!(out, "\"metrics\":{},", json::object(&metrics));
+        }
+        let _ = write!(out, "\"timings\":{},\"phases\":[", self.timings().to_json());
+        for (i, phase) in self.phases.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"name\":{},\"duration_us\":{},\"items\":{}}}",
+                json::string(phase.name),
+                phase.duration.as_micros(),
+                phase.items
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counting {
+        calls: std::cell::RefCell<Vec<&'static str>>,
+    }
+
+    impl Solution for Counting {
+        type Input = u64;
+
+        fn day(&self) -> u32 {
+            0
+        }
+
+        fn parse(&self, input: &str) -> AocResult<u64> {
+            Ok(input.len() as u64)
+        }
+
+        fn part1(&self, input: &u64) -> AocResult<Answer> {
+            self.calls.borrow_mut().push("part1");
+            Ok(Answer::from(*input))
+        }
+
+        fn part2(&self, input: &u64) -> AocResult<Answer> {
+            self.calls.borrow_mut().push("part2");
+            Ok(Answer::from(input * 2))
+        }
+
+        fn solve_both(&self, input: &u64) -> AocResult<(Answer, Answer)> {
+            self.calls.borrow_mut().push("both");
+            Ok((Answer::from(*input), Answer::from(input * 2)))
+        }
+    }
+
+    #[test]
+    fn test_solve_parts_prefers_solve_both() {
+        let solution = Counting {
+            calls: Default::default(),
+        };
+        let answers = solve_parts(&solution, "abc", Part::Both).expect("answers");
+        assert_eq!(answers, vec![Answer::from(3u64), Answer::from(6u64)]);
+        assert_eq!(
+            solve_parts(&solution, "abc", Part::Two).expect("answers"),
+            vec![Answer::from(6u64)]
+        );
+        assert_eq!(*solution.calls.borrow(), vec!["both", "part2"]);
+    }
+
+    #[test]
+    fn test_solve_in_memory() {
+        let day01 = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        assert_eq!(solve(1, Part::One, day01).expect("day 1").to_string(), "3");
+        assert_eq!(solve(1, Part::Two, day01).expect("day 1").to_string(), "6");
+        let day02 = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\n\
+                     1698522-1698528,446443-446449,38593856-38593862,565653-565659,\n\
+                     824824821-824824827,2121212118-2121212124\n";
+        assert_eq!(
+            solve(2, Part::One, day02).expect("day 2").to_string(),
+            "1227775554"
+        );
+        assert_eq!(
+            solve(2, Part::Two, day02).expect("day 2").to_string(),
+            "4174379265"
+        );
+        let day03 = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+        assert_eq!(
+            solve(3, Part::One, day03).expect("day 3").to_string(),
+            "357"
+        );
+        assert_eq!(
+            solve(3, Part::Two, day03).expect("day 3").to_string(),
+            "3121910778619"
+        );
+        assert!(matches!(
+            solve(99, Part::One, day01),
+            Err(AocError::Unsupported(_))
+        ));
+        assert!(solve(1, Part::Both, day01).is_err());
+        assert!(solve(1, Part::One, day03).is_err());
+    }
+
+    #[test]
+    fn test_render_all() {
+        let results = vec![
+            DayAnswers {
+                day: 1,
+                part: Part::One,
+                answers: Ok(vec![Answer::from(3u32)]),
+                duration: Duration::from_millis(5),
+                timings: Some(Timings {
+                    parse: Duration::from_millis(1),
+                    solve: Duration::from_micros(3500),
+                    total: Duration::from_micros(4500),
+                }),
+            },
+            DayAnswers {
+                day: 12,
+                part: Part::One,
+                answers: Err(AocError::Unsupported("nope".to_string())),
+                duration: Duration::ZERO,
+                timings: None,
+            },
+        ];
+        assert_eq!(
+            render_all(&results, true),
+            "Day  Part 1  Part 2  Parse   Solve   Time\n\
+             1    3       -       1.0 ms  3.5 ms  5.0 ms\n\
+             12   failed  failed  -       -       0 ns\n\
+             Day 12 failed: Unsupported: nope\n"
+        );
+        assert_eq!(results[0].answer(2), None);
+        let solved = Stars {
+            part1: true,
+            part2: false,
+        };
+        assert_eq!(
+            render_progress(&[(1, solved), (3, Stars::default())]),
+            "Day 1   *.  Secret Entrance  https://adventofcode.com/2025/day/1\n\
+             Day 3   ..  Lobby            https://adventofcode.com/2025/day/3\n\
+             Stars: 1 of 4\n"
+        );
+        assert_eq!(Part::Both.numbers(), &[1, 2]);
+        assert_eq!(Part::from("2").numbers(), &[2]);
+    }
+
+    #[test]
+    fn test_digit_grouping() {
+        assert_eq!(DigitGrouping::None.apply("1227775554"), "1227775554");
+        assert_eq!(
+            DigitGrouping::Underscore.apply("1227775554"),
+            "1_227_775_554"
+        );
+        assert_eq!(DigitGrouping::Comma.apply("1227775554"), "1,227,775,554");
+        assert_eq!(DigitGrouping::Comma.apply("357"), "357");
+        assert_eq!(DigitGrouping::Comma.apply("-4174"), "-4,174");
+        assert_eq!(DigitGrouping::Comma.apply("R48"), "R48");
+        assert_eq!(DigitGrouping::Comma.apply(""), "");
+    }
+
+    #[test]
+    fn test_solve_with_params() {
+        let day03 = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+        let params = Params::parse("digits=3").expect("params");
+        assert_eq!(
+            solve_with(3, Part::One, day03, &params)
+                .expect("day 3")
+                .to_string(),
+            (987 + 819 + 478 + 921).to_string()
+        );
+        let day01 = "L68\nL30\nR48\n";
+        let params = Params::parse("start=68").expect("params");
+        assert_eq!(
+            solve_with(1, Part::One, day01, &params)
+                .expect("day 1")
+                .to_string(),
+            "1"
+        );
+        let unknown = Params::parse("digits=3").expect("params");
+        assert!(matches!(
+            solve_with(1, Part::One, day01, &unknown),
+            Err(AocError::InvalidArgument(_))
+        ));
+        assert!(
+            solve_with(
+                1,
+                Part::One,
+                day01,
+                &Params::parse("start=100").expect("params")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_phases() {
+        let mut phases = Phases::new();
+        let words = phases.time("parse", || vec!["a", "b", "c"], |v| v.len());
+        let total = phases.time("solve", || words.len() * 2, |_| words.len());
+        assert_eq!(total, 6);
+        let recorded: Vec<(&str, usize)> = phases.iter().map(|p| (p.name, p.items)).collect();
+        assert_eq!(recorded, vec![("parse", 3), ("solve", 3)]);
+        assert!(phases.total() >= phases.iter().map(|p| p.duration).max().expect("max"));
+    }
+
+    #[test]
+    fn test_part_result_json() {
+        let result = PartResult {
+            day: 2,
+            part: 1,
+            answer: Answer("1227775554".to_string()),
+            elapsed: Duration::from_micros(12_400),
+            timings: Timings {
+                parse: Duration::from_micros(200),
+                solve: Duration::from_micros(12_000),
+                total: Duration::from_micros(12_200),
+            },
+        };
+        assert_eq!(
+            result.to_json(),
+            "{\"day\":2,\"part\":1,\"answer\":1227775554,\"elapsed_ms\":12,\"timings\":\
+             {\"parse_us\":200,\"solve_us\":12000,\"total_us\":12200}}"
+        );
+        let solved = PartResult::solve(1, 2, "L68\nL30\nR48\n", &Params::new()).expect("solve");
+        assert_eq!((solved.day, solved.part), (1, 2));
+        assert_eq!(
+            solved.timings.total,
+            solved.timings.parse + solved.timings.solve
+        );
+        assert!(PartResult::solve(1, 1, "X1\n", &Params::new()).is_err());
+    }
+
+    #[test]
+    fn test_report_json() {
+        let report = RunReport {
+            day: 2,
+            part: 1,
+            mode: "two".to_string(),
+            settings: vec![],
+            metrics: vec![],
+            answer: "1227775554".to_string(),
+            phases: vec![
+                Phase {
+                    name: "read",
+                    duration: Duration::from_micros(15),
+                    items: 120,
+                },
+                Phase {
+                    name: "solve",
+                    duration: Duration::from_millis(2),
+                    items: 11,
+                },
+            ],
+        };
+        assert_eq!(
+            report.to_json(),
+            "{\"day\":2,\"part\":1,\"answer\":1227775554,\"elapsed_ms\":2,\"mode\":\"two\",\
+             \"timings\":{\"parse_us\":0,\"solve_us\":2000,\"total_us\":2015},\"phases\":[\
+             {\"name\":\"read\",\"duration_us\":15,\"items\":120},\
+             {\"name\":\"solve\",\"duration_us\":2000,\"items\":11}]}"
+        );
+        let with_settings = RunReport {
+            day: 3,
+            part: 1,
+            mode: "two".to_string(),
+            settings: vec![("tie_break", "latest".to_string())],
+            metrics: vec![("lines", 4)],
+            answer: "357".to_string(),
+            phases: vec![],
+        };
+        assert_eq!(
+            with_settings.to_json(),
+            "{\"day\":3,\"part\":1,\"answer\":357,\"elapsed_ms\":0,\"mode\":\"two\",\
+             \"settings\":{\"tie_break\":\"latest\"},\"metrics\":{\"lines\":4},\"timings\":{\"parse_us\":0,\"solve_us\":0,\"total_us\":0},\
+             \"phases\":[]}"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// Where the registry lists every day, as written in `src/solver.rs`.
+const COMMANDS_START: &str = "pub static COMMANDS: &[DayCommand] = &[";
+
+/// Source of a new `src/days/dayNN.rs`: a `Solution` that parses lines
+/// and reports both parts as unsolved, the command line and description
+/// the registry needs, and a test that empty input is rejected.
+pub fn day_module(day: u32, title: &str) -> String {
+    TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{dd}", &format!("{:02}", day))
+        .replace("{title}", &format!("{:?}", title))
+}
+
+const TEMPLATE: &str = r#"use clap::{CommandFactory, FromArgMatches};
+
+use crate::cli::CommonConfig;
+use crate::crash;
+use crate::describe::{Description, Example, Grammar, Rules};
+use crate::error::AocError;
+use crate::logging;
+use crate::result::AocResult;
+use crate::run::{self, Part};
+use crate::solution::{Answer, Solution};
+use tracing::info_span;
+
+#[derive(clap::Parser, Debug, Clone)]
+pub struct Config {
+    #[command(flatten)]
+    pub common: CommonConfig,
+}
+
+/// Runs `aoc day{dd}`: solves `--part`, both by default, through the
+/// shared runner, or benchmarks it with `--bench`.
+pub fn run(matches: &clap::ArgMatches) -> AocResult<bool> {
+    let config = Config::from_arg_matches(matches).unwrap_or_else(|e| e.exit());
+    let common = &config.common;
+    logging::init(common.verbosity, common.log_format);
+    let input = common.input_path({day});
+    crash::install({day}, &input);
+    let _span = info_span!("day", day = {day}).entered();
+    let part = common.part.unwrap_or(Part::Both);
+    if common.bench {
+        return run::run_bench({day}, &input, part, common);
+    }
+    run::run_part({day}, &input, part, common)?;
+    Ok(true)
+}
+
+pub fn describe() -> Description {
+    Description {
+        day: {day},
+        title: {title},
+        command: Config::command(),
+        modes: vec![],
+        grammar: Rules(vec![("input", Grammar::lines(Grammar::Text))]),
+        examples: vec![Example {
+            args: "--input data/day{dd}/test_input.txt",
+            help: "solve both parts for the example input",
+        }],
+        worked: vec![],
+        stars: None,
+    }
+}
+
+pub struct Day{dd};
+
+impl Solution for Day{dd} {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        {day}
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Vec<String>> {
+        let lines: Vec<String> = input.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            return Err(AocError::EmptyInput("no lines".to_string()));
+        }
+        Ok(lines)
+    }
+
+    fn part1(&self, _input: &Vec<String>) -> AocResult<Answer> {
+        Err(AocError::Unsupported(
+            "day {day} part 1 is not solved yet".to_string(),
+        ))
+    }
+
+    fn part2(&self, _input: &Vec<String>) -> AocResult<Answer> {
+        Err(AocError::Unsupported(
+            "day {day} part 2 is not solved yet".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_input() {
+        assert!(matches!(Day{dd}.parse(""), Err(AocError::EmptyInput(_))));
+    }
+}
+"#;
+
+/// Creates the solver module and data files for `day`, the puzzle called
+/// `title`, under the crate at `root`, and registers the module in
+/// `src/days/mod.rs` and the day registry. Refuses to overwrite an
+/// existing day. Returns the files created or changed.
+pub fn new_day(root: &Path, day: u32, title: &str) -> AocResult<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::InvalidArgument(format!(
+            "day {} is not 1 to 25",
+            day
+        )));
+    }
+    let name = format!("day{:02}", day);
+    let module = root.join("src/days").join(format!("{}.rs", name));
+    if module.exists() {
+        return Err(AocError::Unsupported(format!(
+            "{} already exists",
+            module.display()
+        )));
+    }
+    let days = root.join("src/days/mod.rs");
+    let solver = root.join("src/solver.rs");
+    let days_source = add_module(&read(&days)?, &name);
+    let solver_source = register_day(&read(&solver)?, &name, day, title)?;
+
+    let data = root.join("data").join(&name);
+    std::fs::create_dir_all(&data).map_err(|e| io_error(&data, e))?;
+    let mut changed = Vec::new();
+    for file in ["input.txt", "test_input.txt"] {
+        let path = data.join(file);
+        if !path.exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+    write(&module, &day_module(day, title))?;
+    write(&days, &days_source)?;
+    write(&solver, &solver_source)?;
+    changed.extend([module, days, solver]);
+    Ok(changed)
+}
+
+/// `source` with `pub mod name;` added in order among the other modules.
+fn add_module(source: &str, name: &str) -> String {
+    let declaration = format!("pub mod {};", name);
+    let mut lines: Vec<&str> = source.lines().collect();
+    let at = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod ") && *line > declaration.as_str())
+        .unwrap_or(lines.len());
+    lines.insert(at, &declaration);
+    lines.join("\n") + "\n"
+}
+
+/// `source` with an entry for `DayNN` appended to the `COMMANDS` list.
+fn register_day(source: &str, name: &str, day: u32, title: &str) -> AocResult<String> {
+    let missing = || AocError::ParseError("day registry not found".to_string());
+    let start = source.find(COMMANDS_START).ok_or_else(missing)? + COMMANDS_START.len();
+    let end = start + source[start..].find("];").ok_or_else(missing)?;
+    let module = format!("crate::days::{}", name);
+    let about = format!("Day {}: {}", day, title);
+    let entry = format!(
+        "    DayCommand {{\n        \
+         day: {day},\n        \
+         name: \"{name}\",\n        \
+         title: {title:?},\n        \
+         about: {about:?},\n        \
+         solver: &{module}::Day{day:02},\n        \
+         describe: {module}::describe,\n        \
+         command: {module}::Config::command,\n        \
+         run: {module}::run,\n    \
+         }},\n"
+    );
+    let entries = source[start..end].trim_end();
+    Ok(format!("{}{}\n{}", &source[..start], entries, entry) + &source[end..])
+}
+
+fn read(path: &Path) -> AocResult<String> {
+    std::fs::read_to_string(path).map_err(|e| io_error(path, e))
+}
+
+fn write(path: &Path, contents: &str) -> AocResult<()> {
+    std::fs::write(path, contents).map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> AocError {
+    AocError::io(path, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_module() {
+        let source = day_module(7, "Laboratories");
+        assert!(source.contains("pub struct Day07;"));
+        assert!(source.contains("title: \"Laboratories\","));
+        assert!(source.contains("impl Solution for Day07 {"));
+        assert!(source.contains("        7\n"));
+        assert!(source.contains("data/day07/test_input.txt"));
+        assert!(!source.contains("{dd}"));
+
+        let source = day_module(8, "A \"Quoted\" \\ Title");
+        assert!(source.contains("title: \"A \\\"Quoted\\\" \\\\ Title\","));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc25-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/days")).expect("src dir");
+        std::fs::write(
+            root.join("src/days/mod.rs"),
+            "pub mod day01;\npub mod day02;\npub mod day10;\n",
+        )
+        .expect("days/mod.rs");
+        std::fs::write(
+            root.join("src/solver.rs"),
+            "use x;\n\npub static COMMANDS: &[DayCommand] = &[\n    DayCommand {\n        day: 1,\n    },\n];\n",
+        )
+        .expect("solver.rs");
+
+        let changed = new_day(&root, 7, "Laboratories").expect("new day");
+        assert_eq!(changed.len(), 5);
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/days/mod.rs")).expect("days/mod.rs"),
+            "pub mod day01;\npub mod day02;\npub mod day07;\npub mod day10;\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/solver.rs")).expect("solver.rs"),
+            "use x;\n\npub static COMMANDS: &[DayCommand] = &[\n    DayCommand {\n        day: 1,\n    },\n\
+             \x20   DayCommand {\n        day: 7,\n        name: \"day07\",\n        \
+             title: \"Laboratories\",\n        about: \"Day 7: Laboratories\",\n        \
+             solver: &crate::days::day07::Day07,\n        \
+             describe: crate::days::day07::describe,\n        \
+             command: crate::days::day07::Config::command,\n        \
+             run: crate::days::day07::run,\n    },\n];\n"
+        );
+        assert!(root.join("data/day07/test_input.txt").exists());
+        assert!(new_day(&root, 7, "Laboratories").is_err());
+        assert!(new_day(&root, 26, "Laboratories").is_err());
+
+        new_day(&root, 8, "Say \"Hi\"").expect("new day");
+        let solver = std::fs::read_to_string(root.join("src/solver.rs")).expect("solver.rs");
+        assert!(solver.contains("title: \"Say \\\"Hi\\\"\",\n"));
+        assert!(solver.contains("about: \"Day 8: Say \\\"Hi\\\"\",\n"));
+        std::fs::remove_dir_all(&root).expect("clean up");
+    }
+}
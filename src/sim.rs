@@ -0,0 +1,287 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A simulation state that knows how to advance itself by one step.
+pub trait Step {
+    /// Advance by one step, returning `false` once the simulation has
+    /// finished and no step was taken.
+    fn step(&mut self) -> bool;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<S> {
+    pub steps: u64,
+    pub state: S,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Step count at which the repeating state was first seen.
+    pub start: u64,
+    pub length: u64,
+}
+
+/// Drives a `Step` state, keeping a bounded history of previous states so
+/// the caller can roll back, and detecting cycles to skip ahead.
+#[derive(Debug, Clone)]
+pub struct Stepper<S> {
+    state: S,
+    steps: u64,
+    history: VecDeque<Snapshot<S>>,
+    capacity: usize,
+}
+
+impl<S: Step + Clone> Stepper<S> {
+    pub fn new(state: S) -> Self {
+        Self::with_history(state, 0)
+    }
+
+    pub fn with_history(state: S, capacity: usize) -> Self {
+        Stepper {
+            state,
+            steps: 0,
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn step(&mut self) -> bool {
+        if self.capacity > 0 {
+            if self.history.len() == self.capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(self.snapshot());
+        }
+        if self.state.step() {
+            self.steps += 1;
+            true
+        } else {
+            if self.capacity > 0 {
+                self.history.pop_back();
+            }
+            false
+        }
+    }
+
+    /// Take up to `n` steps, returning how many were taken.
+    pub fn run(&mut self, n: u64) -> u64 {
+        let start = self.steps;
+        while self.steps - start < n && self.step() {}
+        self.steps - start
+    }
+
+    pub fn run_to_end(&mut self) -> u64 {
+        let start = self.steps;
+        while self.step() {}
+        self.steps - start
+    }
+
+    /// Step until `predicate` holds for the current state. Returns `false`
+    /// if the simulation finished first.
+    pub fn run_until<P>(&mut self, mut predicate: P) -> bool
+    where
+        P: FnMut(&S) -> bool,
+    {
+        loop {
+            if predicate(&self.state) {
+                return true;
+            }
+            if !self.step() {
+                return false;
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot<S> {
+        Snapshot {
+            steps: self.steps,
+            state: self.state.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: Snapshot<S>) {
+        self.history.retain(|s| s.steps < snapshot.steps);
+        self.steps = snapshot.steps;
+        self.state = snapshot.state;
+    }
+
+    /// Undo the last `n` steps from the history ring buffer. Returns `false`
+    /// without changing anything if fewer than `n` snapshots are held.
+    pub fn rollback(&mut self, n: usize) -> bool {
+        if n == 0 {
+            return true;
+        }
+        if n > self.history.len() {
+            return false;
+        }
+        let keep = self.history.len() - n;
+        let snapshot = self.history.drain(keep..).next().expect("snapshot");
+        self.steps = snapshot.steps;
+        self.state = snapshot.state;
+        true
+    }
+}
+
+impl<S: Step + Clone + Hash + Eq> Stepper<S> {
+    /// Step forward looking for a state seen before, giving up after
+    /// `max_steps`. The stepper is left just after the repeat.
+    pub fn find_cycle(&mut self, max_steps: u64) -> Option<Cycle> {
+        let mut seen: HashMap<S, u64> = HashMap::new();
+        seen.insert(self.state.clone(), self.steps);
+        for _ in 0..max_steps {
+            if !self.step() {
+                return None;
+            }
+            if let Some(&start) = seen.get(&self.state) {
+                return Some(Cycle {
+                    start,
+                    length: self.steps - start,
+                });
+            }
+            seen.insert(self.state.clone(), self.steps);
+        }
+        None
+    }
+
+    /// Advance until `target` total steps have been taken, skipping whole
+    /// cycles once one is detected. Returns the step count reached, which is
+    /// less than `target` only if the simulation finished early.
+    pub fn fast_forward(&mut self, target: u64) -> u64 {
+        if self.steps >= target {
+            return self.steps;
+        }
+        if let Some(cycle) = self.find_cycle(target - self.steps) {
+            let remaining = target - self.steps;
+            self.steps += remaining - remaining % cycle.length;
+        }
+        let remaining = target - self.steps;
+        self.run(remaining);
+        self.steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walks 0, 1, 2 .. up to `limit`, wrapping to `wrap_to` after `modulus - 1`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Counter {
+        value: u64,
+        modulus: u64,
+        wrap_to: u64,
+        limit: Option<u64>,
+        taken: u64,
+    }
+
+    impl Counter {
+        fn new(modulus: u64, wrap_to: u64) -> Self {
+            Counter {
+                value: 0,
+                modulus,
+                wrap_to,
+                limit: None,
+                taken: 0,
+            }
+        }
+    }
+
+    impl Step for Counter {
+        fn step(&mut self) -> bool {
+            if self.limit.is_some_and(|l| self.taken >= l) {
+                return false;
+            }
+            self.value += 1;
+            if self.value == self.modulus {
+                self.value = self.wrap_to;
+            }
+            if self.limit.is_some() {
+                self.taken += 1;
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn test_run_and_finish() {
+        let mut counter = Counter::new(100, 0);
+        counter.limit = Some(5);
+        let mut stepper = Stepper::new(counter);
+        assert_eq!(stepper.run(3), 3);
+        assert_eq!(stepper.run_to_end(), 2);
+        assert!(!stepper.step());
+        assert_eq!(stepper.steps(), 5);
+        assert_eq!(stepper.state().value, 5);
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut stepper = Stepper::new(Counter::new(10, 0));
+        assert!(stepper.run_until(|c| c.value == 7));
+        assert_eq!(stepper.steps(), 7);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut stepper = Stepper::with_history(Counter::new(100, 0), 3);
+        stepper.run(10);
+        assert_eq!(stepper.history_len(), 3);
+        assert!(stepper.rollback(2));
+        assert_eq!(stepper.steps(), 8);
+        assert_eq!(stepper.state().value, 8);
+        assert_eq!(stepper.history_len(), 1);
+        assert!(!stepper.rollback(2));
+        assert_eq!(stepper.steps(), 8);
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut stepper = Stepper::new(Counter::new(100, 0));
+        stepper.run(4);
+        let snapshot = stepper.snapshot();
+        stepper.run(10);
+        stepper.restore(snapshot);
+        assert_eq!(stepper.steps(), 4);
+        assert_eq!(stepper.state().value, 4);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 1 2 3 4 5 | 2 3 4 5 | ...
+        let mut stepper = Stepper::new(Counter::new(6, 2));
+        let cycle = stepper.find_cycle(100).expect("cycle");
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 2,
+                length: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_fast_forward_matches_stepping() {
+        let target = 1_000_003;
+        let mut slow = Stepper::new(Counter::new(6, 2));
+        slow.run(target);
+        let mut fast = Stepper::new(Counter::new(6, 2));
+        assert_eq!(fast.fast_forward(target), target);
+        assert_eq!(fast.state().value, slow.state().value);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::error::AocError;
+use crate::result::AocResult;
+
+/// How many leading lines to inspect.
+const SAMPLE_LINES: usize = 3;
+
+/// Days whose input format `sniff` knows.
+pub const KNOWN_DAYS: [u32; 3] = [1, 2, 3];
+
+/// Whether one input line fits the format of `day`, or `None` for a day
+/// without a known format.
+fn line_matches(day: u32, line: &str) -> Option<bool> {
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    Some(match day {
+        1 => line.strip_prefix(['L', 'R']).is_some_and(digits),
+        2 => line
+            .split(',')
+            .map(str::trim)
+            .filter(|range| !range.is_empty())
+            .all(|range| {
+                range
+                    .split_once('-')
+                    .is_some_and(|(start, end)| digits(start) && (end.is_empty() || digits(end)))
+            }),
+        // One battery string, or several separated by whitespace.
+        3 => line.split_whitespace().all(|cell| {
+            cell.split_once(':').map_or(digits(cell), |(k, batteries)| {
+                digits(k) && digits(batteries)
+            })
+        }),
+        _ => return None,
+    })
+}
+
+fn sample(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(SAMPLE_LINES)
+}
+
+/// Whether the first few lines of `input` fit the format of `day`, or
+/// `None` if the format of `day` is unknown or the input is blank.
+pub fn matches_day(day: u32, input: &str) -> Option<bool> {
+    let mut lines = sample(input).peekable();
+    lines.peek()?;
+    let mut all = true;
+    for line in lines {
+        all &= line_matches(day, line)?;
+    }
+    Some(all)
+}
+
+/// Days whose format the start of `input` fits.
+pub fn guess_days(input: &str) -> Vec<u32> {
+    KNOWN_DAYS
+        .into_iter()
+        .filter(|&day| matches_day(day, input) == Some(true))
+        .collect()
+}
+
+/// Fails if `input` clearly belongs to a different day than `day`: it does
+/// not fit `day`'s format but does fit another's. Anything less certain
+/// passes, leaving the day's own parser to report problems.
+pub fn check(day: u32, input: &str) -> AocResult<()> {
+    if matches_day(day, input) != Some(false) {
+        return Ok(());
+    }
+    let guesses = guess_days(input);
+    if guesses.is_empty() {
+        return Ok(());
+    }
+    let guesses: Vec<String> = guesses.iter().map(|d| format!("day {}", d)).collect();
+    Err(AocError::ParseError(format!(
+        "input does not look like day {} input, it looks like {}",
+        day,
+        guesses.join(" or ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY01: &str = "L68\nL30\nR48\n";
+    const DAY02: &str = "11-22,95-115,998-1012,\n1698522-1698528,446443-446449\n";
+    const DAY03: &str = "987654321111111\n811111111111119\n";
+
+    #[test]
+    fn test_guess_days() {
+        assert_eq!(guess_days(DAY01), vec![1]);
+        assert_eq!(guess_days(DAY02), vec![2]);
+        assert_eq!(guess_days(DAY03), vec![3]);
+        assert_eq!(guess_days("2:987654321111111\n"), vec![3]);
+        assert_eq!(guess_days("987654321111111 2:811111111111119\n"), vec![3]);
+        assert!(guess_days("hello").is_empty());
+        assert!(guess_days("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(1, DAY01).is_ok());
+        let error = check(1, DAY02).expect_err("mismatch");
+        assert_eq!(
+            error.to_string(),
+            "Parse error: input does not look like day 1 input, it looks like day 2"
+        );
+        assert!(check(3, DAY01).is_err());
+        // Unrecognisable input is left for the day's parser to reject.
+        assert!(check(1, "L68\nfoo\n").is_ok());
+        assert!(check(42, DAY01).is_ok());
+        assert!(check(1, "").is_ok());
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+use crate::params::Params;
+use crate::result::AocResult;
+
+/// A puzzle answer as it would be submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(pub String);
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer(n.to_string())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer(n.to_string())
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        Answer(n.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer(s)
+    }
+}
+
+/// One day's puzzle, solved from input text without touching the filesystem.
+pub trait Solution {
+    type Input;
+
+    fn day(&self) -> u32;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> AocResult<Answer>;
+
+    fn part2(&self, input: &Self::Input) -> AocResult<Answer>;
+
+    /// Both parts at once. Override this when the parts can share a single
+    /// pass over the input; the runner calls it whenever both are wanted.
+    fn solve_both(&self, input: &Self::Input) -> AocResult<(Answer, Answer)> {
+        Ok((self.part1(input)?, self.part2(input)?))
+    }
+
+    /// Names of the `Params` this day reads; any others are rejected.
+    fn param_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// `part1` adjusted by `params`. Days with knobs override this.
+    fn part1_with(&self, input: &Self::Input, _params: &Params) -> AocResult<Answer> {
+        self.part1(input)
+    }
+
+    /// `part2` adjusted by `params`. Days with knobs override this.
+    fn part2_with(&self, input: &Self::Input, _params: &Params) -> AocResult<Answer> {
+        self.part2(input)
+    }
+
+    /// Both parts adjusted by `params`, sharing work via `solve_both` when
+    /// there are none.
+    fn solve_both_with(&self, input: &Self::Input, params: &Params) -> AocResult<(Answer, Answer)> {
+        if params.is_empty() {
+            return self.solve_both(input);
+        }
+        Ok((
+            self.part1_with(input, params)?,
+            self.part2_with(input, params)?,
+        ))
+    }
+}
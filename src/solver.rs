@@ -0,0 +1,201 @@
+use std::any::Any;
+
+use clap::{CommandFactory, FromArgMatches};
+
+use crate::days::day01::{self, Day01};
+use crate::days::day02::{self, Day02};
+use crate::days::day03::{self, Day03};
+use crate::describe::Description;
+use crate::error::AocError;
+use crate::params::Params;
+use crate::result::AocResult;
+use crate::run::{Part, solve_parts_timed};
+use crate::solution::{Answer, Solution};
+use crate::timing::Timings;
+
+/// Input parsed by a `Solver`, only usable with the solver that made it.
+pub struct Parsed {
+    day: u32,
+    input: Box<dyn Any + Send + Sync>,
+}
+
+impl Parsed {
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+}
+
+/// A day's `Solution` with its input type hidden, so every day can be kept
+/// in one registry and driven by the same runner, tests and benchmarks.
+/// Implemented for every `Solution`.
+pub trait Solver: Send + Sync {
+    fn day(&self) -> u32;
+
+    fn parse(&self, input: &str) -> AocResult<Parsed>;
+
+    fn part1(&self, input: &Parsed) -> AocResult<Answer>;
+
+    fn part2(&self, input: &Parsed) -> AocResult<Answer>;
+
+    /// Parses `input` and solves `part` with `params`, as `solve_parts_timed`.
+    fn solve(&self, input: &str, part: Part, params: &Params) -> AocResult<(Vec<Answer>, Timings)>;
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Send + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Parsed> {
+        Ok(Parsed {
+            day: Solution::day(self),
+            input: Box::new(Solution::parse(self, input)?),
+        })
+    }
+
+    fn part1(&self, input: &Parsed) -> AocResult<Answer> {
+        Solution::part1(self, downcast(self, input)?)
+    }
+
+    fn part2(&self, input: &Parsed) -> AocResult<Answer> {
+        Solution::part2(self, downcast(self, input)?)
+    }
+
+    fn solve(&self, input: &str, part: Part, params: &Params) -> AocResult<(Vec<Answer>, Timings)> {
+        solve_parts_timed(self, input, part, params)
+    }
+}
+
+fn downcast<'a, S>(solution: &S, parsed: &'a Parsed) -> AocResult<&'a S::Input>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    parsed.input.downcast_ref().ok_or_else(|| {
+        AocError::Unsupported(format!(
+            "input parsed for day {} given to day {}",
+            parsed.day,
+            solution.day()
+        ))
+    })
+}
+
+/// The solver for `day`, if it is implemented.
+pub fn find(day: u32) -> Option<&'static dyn Solver> {
+    command(day).map(|command| command.solver)
+}
+
+/// The implemented day numbers, in order.
+pub fn days() -> impl Iterator<Item = u32> {
+    COMMANDS.iter().map(|command| command.day)
+}
+
+/// Everything the tools know about one day: its solver, its description
+/// and its command line, run as `aoc <name>` with the same flags as the
+/// day's own binary.
+pub struct DayCommand {
+    pub day: u32,
+    pub name: &'static str,
+    /// The puzzle's title on the site.
+    pub title: &'static str,
+    pub about: &'static str,
+    pub solver: &'static dyn Solver,
+    /// The day's modes, flags and input grammar for `aoc25 describe`.
+    pub describe: fn() -> Description,
+    pub command: fn() -> clap::Command,
+    /// Runs the day, returning false if it failed a check such as a
+    /// benchmark baseline. Exits through clap if the flags don't parse.
+    pub run: fn(&clap::ArgMatches) -> AocResult<bool>,
+}
+
+/// Every implemented day, in day order. Add a day here to make it
+/// available everywhere.
+pub static COMMANDS: &[DayCommand] = &[
+    DayCommand {
+        day: 1,
+        name: "day01",
+        title: "Secret Entrance",
+        about: "Day 1: Secret Entrance",
+        solver: &Day01,
+        describe: day01::describe,
+        command: day01::Config::command,
+        run: |matches| {
+            day01::cli::run(day01::Config::from_arg_matches(matches).unwrap_or_else(|e| e.exit()))
+        },
+    },
+    DayCommand {
+        day: 2,
+        name: "day02",
+        title: "Gift Shop",
+        about: "Day 2: Gift Shop",
+        solver: &Day02,
+        describe: day02::describe,
+        command: day02::Config::command,
+        run: |matches| {
+            day02::cli::run(day02::Config::from_arg_matches(matches).unwrap_or_else(|e| e.exit()))
+        },
+    },
+    DayCommand {
+        day: 3,
+        name: "day03",
+        title: "Lobby",
+        about: "Day 3: Lobby",
+        solver: &Day03,
+        describe: day03::describe,
+        command: day03::Config::command,
+        run: |matches| {
+            day03::cli::run(day03::Config::from_arg_matches(matches).unwrap_or_else(|e| e.exit()))
+        },
+    },
+];
+
+/// Everything about `day`, if it is implemented.
+pub fn command(day: u32) -> Option<&'static DayCommand> {
+    COMMANDS.iter().find(|command| command.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY01: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<u32> = days().collect();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(find(26).is_none());
+        for command in COMMANDS {
+            assert_eq!(command.solver.day(), command.day);
+            assert_eq!(command.name, format!("day{:02}", command.day));
+            assert_eq!(
+                command.about,
+                format!("Day {}: {}", command.day, command.title)
+            );
+            assert_eq!((command.describe)().day, command.day);
+        }
+        assert_eq!(command(2).map(|c| c.title), Some("Gift Shop"));
+        assert!(command(26).is_none());
+        let day01 = find(1).expect("day 1");
+        let parsed = day01.parse(DAY01).expect("parsed");
+        assert_eq!(parsed.day(), 1);
+        assert_eq!(day01.part1(&parsed).expect("part 1").to_string(), "3");
+        assert_eq!(day01.part2(&parsed).expect("part 2").to_string(), "6");
+        assert_eq!(
+            day01
+                .solve(DAY01, Part::Both, &Params::new())
+                .expect("both")
+                .0,
+            vec![Answer::from(3u32), Answer::from(6u32)]
+        );
+        let day03 = find(3).expect("day 3");
+        assert!(matches!(
+            day03.part1(&parsed),
+            Err(AocError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{self, Answers};
+use crate::error::AocError;
+use crate::input;
+use crate::result::AocResult;
+use crate::run::{self, Part};
+
+/// What adventofcode.com said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+    /// Submitted too soon after a wrong answer. Holds the time left as the
+    /// site words it, e.g. `4m 51s`, if it said.
+    Wait(Option<String>),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// A response none of the above matched.
+    Unknown,
+}
+
+impl Verdict {
+    /// The verdict in the `<article>` of the page returned after posting an
+    /// answer.
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            let left = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(left, _)| left.to_string());
+            Verdict::Wait(left)
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether resubmitting the same answer would get the same verdict.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+        )
+    }
+
+    /// The name kept in the submissions file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait(_) => "wait",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "wait" => Verdict::Wait(None),
+            "wrong-level" => Verdict::WrongLevel,
+            "unknown" => Verdict::Unknown,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(Some(left)) => write!(f, "not checked, wait {}", left),
+            Verdict::Wait(None) => write!(f, "not checked, submitted too recently"),
+            Verdict::WrongLevel => write!(f, "not checked, the part is solved or locked"),
+            Verdict::Unknown => write!(f, "not recognised in the response"),
+        }
+    }
+}
+
+/// An answer submitted for one part and the verdict it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    /// A line of the submissions file, e.g. `1\t1227775554\tcorrect`.
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}", self.part, self.answer, self.verdict.name())
+    }
+
+    fn from_line(line: &str) -> AocResult<Self> {
+        let invalid = || AocError::ParseError(format!("invalid submission '{}'", line));
+        let mut fields = line.split('\t');
+        let (Some(part), Some(answer), Some(verdict), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid());
+        };
+        Ok(Submission {
+            part: part.parse().map_err(|_| invalid())?,
+            answer: answer.to_string(),
+            verdict: Verdict::from_name(verdict).ok_or_else(invalid)?,
+        })
+    }
+}
+
+/// Where the submissions for `day` are recorded, oldest first.
+pub fn submissions_path(day: u32) -> PathBuf {
+    submissions_path_in(Path::new("data"), day)
+}
+
+/// `submissions_path` under `dir` instead of `data/`.
+pub fn submissions_path_in(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("day{:02}/submissions.txt", day))
+}
+
+/// The submissions recorded in `path`, or none if it doesn't exist.
+pub fn history(path: &Path) -> AocResult<Vec<Submission>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => text.lines().map(Submission::from_line).collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(AocError::io(path, e)),
+    }
+}
+
+/// An earlier submission of `answer` to `part` whose verdict still holds.
+pub fn recorded<'a>(history: &'a [Submission], part: u32, answer: &str) -> Option<&'a Submission> {
+    history
+        .iter()
+        .rev()
+        .find(|s| s.part == part && s.answer == answer && s.verdict.is_final())
+}
+
+/// Which parts of a day have been solved on the site, i.e. have had an
+/// answer judged correct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stars {
+    pub part1: bool,
+    pub part2: bool,
+}
+
+impl Stars {
+    /// The stars earned by the correct answers in `history`.
+    pub fn from_history(history: &[Submission]) -> Self {
+        let solved = |part| {
+            history
+                .iter()
+                .any(|s| s.part == part && s.verdict == Verdict::Correct)
+        };
+        Stars {
+            part1: solved(1),
+            part2: solved(2),
+        }
+    }
+
+    /// The stars for `day` from its submissions file under `dir`.
+    pub fn load(dir: &Path, day: u32) -> AocResult<Self> {
+        Ok(Self::from_history(&history(&submissions_path_in(
+            dir, day,
+        ))?))
+    }
+
+    pub fn count(&self) -> u32 {
+        self.part1 as u32 + self.part2 as u32
+    }
+}
+
+/// A `*` per solved part and a `.` per unsolved one, e.g. `*.`.
+impl fmt::Display for Stars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let star = |solved| if solved { '*' } else { '.' };
+        write!(f, "{}{}", star(self.part1), star(self.part2))
+    }
+}
+
+fn record(path: &Path, submission: &Submission) -> AocResult<()> {
+    let io_error = |e: std::io::Error| AocError::io(path, e);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)?;
+    writeln!(file, "{}", submission.to_line()).map_err(io_error)
+}
+
+/// Solves `part` of `day` from its input file and posts the answer, then
+/// records the verdict, adding a correct answer to the verified answers.
+/// If the same answer already got a verdict that still holds, that is
+/// returned without posting. Returns the submission and whether it was
+/// posted.
+pub fn submit(day: u32, part: Part) -> AocResult<(Submission, bool)> {
+    let number = match part {
+        Part::One => 1,
+        Part::Two => 2,
+        Part::Both => {
+            return Err(AocError::Unsupported(
+                "submit one part at a time".to_string(),
+            ));
+        }
+    };
+    let input_path = input::input_path(day);
+    let input = std::fs::read_to_string(&input_path).map_err(|e| AocError::io(input_path, e))?;
+    let answer = run::solve(day, part, &input)?.0;
+
+    let path = submissions_path(day);
+    if let Some(previous) = recorded(&history(&path)?, number, &answer) {
+        return Ok((previous.clone(), false));
+    }
+    let level = number.to_string();
+    let page = input::request(
+        &format!("day/{}/answer", day),
+        &[("level", &level), ("answer", &answer)],
+    )?;
+    let submission = Submission {
+        part: number,
+        answer,
+        verdict: Verdict::parse(&page),
+    };
+    record(&path, &submission)?;
+    if submission.verdict == Verdict::Correct {
+        let answers_path = Path::new(answers::ANSWERS_PATH);
+        let mut answers = Answers::load(answers_path)?;
+        answers.record(day, number, &submission.answer);
+        answers.save(answers_path)?;
+    }
+    Ok((submission, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer. If you're stuck...")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently. You have 4m 51s left to wait."
+            )),
+            Verdict::Wait(Some("4m 51s".to_string()))
+        );
+        assert_eq!(
+            Verdict::parse(&page("You don't seem to be solving the right level.")),
+            Verdict::WrongLevel
+        );
+        assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_history() {
+        let path = std::env::temp_dir().join(format!("aoc25-submissions-{}", std::process::id()));
+        assert!(history(&path).expect("no history").is_empty());
+        let submissions = [
+            Submission {
+                part: 1,
+                answer: "10".to_string(),
+                verdict: Verdict::TooLow,
+            },
+            Submission {
+                part: 1,
+                answer: "12".to_string(),
+                verdict: Verdict::Wait(Some("30s".to_string())),
+            },
+            Submission {
+                part: 1,
+                answer: "12".to_string(),
+                verdict: Verdict::Correct,
+            },
+        ];
+        for submission in &submissions {
+            record(&path, submission).expect("recorded");
+        }
+        let history = history(&path).expect("history");
+        assert_eq!(history[1].verdict, Verdict::Wait(None));
+        assert_eq!(recorded(&history, 1, "10"), Some(&submissions[0]));
+        assert_eq!(recorded(&history, 1, "12"), Some(&submissions[2]));
+        assert_eq!(recorded(&history, 2, "12"), None);
+        std::fs::remove_file(&path).expect("clean up");
+        assert!(Submission::from_line("1\t12").is_err());
+        assert!(Submission::from_line("1\t12\tmaybe").is_err());
+    }
+
+    #[test]
+    fn test_stars() {
+        let submission = |part, verdict| Submission {
+            part,
+            answer: "12".to_string(),
+            verdict,
+        };
+        let none = Stars::from_history(&[submission(1, Verdict::TooLow)]);
+        assert_eq!((none.count(), none.to_string()), (0, "..".to_string()));
+        let one = Stars::from_history(&[
+            submission(1, Verdict::TooLow),
+            submission(1, Verdict::Correct),
+            submission(2, Verdict::Wait(None)),
+        ]);
+        assert_eq!((one.count(), one.to_string()), (1, "*.".to_string()));
+        let both = Stars::from_history(&[
+            submission(1, Verdict::Correct),
+            submission(2, Verdict::Correct),
+        ]);
+        assert_eq!(both.to_string(), "**");
+        let dir = std::env::temp_dir().join(format!("aoc25-stars-{}", std::process::id()));
+        assert_eq!(Stars::load(&dir, 1).ok(), Some(Stars::default()));
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use crate::geometry::{Direction, Point};
+
+/// Walker with a position and heading that remembers every point it has
+/// stood on, for puzzles phrased as "turn, then walk n steps".
+#[derive(Debug, Clone)]
+pub struct Turtle {
+    position: Point,
+    heading: Direction,
+    visited: HashSet<Point>,
+    first_revisit: Option<Point>,
+}
+
+impl Default for Turtle {
+    fn default() -> Self {
+        Turtle::new(Point::ORIGIN, Direction::Up)
+    }
+}
+
+impl Turtle {
+    pub fn new(position: Point, heading: Direction) -> Self {
+        Turtle {
+            position,
+            heading,
+            visited: HashSet::from([position]),
+            first_revisit: None,
+        }
+    }
+
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    pub fn heading(&self) -> Direction {
+        self.heading
+    }
+
+    pub fn turn_left(&mut self) {
+        self.heading = self.heading.turn_left();
+    }
+
+    pub fn turn_right(&mut self) {
+        self.heading = self.heading.turn_right();
+    }
+
+    pub fn turn_around(&mut self) {
+        self.heading = self.heading.reverse();
+    }
+
+    pub fn face(&mut self, heading: Direction) {
+        self.heading = heading;
+    }
+
+    /// Walk `distance` steps along the current heading, visiting every
+    /// point on the way.
+    pub fn forward(&mut self, distance: u64) {
+        for _ in 0..distance {
+            self.enter(self.position + self.heading.delta());
+        }
+    }
+
+    /// Like `forward`, but returns the points entered in order.
+    pub fn trace(&mut self, distance: u64) -> Vec<Point> {
+        (0..distance)
+            .map(|_| {
+                let next = self.position + self.heading.delta();
+                self.enter(next);
+                next
+            })
+            .collect()
+    }
+
+    /// Move straight to `position` without visiting anything in between.
+    pub fn jump(&mut self, position: Point) {
+        self.enter(position);
+    }
+
+    pub fn visited(&self) -> &HashSet<Point> {
+        &self.visited
+    }
+
+    pub fn has_visited(&self, point: Point) -> bool {
+        self.visited.contains(&point)
+    }
+
+    /// The first point the turtle stood on twice, if any.
+    pub fn first_revisit(&self) -> Option<Point> {
+        self.first_revisit
+    }
+
+    /// Manhattan distance from `from` to the current position.
+    pub fn distance_from(&self, from: Point) -> u64 {
+        self.position.manhattan(from)
+    }
+
+    fn enter(&mut self, point: Point) {
+        self.position = point;
+        if !self.visited.insert(point) && self.first_revisit.is_none() {
+            self.first_revisit = Some(point);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_and_turns() {
+        let mut turtle = Turtle::default();
+        turtle.forward(3);
+        assert_eq!(turtle.position(), Point::new(0, -3));
+        turtle.turn_right();
+        assert_eq!(turtle.heading(), Direction::Right);
+        turtle.forward(2);
+        turtle.turn_around();
+        turtle.forward(1);
+        assert_eq!(turtle.position(), Point::new(1, -3));
+        assert_eq!(turtle.distance_from(Point::ORIGIN), 4);
+        assert_eq!(turtle.visited().len(), 6);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut turtle = Turtle::new(Point::new(5, 5), Direction::Left);
+        assert_eq!(turtle.trace(2), vec![Point::new(4, 5), Point::new(3, 5)]);
+        assert!(turtle.trace(0).is_empty());
+        assert!(turtle.has_visited(Point::new(5, 5)));
+    }
+
+    #[test]
+    fn test_first_revisit() {
+        // R8, R4, R4, R8 crosses itself first at (4, 0).
+        let mut turtle = Turtle::new(Point::ORIGIN, Direction::Up);
+        for distance in [8, 4, 4, 8] {
+            turtle.turn_right();
+            turtle.forward(distance);
+        }
+        assert_eq!(turtle.first_revisit(), Some(Point::new(4, 0)));
+        turtle.jump(Point::ORIGIN);
+        assert_eq!(turtle.first_revisit(), Some(Point::new(4, 0)));
+    }
+}
@@ -0,0 +1,261 @@
+//! End-to-end runs of the built binaries: each test writes its inputs to a
+//! scratch directory, runs a binary or `aoc` subcommand on them and checks
+//! the exit status and the shape of what it printed.
+
+use std::path::{Path, PathBuf};
+
+use aoc25::error::{EXIT_INVALID, EXIT_IO, EXIT_PARSE};
+use assert_cmd::Command;
+use predicates::prelude::*;
+use predicates::str::{contains, ends_with, starts_with};
+
+const DAY01: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+const DAY02: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+                     1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+                     824824821-824824827,2121212118-2121212124\n";
+const DAY03: &str = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+
+/// A fresh directory laid out like `data/`, with `input.txt` for each day.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc25-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for (day, input) in [(1, DAY01), (2, DAY02), (3, DAY03)] {
+        let day_dir = dir.join(format!("day{:02}", day));
+        std::fs::create_dir_all(&day_dir).expect("scratch directory");
+        std::fs::write(day_dir.join("input.txt"), input).expect("scratch input");
+    }
+    dir
+}
+
+fn input(dir: &Path, day: u32) -> String {
+    dir.join(format!("day{:02}/input.txt", day))
+        .display()
+        .to_string()
+}
+
+fn bin(name: &str) -> Command {
+    Command::cargo_bin(name).expect("binary built")
+}
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_default_runs_print_the_answer() {
+    let dir = scratch("default");
+    let cases = [
+        ("day01", 1, "Zero count: 3\n"),
+        (
+            "day02",
+            2,
+            "Total invalid IDs: 8\nSum of invalid IDs: 1227775554\n",
+        ),
+        ("day03", 3, "Total jolt from all battery lines: 357\n"),
+    ];
+    for (binary, day, expected) in cases {
+        bin(binary)
+            .args(["-i", &input(&dir, day)])
+            .assert()
+            .success()
+            .stdout(expected);
+    }
+}
+
+#[test]
+fn test_aoc_subcommands_match_the_day_binaries() {
+    let dir = scratch("subcommand");
+    let day01 = input(&dir, 1);
+    let args = ["-i", day01.as_str(), "--mode", "during"];
+    let own = bin("day01").args(args).output().expect("day01 runs");
+    bin("aoc")
+        .arg("day01")
+        .args(args)
+        .assert()
+        .success()
+        .stdout(stdout(&own))
+        .stdout("Zero count: 6\n");
+
+    bin("aoc")
+        .args(["day03", "-i", &input(&dir, 3), "--part", "both"])
+        .assert()
+        .stdout("Part 1: 357\nPart 2: 3121910778619\n");
+}
+
+#[test]
+fn test_json_output_is_one_object() {
+    let dir = scratch("json");
+    let output = bin("day01")
+        .args(["-i", &input(&dir, 1), "--output", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let text = stdout(&output);
+    let line = text.strip_suffix('\n').expect("newline");
+    assert!(!line.contains('\n'));
+    assert!(line.starts_with("{\"day\":1,\"part\":1,\"answer\":3,\"elapsed_ms\":"));
+    assert!(line.contains("\"mode\":\"after\","));
+    assert!(line.contains("\"phases\":[{\"name\":\"read\""));
+    assert!(line.ends_with("]}"));
+
+    let output = bin("day03")
+        .args(["-i", &input(&dir, 3), "--part", "both", "--output", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let text = stdout(&output);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("{\"day\":3,\"part\":1,\"answer\":357,\"elapsed_ms\":"));
+    assert!(lines[1].starts_with("{\"day\":3,\"part\":2,\"answer\":3121910778619,"));
+    assert!(lines.iter().all(|line| line.ends_with('}')));
+}
+
+#[test]
+fn test_json_output_covers_every_mode() {
+    let dir = scratch("json-modes");
+    let json = |binary: &str, day: u32, args: &[&str]| {
+        let output = bin(binary)
+            .args(["-i", &input(&dir, day), "--output", "json"])
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .clone();
+        stdout(&output)
+    };
+    assert_eq!(
+        json("day01", 1, &["--first-zero"]),
+        "{\"day\":1,\"part\":1,\"first_zero\":\
+         {\"index\":2,\"instruction\":{\"operation\":\"R\",\"argument\":48}}}\n"
+    );
+    assert_eq!(
+        json("day01", 1, &["--locks"]),
+        "{\"day\":1,\"part\":1,\"locks\":{\"zero_counts\":[3],\"sum\":3,\"product\":3}}\n"
+    );
+    assert!(json("day01", 1, &["--stats"]).starts_with("{\"day\":1,\"part\":1,\"stats\":{"));
+    assert!(
+        json("day02", 2, &["--list", "ids"])
+            .starts_with("{\"day\":2,\"part\":1,\"invalid_ids\":[11,22,99,1010,")
+    );
+    assert!(
+        json("day02", 2, &["--list", "ranges", "--mode", "multiple"])
+            .starts_with("{\"day\":2,\"part\":2,\"invalid_ranges\":[{\"start\":11,\"end\":11},")
+    );
+    let bench = json(
+        "day01",
+        1,
+        &["--bench", "--iterations", "2", "--warmup", "0"],
+    );
+    assert!(bench.starts_with("{\"bench\":{\"day\":1,\"stages\":{\"parse\":{"));
+    assert_eq!(bench.lines().count(), 1);
+}
+
+#[test]
+fn test_missing_input_exits_with_io_status() {
+    let dir = scratch("missing");
+    let missing = dir.join("day09/input.txt").display().to_string();
+    for binary in ["day01", "day02"] {
+        bin(binary)
+            .args(["-i", &missing])
+            .assert()
+            .code(EXIT_IO)
+            .stdout("")
+            .stderr(starts_with("error: IO error: ").and(contains(missing.as_str())));
+    }
+}
+
+#[test]
+fn test_missing_baseline_exits_with_io_status() {
+    let dir = scratch("baseline");
+    bin("day01")
+        .args(["-i", &input(&dir, 1), "-b", "--compare-baseline", "missing"])
+        .args(["--iterations", "1", "--warmup", "0"])
+        .current_dir(&dir)
+        .assert()
+        .code(EXIT_IO)
+        .stderr(starts_with("error: IO error: ").and(contains("missing.tsv")));
+}
+
+#[test]
+fn test_bad_input_exits_with_parse_status() {
+    let dir = scratch("bad");
+    let bad = dir.join("bad.txt");
+    std::fs::write(&bad, "L68\nX1\n").expect("bad input");
+    bin("day01")
+        .args(["-i", &bad.display().to_string()])
+        .assert()
+        .code(EXIT_PARSE)
+        .stderr(format!(
+            "error: {}:2:1: expected a keyword, found 'X'\n  |\n2 | X1\n  | ^\n",
+            bad.display()
+        ));
+
+    std::fs::write(&bad, "11-22,-15\n").expect("bad input");
+    bin("day02")
+        .args(["-i", &bad.display().to_string()])
+        .assert()
+        .code(EXIT_PARSE)
+        .stderr(contains(":1:7: expected a digit, found '-'"));
+}
+
+#[test]
+fn test_invalid_mode_is_a_usage_error() {
+    let dir = scratch("mode");
+    bin("day02")
+        .args(["-i", &input(&dir, 2), "--mode", "three"])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(contains("possible values: two, multiple"));
+}
+
+#[test]
+fn test_target_off_the_dial_exits_with_invalid_status() {
+    let dir = scratch("target");
+    bin("day01")
+        .args(["-i", &input(&dir, 1), "--target", "7", "--dial-size", "5"])
+        .assert()
+        .code(EXIT_INVALID)
+        .stdout("")
+        .stderr("error: Invalid argument: --target 7 is off a dial numbered 0 to 4\n");
+}
+
+#[test]
+fn test_invalid_algorithm_is_a_usage_error() {
+    let dir = scratch("algorithm");
+    bin("day02")
+        .args(["-i", &input(&dir, 2), "--algorithm", "bogus"])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(contains("possible values: naive, fast, auto"));
+}
+
+#[test]
+fn test_all_checks_answers() {
+    let dir = scratch("all");
+    let answers = dir.join("answers.toml").display().to_string();
+    let all = |extra: &[&str]| {
+        let dir = dir.display().to_string();
+        let args = ["all", "--dir", &dir, "--answers", &answers, "--no-timings"];
+        bin("aoc").args(args).args(extra).assert()
+    };
+
+    let recorded = format!("Recorded 6 answers in {}\n", answers);
+    all(&["--record"]).success().stdout(
+        starts_with("Day  Part 1      Part 2\n1    3           6\n").and(ends_with(recorded)),
+    );
+
+    all(&[])
+        .success()
+        .stdout(ends_with("6 of 6 answers verified\n"));
+
+    let recorded = std::fs::read_to_string(&answers).expect("answers");
+    std::fs::write(&answers, recorded.replace("\"3\"", "\"4\"")).expect("answers");
+    all(&[])
+        .code(1)
+        .stdout(contains("Day 1 part 1 regressed: expected 4, got 3\n"));
+}
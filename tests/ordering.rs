@@ -0,0 +1,80 @@
+//! Parallel code paths must print exactly what their sequential
+//! counterparts print. Each test renders both results the way the
+//! binaries do and compares the text byte for byte.
+
+use aoc25::days::{day02, day03};
+use aoc25::progress::NoProgress;
+use aoc25::run::{Part, render_all, solve_all};
+
+const DAY01: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+const DAY02: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+                     1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+                     824824821-824824827,2121212118-2121212124\n";
+const DAY03: &str = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+
+#[test]
+fn test_day02_ranges_parallel_output_matches_sequential() {
+    let ranges = day02::parse_input(DAY02).expect("ranges");
+    for mode in [day02::Mode::Two, day02::Mode::Multiple] {
+        let found = |scan: day02::report::ScanReport| -> Vec<(u64, u64)> {
+            scan.ranges.iter().map(|r| (r.count, r.sum)).collect()
+        };
+        assert_eq!(
+            found(day02::calc_count_sum_parallel(&ranges, mode).expect("parallel")),
+            found(day02::calc_count_sum(&ranges, mode).expect("sequential"))
+        );
+        let sequential = day02::invalid_id_intervals(&ranges, mode).expect("sequential");
+        let parallel = day02::invalid_id_intervals_parallel(&ranges, mode).expect("parallel");
+        assert_eq!(parallel.to_string(), sequential.to_string());
+        let ids = |set: &aoc25::interval::IntervalSet| -> String {
+            set.values().map(|id| format!("{}\n", id)).collect()
+        };
+        assert_eq!(ids(&parallel), ids(&sequential));
+    }
+}
+
+#[test]
+fn test_day03_lines_parallel_output_matches_sequential() {
+    let lines = day03::parse_input(DAY03, day03::BlankLines::Error).expect("lines");
+    for mode in [day03::Mode::Two, day03::Mode::Twelve] {
+        for tie_break in [day03::TieBreak::Earliest, day03::TieBreak::Latest] {
+            let sequential = day03::calc_total_jolt_with_tie_break(
+                &lines,
+                mode,
+                tie_break,
+                &day03::alphabet::DECIMAL,
+                &mut NoProgress,
+            )
+            .expect("sequential");
+            let parallel =
+                day03::calc_total_jolt_parallel(&lines, mode, tie_break, &day03::alphabet::DECIMAL)
+                    .expect("parallel");
+            assert_eq!(format!("{}", parallel), format!("{}", sequential));
+        }
+    }
+}
+
+#[test]
+fn test_run_all_parallel_output_matches_sequential() {
+    let inputs = vec![
+        (1, DAY01.to_string()),
+        (2, DAY02.to_string()),
+        (3, DAY03.to_string()),
+        (4, "unknown\n".to_string()),
+        (1, DAY03.to_string()),
+    ];
+    for part in [Part::One, Part::Two, Part::Both] {
+        let sequential = render_all(&solve_all(&inputs, part, false), false);
+        let parallel = render_all(&solve_all(&inputs, part, true), false);
+        assert_eq!(parallel, sequential);
+    }
+    let text = render_all(&solve_all(&inputs, Part::Both, true), false);
+    assert!(text.starts_with(
+        "Day  Part 1      Part 2\n\
+         1    3           6\n\
+         2    1227775554  4174379265\n\
+         3    357         3121910778619\n\
+         4    failed      failed\n"
+    ));
+    assert!(text.contains("Day 4 failed: "));
+}